@@ -0,0 +1,261 @@
+import express, { type Request, type RequestHandler } from 'express'
+import cors from 'cors'
+import { optionalToken, requireToken } from './auth'
+import { MAX_LINK_TTL_SECONDS, config, isLinkTtl } from './config'
+import { PdfError, errorHandler, stage } from './errors'
+import { historyEnabled, listHistory } from './history'
+import { idempotencyClient, idempotent } from './idempotency'
+import { describeJob, findJob, startJob, subscribeJob } from './jobs'
+import { msgpackBody } from './msgpack'
+import { PAGED_POLYFILL, renderPdf, withPage, type RenderWarning } from './render'
+import { readStoredReport, recordReport, signedUrl, verifySignature } from './reports'
+import { readComparison, readRequest } from './request'
+import { brandProfiles, describeFile, describeResources, loadResources, watchResources } from './resources'
+import { assetId, deleteProfile, deleteTemplate, saveProfile, saveTemplate } from './store'
+import { traceRequests } from './telemetry'
+import { inspectLayout } from './validate'
+
+const MAX_WARNING_HEADER_LENGTH = 4000
+const DEFAULT_HISTORY_LIMIT = 50
+const MAX_HISTORY_LIMIT = 500
+
+let resources = await loadResources(config.assetsDir)
+if (config.watchAssets) {
+    watchResources(config.assetsDir, (next) => {
+        resources = next
+    })
+}
+// The gRPC front end renders with whatever the HTTP side last loaded.
+export const currentResources = () => resources
+const polyfill = await describeFile(PAGED_POLYFILL)
+
+const serializeWarnings = (warnings: RenderWarning[]) => JSON.stringify(warnings).replace(/[^\x20-\x7e]/g, '?')
+
+// Whole warnings are dropped from the end until the JSON fits, so the header always parses; how many were dropped is
+// sent alongside.
+const warningHeader = (warnings: RenderWarning[]) => {
+    let kept = warnings.length
+    while (kept > 0 && serializeWarnings(warnings.slice(0, kept)).length > MAX_WARNING_HEADER_LENGTH) kept--
+    return { value: serializeWarnings(warnings.slice(0, kept)), truncated: warnings.length - kept }
+}
+
+export const app = express()
+const corsOrigin = config.cors.origins.length ? config.cors.origins : config.cors.anyOrigin
+
+app.use(traceRequests)
+app.use(
+    cors({
+        origin: corsOrigin,
+        methods: config.cors.methods,
+        allowedHeaders: config.cors.headers,
+        exposedHeaders: [
+            'X-Report-Id',
+            'X-Report-Pages',
+            'X-Report-Bytes',
+            'X-Report-Sha256',
+            'X-Render-Ms',
+            'X-Render-Warning-Count',
+            'X-Render-Warnings',
+            'X-Render-Warnings-Truncated',
+            'Idempotent-Replayed',
+            'X-Report-Url',
+        ],
+    })
+)
+app.use(express.json({ limit: config.bodyLimit }))
+app.use(msgpackBody(config.bodyLimit))
+
+const admin = requireToken(config.adminToken)
+const renderer = optionalToken(config.renderToken)
+
+const RESPONSE_MODES = ['pdf', 'json']
+
+const caller = (req: Request) => idempotencyClient(req.get('authorization'), req.ip)
+
+// `?response=json` wraps the PDF in a JSON envelope for callers that can't consume a binary stream.
+const deliverPdf =
+    (scope: string, read: (body: unknown) => ReturnType<typeof readRequest>): RequestHandler =>
+    async (req, res) => {
+        const mode = typeof req.query.response === 'string' ? req.query.response : 'pdf'
+        if (!RESPONSE_MODES.includes(mode)) throw new PdfError('invalid_payload', `"response" must be one of ${RESPONSE_MODES.join(', ')}`)
+        const { html, ...options } = read(req.body)
+        const { value, replayed } = await idempotent(req.get('idempotency-key'), scope, caller(req), req.body, async () => {
+            const result = await renderPdf(html, { resources, ...options })
+            return { ...result, ...(await recordReport(mode === 'json' ? 'http-json' : 'http', req.body, result)) }
+        })
+        const { pdf, pages, renderMs, warnings, reportId, sha256, link } = value
+        if (replayed) res.setHeader('Idempotent-Replayed', 'true')
+        if (link) res.setHeader('X-Report-Url', link.url)
+        if (mode === 'json') {
+            res.json({ pdfBase64: Buffer.from(pdf).toString('base64'), pages, warnings, renderMs, reportId, url: link?.url })
+            return
+        }
+        res.setHeader('Content-Type', 'application/pdf')
+        res.setHeader('Content-Disposition', 'inline; filename="report.pdf"')
+        res.setHeader('X-Report-Id', reportId)
+        res.setHeader('X-Report-Pages', String(pages))
+        res.setHeader('X-Report-Bytes', String(pdf.byteLength))
+        res.setHeader('X-Report-Sha256', sha256)
+        res.setHeader('X-Render-Ms', String(renderMs))
+        res.setHeader('X-Render-Warning-Count', String(warnings.length))
+        if (warnings.length) {
+            const header = warningHeader(warnings)
+            res.setHeader('X-Render-Warnings', header.value)
+            if (header.truncated) res.setHeader('X-Render-Warnings-Truncated', String(header.truncated))
+        }
+        res.send(pdf)
+    }
+
+app.post('/pdf', renderer, deliverPdf('pdf', readRequest))
+
+// Current and previous payloads rendered as one side-by-side document with KPI and category deltas.
+app.post('/pdf/compare', renderer, deliverPdf('compare', readComparison))
+
+// Dry run: paginate with paged.js (always, so page boxes exist to inspect) and report layout problems without printing.
+app.post('/pdf/validate', renderer, async (req, res) => {
+    const { html, template, brandProfile } = readRequest(req.body)
+    const { result: report, warnings } = await withPage(html, { resources, template, brandProfile, forcePaged: true }, (page) =>
+        stage('layout_failed', () => page.evaluate(inspectLayout))
+    )
+    res.json({ ...report, renderWarnings: warnings })
+})
+
+// Async variant of POST /pdf for large reports: returns a job id immediately; progress streams over SSE.
+app.post('/jobs', renderer, async (req, res) => {
+    const { html, ...options } = readRequest(req.body)
+    const { value: id, replayed } = await idempotent(req.get('idempotency-key'), 'jobs', caller(req), req.body, async () =>
+        startJob(async (onProgress) => {
+            const result = await renderPdf(html, { resources, ...options, onProgress })
+            return { ...result, reportId: (await recordReport('job', req.body, result)).reportId }
+        })
+    )
+    if (replayed) res.setHeader('Idempotent-Replayed', 'true')
+    res.status(202).location(`/jobs/${id}`).json({ id, events: `/jobs/${id}/events`, pdf: `/jobs/${id}/pdf` })
+})
+
+app.get('/jobs/:id', renderer, (req, res) => {
+    res.json(describeJob(findJob(req.params.id)))
+})
+
+app.get('/jobs/:id/events', renderer, (req, res) => {
+    const job = findJob(req.params.id)
+    res.writeHead(200, { 'Content-Type': 'text/event-stream', 'Cache-Control': 'no-cache', Connection: 'keep-alive' })
+    const unsubscribe = subscribeJob(job, (event) => {
+        res.write(`event: ${event.type}\ndata: ${JSON.stringify(event)}\n\n`)
+        if (event.type !== 'progress') res.end()
+    })
+    req.on('close', unsubscribe)
+})
+
+app.get('/jobs/:id/pdf', renderer, (req, res) => {
+    const job = findJob(req.params.id)
+    if (!job.pdf) {
+        res.status(job.status === 'failed' ? 422 : 202).json(describeJob(job))
+        return
+    }
+    res.setHeader('Content-Type', 'application/pdf')
+    res.setHeader('Content-Disposition', 'inline; filename="report.pdf"')
+    res.send(job.pdf)
+})
+
+// Lists what was delivered, newest first, with fresh download links for reports that are still stored.
+app.get('/reports', admin, async (req, res) => {
+    if (!historyEnabled()) throw new PdfError('not_found', 'Report history is not enabled')
+    const { clientName, limit } = req.query
+    if (clientName != null && typeof clientName !== 'string') throw new PdfError('invalid_payload', '"clientName" must be a single value')
+    const count = limit == null ? DEFAULT_HISTORY_LIMIT : Number(limit)
+    if (!Number.isInteger(count) || count < 1 || count > MAX_HISTORY_LIMIT) {
+        throw new PdfError('invalid_payload', `"limit" must be an integer between 1 and ${MAX_HISTORY_LIMIT}`)
+    }
+    const entries = await listHistory({ clientName, limit: count })
+    res.json({ reports: entries.map((entry) => ({ ...entry, url: signedUrl(entry.reportId)?.url })) })
+})
+
+// Links are signed, so the client portal can hand them out without the service knowing who the viewer is.
+app.get('/reports/:id', async (req, res) => {
+    verifySignature(req.params.id, req.query.expires, req.query.sig)
+    const pdf = await readStoredReport(req.params.id)
+    res.setHeader('Content-Type', 'application/pdf')
+    res.setHeader('Content-Disposition', `inline; filename="report-${req.params.id}.pdf"`)
+    res.setHeader('Cache-Control', 'private, no-store')
+    res.send(pdf)
+})
+
+// Fresh links for an already stored report, e.g. when the original one expired.
+app.post('/reports/:id/links', admin, async (req, res) => {
+    const { ttlSeconds } = (req.body ?? {}) as { ttlSeconds?: unknown }
+    if (ttlSeconds != null && !isLinkTtl(ttlSeconds)) {
+        throw new PdfError('invalid_payload', `"ttlSeconds" must be a whole number of seconds between 1 and ${MAX_LINK_TTL_SECONDS}`)
+    }
+    await readStoredReport(req.params.id)
+    const link = signedUrl(req.params.id, ttlSeconds ?? undefined)
+    if (!link) throw new PdfError('not_found', 'Report links are not enabled')
+    res.status(201).json(link)
+})
+
+app.get('/admin/resources', admin, (_req, res) => {
+    res.json({
+        assetsDir: config.assetsDir,
+        ...describeResources(resources),
+        polyfills: [polyfill],
+    })
+})
+
+// Writes go to the assets directory, so profiles and templates survive restarts and the watcher stays in sync.
+const reload = async () => {
+    resources = await loadResources(config.assetsDir)
+}
+
+const findProfile = (id: string) => {
+    const profile = brandProfiles(resources)[assetId(id)]
+    if (!profile) throw new PdfError('not_found', `Unknown brand profile "${id}"`)
+    return profile
+}
+
+const findTemplate = (id: string) => {
+    const template = resources.templates.find((candidate) => candidate.name === assetId(id))
+    if (!template) throw new PdfError('not_found', `Unknown template "${id}"`)
+    return template
+}
+
+app.get('/profiles', admin, (_req, res) => {
+    res.json({ profiles: Object.keys(brandProfiles(resources)) })
+})
+
+app.get('/profiles/:id', admin, (req, res) => {
+    res.json(findProfile(req.params.id))
+})
+
+app.put('/profiles/:id', admin, async (req, res) => {
+    const id = assetId(req.params.id)
+    const created = !brandProfiles(resources)[id]
+    await saveProfile(config.assetsDir, id, req.body)
+    await reload()
+    res.status(created ? 201 : 200).json(findProfile(id))
+})
+
+app.delete('/profiles/:id', admin, async (req, res) => {
+    await deleteProfile(config.assetsDir, req.params.id, findProfile(req.params.id))
+    await reload()
+    res.status(204).end()
+})
+
+app.get('/templates/:id', admin, (req, res) => {
+    res.type('text/css').send(findTemplate(req.params.id).data)
+})
+
+app.put('/templates/:id', admin, express.text({ type: 'text/css', limit: config.bodyLimit }), async (req, res) => {
+    const id = assetId(req.params.id)
+    const created = !resources.templates.some((candidate) => candidate.name === id)
+    await saveTemplate(config.assetsDir, id, req.body)
+    await reload()
+    res.status(created ? 201 : 200).type('text/css').send(findTemplate(id).data)
+})
+
+app.delete('/templates/:id', admin, async (req, res) => {
+    await deleteTemplate(config.assetsDir, findTemplate(req.params.id).name)
+    await reload()
+    res.status(204).end()
+})
+
+app.use(errorHandler)
@@ -0,0 +1,41 @@
+import type { PDFDocument } from 'pdf-lib'
+import { PdfError } from './errors'
+
+export type Attachment = {
+    name: string
+    content: string
+    mimeType?: string
+    description?: string
+}
+
+const MAX_ATTACHMENTS = 8
+
+export function readAttachments(value: unknown): Attachment[] {
+    if (value == null) return []
+    if (!Array.isArray(value) || value.length > MAX_ATTACHMENTS) {
+        throw new PdfError('invalid_payload', `"attachments" must be an array of at most ${MAX_ATTACHMENTS} files`)
+    }
+    return value.map((item, index) => {
+        const { name, content, mimeType, description } = (item ?? {}) as Record<string, unknown>
+        if (typeof name !== 'string' || !name.trim() || typeof content !== 'string') {
+            throw new PdfError('invalid_payload', `attachments[${index}] needs a "name" and string "content"`)
+        }
+        return {
+            name,
+            content,
+            mimeType: typeof mimeType === 'string' ? mimeType : undefined,
+            description: typeof description === 'string' ? description : undefined,
+        }
+    })
+}
+
+export async function embedAttachments(document: PDFDocument, attachments: Attachment[], now: Date) {
+    for (const attachment of attachments) {
+        await document.attach(new TextEncoder().encode(attachment.content), attachment.name, {
+            mimeType: attachment.mimeType,
+            description: attachment.description,
+            creationDate: now,
+            modificationDate: now,
+        })
+    }
+}
@@ -0,0 +1,50 @@
+import { createHash, randomUUID } from 'node:crypto'
+import { appendFile } from 'node:fs/promises'
+import { config } from './config'
+import { payloadHash } from './errors'
+import { payloadMetadata } from './reporting'
+
+export type Delivery = 'http' | 'http-json' | 'job' | 'grpc' | 'grpc-batch'
+
+export type AuditRecord = {
+    reportId: string
+    timestamp: string
+    tenant?: string
+    template?: string
+    payloadHash: string
+    pages: number
+    bytes: number
+    sha256: string
+    delivery: Delivery
+}
+
+// Appends are chained so concurrent renders never interleave partial lines in the file.
+let pending = Promise.resolve()
+
+const append = (record: AuditRecord) => {
+    const line = `${JSON.stringify(record)}\n`
+    const file = config.audit.file
+    if (!file) {
+        console.log(line.trimEnd())
+        return
+    }
+    pending = pending.then(() => appendFile(file, line)).catch((error) => console.error('failed to write audit record', error, line))
+}
+
+// Every report that leaves the service gets an id and an audit line; the payload itself is only ever hashed.
+export function auditReport(delivery: Delivery, body: unknown, pdf: Uint8Array, pages: number) {
+    const { tenant, template } = payloadMetadata(body)
+    const record: AuditRecord = {
+        reportId: randomUUID(),
+        timestamp: new Date().toISOString(),
+        tenant,
+        template,
+        payloadHash: payloadHash(body),
+        pages,
+        bytes: pdf.byteLength,
+        sha256: createHash('sha256').update(pdf).digest('hex'),
+        delivery,
+    }
+    append(record)
+    return record
+}
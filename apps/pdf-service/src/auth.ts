@@ -0,0 +1,20 @@
+import { timingSafeEqual } from 'node:crypto'
+import type { RequestHandler } from 'express'
+import { PdfError } from './errors'
+
+export const tokenMatches = (provided: string, expected: string) => {
+    const a = Buffer.from(provided)
+    const b = Buffer.from(expected)
+    return a.length === b.length && timingSafeEqual(a, b)
+}
+
+export const requireToken =
+    (token: string | undefined): RequestHandler =>
+    (req, _res, next) => {
+        const provided = req.get('authorization')?.replace(/^Bearer\s+/i, '') ?? ''
+        if (!token || !tokenMatches(provided, token)) throw new PdfError('unauthorized', 'Missing or invalid bearer token')
+        next()
+    }
+
+// Render endpoints stay open when no token is configured, for deployments that only expose them on a private network.
+export const optionalToken = (token: string | undefined): RequestHandler => (token ? requireToken(token) : (_req, _res, next) => next())
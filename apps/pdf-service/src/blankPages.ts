@@ -0,0 +1,51 @@
+import { PDFArray, type PDFDocument, type PDFPage, PDFRawStream, decodePDFRawStream } from 'pdf-lib'
+
+const TOKEN = /\((?:\\[\s\S]|[^\\)])*\)|<<|>>|<[\da-f\s]*>|\/[^\s()<>[\]{}/%]*|[[\]{}]|%[^\r\n]*|[^\s()<>[\]{}/%]+/gi
+const OPERAND = /^(?:[+-]?(?:\d+\.?\d*|\.\d+)|[(<[\]/%].*)$/
+// Text, XObjects, shadings, inline images and strokes always leave marks.
+const PAINTS = new Set(['Tj', 'TJ', "'", '"', 'Do', 'sh', 'BI', 'S', 's', 'B', 'B*', 'b', 'b*'])
+const FILLS = new Set(['f', 'F', 'f*'])
+
+// Fills count too (a page can be nothing but a vector chart), except in white: Chrome paints a white background
+// rectangle on every page, the empty ones included.
+const marks = (content: string) => {
+    const saved: boolean[] = []
+    let white = false
+    let operands: string[] = []
+    for (const [token] of content.matchAll(TOKEN)) {
+        if (OPERAND.test(token)) {
+            operands.push(token)
+            continue
+        }
+        if (PAINTS.has(token) || (FILLS.has(token) && !white)) return true
+        if (token === 'q') saved.push(white)
+        else if (token === 'Q') white = saved.pop() ?? false
+        else if (['g', 'rg', 'sc', 'scn'].includes(token)) white = operands.length > 0 && operands.every((operand) => Number(operand) === 1)
+        else if (token === 'k') white = operands.length === 4 && operands.every((operand) => Number(operand) === 0)
+        operands = []
+    }
+    return false
+}
+
+const contentStreams = (page: PDFPage) => {
+    const contents = page.node.Contents()
+    if (!contents) return []
+    const items = contents instanceof PDFArray ? contents.asArray() : [contents]
+    return items.map((item) => page.doc.context.lookup(item)).filter((item) => item instanceof PDFRawStream)
+}
+
+const isBlank = (page: PDFPage) => {
+    if (page.node.Annots()?.size()) return false
+    return contentStreams(page).every((stream) => {
+        const text = Buffer.from(decodePDFRawStream(stream).decode()).toString('latin1')
+        return !marks(text)
+    })
+}
+
+// A break landing exactly on a page boundary makes Chrome emit an empty page; drop those, but never the only page.
+export function removeBlankPages(document: PDFDocument) {
+    const blank = document.getPages().flatMap((page, index) => (isBlank(page) ? [index] : []))
+    if (blank.length === document.getPageCount()) blank.shift()
+    for (const index of blank.reverse()) document.removePage(index)
+    return blank.length
+}
@@ -0,0 +1,90 @@
+import type { Page } from 'puppeteer'
+import { PdfError } from './errors'
+
+export type BrandProfile = {
+    name?: string
+    colors?: { primary?: string; accent?: string }
+    // A font family from assets/fonts, referenced by file name.
+    font?: string
+    // Image file next to the profile, e.g. profiles/smile-co.svg.
+    logo?: string
+    footer?: string
+    // Disclaimer set after the report's own legal text, or as the last section when it has none; blank lines separate clauses.
+    legal?: string
+}
+
+// Logo bytes are inlined at load time so a render never touches the assets directory.
+export type LoadedBrandProfile = BrandProfile & { logoDataUrl?: string }
+
+const cssString = (value: string) => `"${value.replace(/["\\]/g, '\\$&').replace(/[\r\n]+/g, ' ')}"`
+
+const cssValue = (value: string) => value.replace(/[;{}<>]/g, '')
+
+export function parseBrandProfile(file: string, text: string): BrandProfile {
+    try {
+        return JSON.parse(text) as BrandProfile
+    } catch (error) {
+        throw new Error(`Brand profile ${file} is not valid JSON`, { cause: error })
+    }
+}
+
+// Custom properties are set with !important so the stored profile wins over whatever colors the caller sent.
+export function brandCss(profile: BrandProfile) {
+    const variables = [
+        profile.font && `--rf-brand-font: ${cssString(profile.font)}, system-ui, sans-serif !important;`,
+        profile.colors?.primary && `--rf-brand-primary: ${cssValue(profile.colors.primary)} !important;`,
+        profile.colors?.accent && `--rf-brand-accent: ${cssValue(profile.colors.accent)} !important;`,
+    ].filter(Boolean)
+    const footer = profile.footer
+        ? `@page { @bottom-center { content: ${cssString(profile.footer)}; font-size:8px; color:#64748b; vertical-align:top; padding-top:2mm; } }`
+        : ''
+    return `:root { ${variables.join(' ')} }\n${footer}`
+}
+
+// The payload may name its profile explicitly; otherwise the report template records it on <html data-brand-profile>.
+export async function resolveBrandProfile(page: Page, profiles: Record<string, LoadedBrandProfile>, requested?: string) {
+    const id = requested ?? (await page.$eval('html', (html) => html.dataset.brandProfile))
+    if (!id) return undefined
+    const profile = profiles[id]
+    if (!profile) throw new PdfError('invalid_payload', `Unknown brand profile "${id}"`)
+    return profile
+}
+
+// Mirrors the template's Legal component, which styles the section and keeps it on the final page.
+const appendLegal = (legal: string) =>
+    // Each language edition ends with its own copy.
+    document.querySelectorAll('main').forEach((main) => {
+        const section =
+            main.querySelector(':scope > .legal') ??
+            main.appendChild(Object.assign(document.createElement('section'), { className: 'legal', ariaLabel: 'Legal' }))
+        legal
+            .split(/\n\s*\n/)
+            .map((clause) => clause.replace(/\s+/g, ' ').trim())
+            .filter(Boolean)
+            .forEach((clause) => {
+                const paragraph = document.createElement('p')
+                paragraph.textContent = clause
+                section.append(paragraph)
+            })
+    })
+
+export async function applyBrandProfile(page: Page, profile: LoadedBrandProfile) {
+    await page.addStyleTag({ content: brandCss(profile) })
+    if (profile.legal?.trim()) await page.evaluate(appendLegal, profile.legal)
+    if (!profile.logoDataUrl) return
+    await page.evaluate(
+        (src, alt) =>
+            // One header per language edition.
+            document.querySelectorAll('.header-brand').forEach((brand) => {
+                brand.querySelector('.header-logo')?.remove()
+                const logo = document.createElement('img')
+                logo.className = 'report-image header-logo'
+                logo.src = src
+                logo.alt = alt
+                logo.style.height = '32px'
+                brand.prepend(logo)
+            }),
+        profile.logoDataUrl,
+        profile.name ?? ''
+    )
+}
@@ -0,0 +1,26 @@
+import puppeteer, { type Browser } from 'puppeteer'
+
+let shared: Promise<Browser> | undefined
+
+// Chrome keeps its font, glyph-width and text-shaping caches per browser process, so reusing one browser means
+// the KPI names and headings that recur in every report are measured once rather than on every request.
+// Each render still gets its own browser context, so cookies, storage and cache entries never leak between renders.
+export function sharedBrowser() {
+    shared ??= puppeteer.launch({ args: ['--no-sandbox'] }).then((browser) => {
+        browser.once('disconnected', () => {
+            shared = undefined
+        })
+        return browser
+    })
+    // A failed launch shouldn't be cached; the next render retries.
+    shared.catch(() => {
+        shared = undefined
+    })
+    return shared
+}
+
+export async function closeBrowser() {
+    const browser = await shared?.catch(() => undefined)
+    shared = undefined
+    await browser?.close()
+}
@@ -0,0 +1,141 @@
+#!/usr/bin/env bun
+import { spawn } from 'node:child_process'
+import { watch } from 'node:fs'
+import { readFile, writeFile } from 'node:fs/promises'
+import { createRequire } from 'node:module'
+import { dirname } from 'node:path'
+import { fileURLToPath } from 'node:url'
+import { parseArgs } from 'node:util'
+import { type DraftBundle, renderReportParallel, sourceAttachment } from 'report-template'
+import { closeBrowser } from './browser'
+import { config } from './config'
+import { PdfError } from './errors'
+import { renderPdf } from './render'
+import { loadResources } from './resources'
+
+const USAGE = `Usage: reportflow-pdf render <input.json | -> [-o <report.pdf | ->] [--template <name>] [--deterministic]
+       reportflow-pdf watch <input.json> -o <report.pdf> [--template <name>]
+
+Input is either a report bundle (rendered with report-template) or { "html": "..." }.
+Use - to read the payload from stdin; without -o (or with -o -) the PDF is written to stdout.
+--deterministic stamps fixed dates and input-derived ids, so identical input gives a byte-identical PDF.
+watch re-renders whenever the payload, the assets directory, or the report-template sources change.`
+
+const WATCH_DEBOUNCE_MS = 250
+
+type Payload = DraftBundle & { html?: string }
+
+const toHtml = async (payload: Payload) => {
+    if (typeof payload.html === 'string') return payload.html
+    if (!payload.clientName || !Array.isArray(payload.sections)) {
+        throw new PdfError('invalid_payload', 'Input must be a report bundle (clientName, sections) or { "html": "..." }')
+    }
+    return renderReportParallel(payload)
+}
+
+const readInput = async (input: string) => {
+    if (input !== '-') return readFile(input, 'utf8')
+    const chunks: Buffer[] = []
+    for await (const chunk of process.stdin) chunks.push(Buffer.from(chunk))
+    return Buffer.concat(chunks).toString('utf8')
+}
+
+// Raw bytes straight to the stream (no encoding) and wait for the flush, so pipes receive an intact PDF.
+const writeOutput = async (output: string, pdf: Uint8Array) => {
+    if (output !== '-') return writeFile(output, pdf)
+    await new Promise<void>((resolve, reject) => process.stdout.write(pdf, (error) => (error ? reject(error) : resolve())))
+}
+
+const render = async (input: string, output: string, template?: string, deterministic?: boolean) => {
+    const payload = JSON.parse(await readInput(input)) as Payload
+    const resources = await loadResources(config.assetsDir)
+    const attachment = typeof payload.html === 'string' ? undefined : sourceAttachment(payload)
+    const { pdf, warnings } = await renderPdf(await toHtml(payload), {
+        resources,
+        template,
+        attachments: attachment ? [attachment] : [],
+        fromFile: true,
+        deterministic,
+    })
+    await writeOutput(output, pdf)
+    for (const warning of warnings) console.error(`warning [${warning.source}]: ${warning.message}`)
+}
+
+// Each render runs in a fresh process so edits to report-template components are picked up without a module cache.
+const watchAndRender = (input: string, output: string, template?: string) => {
+    const require = createRequire(import.meta.url)
+    const templateSources = dirname(require.resolve('report-template'))
+    const args = [fileURLToPath(import.meta.url), 'render', input, '-o', output, ...(template ? ['--template', template] : [])]
+    let running = false
+    let pending = false
+    let timer: ReturnType<typeof setTimeout> | undefined
+
+    const run = () => {
+        if (running) {
+            pending = true
+            return
+        }
+        running = true
+        const startedAt = Date.now()
+        const child = spawn(process.execPath, args, { stdio: ['ignore', 'inherit', 'inherit'] })
+        child.on('exit', (code) => {
+            running = false
+            console.error(code === 0 ? `rendered ${output} in ${Date.now() - startedAt}ms` : `render failed (exit ${code})`)
+            if (pending) {
+                pending = false
+                run()
+            }
+        })
+    }
+    const schedule = () => {
+        clearTimeout(timer)
+        timer = setTimeout(run, WATCH_DEBOUNCE_MS)
+    }
+
+    for (const path of [input, config.assetsDir, templateSources]) {
+        try {
+            watch(path, { recursive: true }, schedule)
+        } catch (error) {
+            console.error(`cannot watch ${path}: ${error instanceof Error ? error.message : String(error)}`)
+        }
+    }
+    console.error(`watching ${input}, ${config.assetsDir}, ${templateSources}`)
+    run()
+}
+
+const main = async () => {
+    const { positionals, values } = parseArgs({
+        allowPositionals: true,
+        options: {
+            output: { type: 'string', short: 'o' },
+            template: { type: 'string' },
+            deterministic: { type: 'boolean' },
+            help: { type: 'boolean', short: 'h' },
+        },
+    })
+    const [command, input] = positionals
+    const output = values.output ?? '-'
+    if (values.help || !['render', 'watch'].includes(command ?? '') || !input) {
+        console.error(USAGE)
+        process.exit(values.help ? 0 : 2)
+    }
+    if (command === 'watch') {
+        if (input === '-' || !values.output || values.output === '-') {
+            console.error('watch needs a payload file and an output file.')
+            process.exit(2)
+        }
+        return watchAndRender(input, values.output, values.template)
+    }
+    if (output === '-' && process.stdout.isTTY) {
+        console.error('Refusing to write a PDF to a terminal; pass -o <file> or redirect stdout.')
+        process.exit(2)
+    }
+    await render(input, output, values.template, values.deterministic)
+    await closeBrowser()
+}
+
+main().catch((error) => {
+    const code = error instanceof PdfError ? error.code : 'generation_failed'
+    console.error(`error [${code}]: ${error instanceof Error ? error.message : String(error)}`)
+    process.exit(1)
+})
@@ -0,0 +1,126 @@
+import { existsSync, readFileSync } from 'node:fs'
+import { resolve } from 'node:path'
+
+// Shape of reportflow.toml; every key is optional and env vars win over the file.
+type FileConfig = {
+    server?: { host?: string; port?: number }
+    cors?: { origins?: string[]; any_origin?: boolean; methods?: string[]; headers?: string[] }
+    limits?: { body?: string; render_timeout_ms?: number }
+    assets?: { dir?: string; watch?: boolean }
+    auth?: { admin_token?: string; render_token?: string }
+    error_reporting?: { url?: string; environment?: string }
+    grpc?: { port?: number; tls_cert?: string; tls_key?: string }
+    audit?: { file?: string }
+    reports?: { dir?: string; database?: string; signing_secret?: string; link_ttl_seconds?: number; public_url?: string }
+}
+
+const DEFAULT_CONFIG_FILE = 'reportflow.toml'
+
+const list = (value: string | undefined) =>
+    value
+        ?.split(',')
+        .map((item) => item.trim())
+        .filter(Boolean)
+
+const flag = (value: string | undefined) => (value == null ? undefined : value === 'true')
+
+const number = (value: string | undefined) => (value == null ? undefined : Number(value))
+
+// The example config's placeholders; a copy that still has one must not start up with a guessable secret.
+const PLACEHOLDER_SECRETS = ['change-me', 'changeme']
+
+const secret = (name: string, value: string | undefined) => {
+    if (value != null && PLACEHOLDER_SECRETS.includes(value.trim().toLowerCase())) {
+        throw new Error(`${name} is still the placeholder "${value}"; set a real secret or leave it unset`)
+    }
+    return value
+}
+
+// Signed links are bearer credentials, so none may outlive a month, whether minted by default or on request.
+export const MAX_LINK_TTL_SECONDS = 30 * 24 * 60 * 60
+
+export const isLinkTtl = (value: unknown): value is number =>
+    Number.isSafeInteger(value) && (value as number) >= 1 && (value as number) <= MAX_LINK_TTL_SECONDS
+
+const linkTtl = (value: number) => {
+    if (!isLinkTtl(value)) throw new Error(`reports.link_ttl_seconds must be a whole number of seconds between 1 and ${MAX_LINK_TTL_SECONDS}`)
+    return value
+}
+
+const port = (name: string, value: unknown) => {
+    if (!Number.isInteger(value) || (value as number) < 1 || (value as number) > 65_535) {
+        throw new Error(`${name} must be a whole number between 1 and 65535`)
+    }
+    return value as number
+}
+
+// setTimeout fires at once for anything past a signed 32-bit delay, so longer timeouts would behave like none at all.
+const MAX_TIMEOUT_MS = 2 ** 31 - 1
+
+const timeout = (name: string, value: unknown) => {
+    if (!Number.isInteger(value) || (value as number) < 1 || (value as number) > MAX_TIMEOUT_MS) {
+        throw new Error(`${name} must be a whole number of milliseconds between 1 and ${MAX_TIMEOUT_MS}`)
+    }
+    return value as number
+}
+
+const readConfigFile = (): FileConfig => {
+    const explicit = process.env.REPORTFLOW_CONFIG
+    const file = resolve(explicit ?? DEFAULT_CONFIG_FILE)
+    if (!existsSync(file)) {
+        if (explicit) throw new Error(`Config file ${file} does not exist`)
+        return {}
+    }
+    return Bun.TOML.parse(readFileSync(file, 'utf8')) as FileConfig
+}
+
+const file = readConfigFile()
+const env = process.env
+const dev = env.NODE_ENV !== 'production'
+const grpcPort = number(env.GRPC_PORT) ?? file.grpc?.port
+
+export const config = {
+    dev,
+    host: env.HOST ?? file.server?.host ?? '0.0.0.0',
+    port: port('server.port', number(env.PORT) ?? file.server?.port ?? 3001),
+    bodyLimit: env.BODY_LIMIT ?? file.limits?.body ?? '4mb',
+    renderTimeoutMs: timeout('limits.render_timeout_ms', number(env.RENDER_TIMEOUT_MS) ?? file.limits?.render_timeout_ms ?? 30_000),
+    assetsDir: env.ASSETS_DIR ?? file.assets?.dir ?? 'assets',
+    watchAssets: flag(env.WATCH_ASSETS) ?? file.assets?.watch ?? dev,
+    // Admin endpoints are disabled entirely unless a token is configured.
+    adminToken: secret('auth.admin_token', env.ADMIN_TOKEN ?? file.auth?.admin_token),
+    // Bearer token for rendering; without it the HTTP render endpoints are open to anyone who can reach the port.
+    renderToken: secret('auth.render_token', env.RENDER_TOKEN ?? file.auth?.render_token),
+    // Generic JSON webhook for 5xx and crash reports; unset disables reporting.
+    errorReporting: {
+        url: env.ERROR_REPORTING_URL ?? file.error_reporting?.url,
+        environment: env.ERROR_REPORTING_ENVIRONMENT ?? file.error_reporting?.environment ?? (dev ? 'development' : 'production'),
+    },
+    // JSON-lines audit trail of every delivered report; without a file the records go to stdout.
+    audit: {
+        file: env.AUDIT_LOG ?? file.audit?.file,
+    },
+    // Delivered PDFs are kept only when a directory is set; download links additionally need a signing secret.
+    reports: {
+        dir: env.REPORTS_DIR ?? file.reports?.dir,
+        // A SQLite file or a postgres:// URL; enables GET /reports listing what was delivered.
+        database: env.REPORTS_DATABASE ?? file.reports?.database,
+        signingSecret: secret('reports.signing_secret', env.REPORT_SIGNING_SECRET ?? file.reports?.signing_secret),
+        linkTtlSeconds: linkTtl(number(env.REPORT_LINK_TTL_SECONDS) ?? file.reports?.link_ttl_seconds ?? 7 * 24 * 60 * 60),
+        // Prefix for links handed to other systems, e.g. https://reports.example.com; links are relative otherwise.
+        publicUrl: env.REPORTS_PUBLIC_URL ?? file.reports?.public_url ?? '',
+    },
+    // The gRPC front end only starts when a port is configured, and then needs TLS, a render token, or both.
+    grpc: {
+        port: grpcPort == null ? undefined : port('grpc.port', grpcPort),
+        tlsCert: env.GRPC_TLS_CERT ?? file.grpc?.tls_cert,
+        tlsKey: env.GRPC_TLS_KEY ?? file.grpc?.tls_key,
+    },
+    cors: {
+        // Cross-origin requests are refused unless origins are listed, or any origin is explicitly allowed (local dev).
+        origins: list(env.CORS_ORIGINS) ?? file.cors?.origins ?? [],
+        anyOrigin: flag(env.CORS_ANY_ORIGIN) ?? file.cors?.any_origin ?? false,
+        methods: list(env.CORS_METHODS) ?? file.cors?.methods ?? ['GET', 'POST', 'PUT', 'DELETE'],
+        headers: list(env.CORS_HEADERS) ?? file.cors?.headers ?? ['Content-Type', 'Authorization', 'Idempotency-Key'],
+    },
+}
@@ -0,0 +1,40 @@
+import { type PDFDocument, PDFHexString, PDFName, PDFRawStream, PDFRef, decodePDFRawStream } from 'pdf-lib'
+import { createHash } from 'node:crypto'
+
+// Stamped wherever the PDF would otherwise carry the render time.
+export const DETERMINISTIC_DATE = new Date('2000-01-01T00:00:00.000Z')
+
+const XMP_DATE = /(<(xmp:(?:CreateDate|ModifyDate|MetadataDate))>)[^<]*(<\/\2>)|((?:xmp:(?:CreateDate|ModifyDate|MetadataDate))=")[^"]*(")/g
+const XMP_UUID = /uuid:[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}/gi
+
+const digest = (seed: string) => createHash('sha256').update(seed).digest('hex')
+
+const uuidFrom = (seed: string) => {
+    const hex = digest(seed)
+    return `${hex.slice(0, 8)}-${hex.slice(8, 12)}-${hex.slice(12, 16)}-${hex.slice(16, 20)}-${hex.slice(20, 32)}`
+}
+
+// Chrome's tagged output carries an XMP packet with its own timestamps and random document/instance ids.
+const rewriteXmp = (document: PDFDocument, seed: string) => {
+    const ref = document.catalog.get(PDFName.of('Metadata'))
+    if (!(ref instanceof PDFRef)) return
+    const stream = document.context.lookup(ref)
+    if (!(stream instanceof PDFRawStream)) return
+    const iso = DETERMINISTIC_DATE.toISOString()
+    let uuids = 0
+    const xmp = Buffer.from(decodePDFRawStream(stream).decode())
+        .toString('utf8')
+        .replace(XMP_DATE, (_match, open, _name, close, attr, quote) => (open ? `${open}${iso}${close}` : `${attr}${iso}${quote}`))
+        .replace(XMP_UUID, () => `uuid:${uuidFrom(`${seed}:${uuids++}`)}`)
+    document.context.assign(ref, document.context.stream(xmp, { Type: 'Metadata', Subtype: 'XML' }))
+}
+
+// Byte-identical inputs must give byte-identical PDFs, so everything time- or randomness-derived is replaced with
+// values derived from the input. pdf-lib already writes objects in reference order, which keeps the layout stable.
+export function makeDeterministic(document: PDFDocument, seed: string) {
+    document.setCreationDate(DETERMINISTIC_DATE)
+    document.setModificationDate(DETERMINISTIC_DATE)
+    const id = PDFHexString.of(digest(seed).slice(0, 32))
+    document.context.trailerInfo.ID = document.context.obj([id, id])
+    rewriteXmp(document, seed)
+}
@@ -0,0 +1,93 @@
+import { createHash, randomUUID } from 'node:crypto'
+import type { ErrorRequestHandler } from 'express'
+import { payloadMetadata, reportError } from './reporting'
+import { renderFailures } from './telemetry'
+
+export type PdfErrorCode =
+    | 'invalid_payload'
+    | 'unauthorized'
+    | 'not_found'
+    | 'idempotency_conflict'
+    | 'link_expired'
+    | 'payload_too_large'
+    | 'too_many_jobs'
+    | 'resource_load_failed'
+    | 'layout_failed'
+    | 'serialization_failed'
+    | 'render_timeout'
+    | 'generation_failed'
+
+const STATUS: Record<PdfErrorCode, number> = {
+    invalid_payload: 400,
+    unauthorized: 401,
+    not_found: 404,
+    idempotency_conflict: 409,
+    link_expired: 410,
+    payload_too_large: 413,
+    too_many_jobs: 429,
+    resource_load_failed: 502,
+    layout_failed: 422,
+    serialization_failed: 500,
+    render_timeout: 504,
+    generation_failed: 500,
+}
+
+export class PdfError extends Error {
+    readonly code: PdfErrorCode
+    readonly details?: Record<string, unknown>
+
+    constructor(code: PdfErrorCode, message: string, options?: { cause?: unknown; details?: Record<string, unknown> }) {
+        super(message, options)
+        this.name = 'PdfError'
+        this.code = code
+        this.details = options?.details
+    }
+
+    get status() {
+        return STATUS[this.code]
+    }
+}
+
+// Tags whatever a render stage throws with that stage's code, keeping the original as `cause`.
+export const stage = async <T>(code: PdfErrorCode, run: () => Promise<T>) => {
+    try {
+        return await run()
+    } catch (error) {
+        if (error instanceof PdfError) throw error
+        throw new PdfError(code, error instanceof Error ? error.message : String(error), { cause: error })
+    }
+}
+
+const fromBodyParser = (error: { type?: string; message?: string }) => {
+    if (error.type === 'entity.too.large') return new PdfError('payload_too_large', 'Request body exceeds the size limit')
+    if (error.type === 'entity.parse.failed') return new PdfError('invalid_payload', 'Request body is not valid JSON')
+    return undefined
+}
+
+export const payloadHash = (body: unknown) =>
+    createHash('sha256')
+        .update(JSON.stringify(body ?? null))
+        .digest('hex')
+        .slice(0, 16)
+
+// Server-side failures get an id that is returned to the caller and logged with the payload hash, so a report
+// from a customer can be matched to the log line and replayed without logging the payload itself.
+export const errorHandler: ErrorRequestHandler = (error, req, res, next) => {
+    if (res.headersSent) return next(error)
+    const pdfError =
+        error instanceof PdfError
+            ? error
+            : (fromBodyParser(error) ?? new PdfError('generation_failed', 'Unexpected error while generating the PDF', { cause: error }))
+    renderFailures.add(1, { code: pdfError.code })
+    if (pdfError.status < 500) {
+        res.status(pdfError.status).json({ error: { code: pdfError.code, message: pdfError.message, details: pdfError.details } })
+        return
+    }
+    const errorId = randomUUID()
+    const hash = payloadHash(req.body)
+    console.error(`[${errorId}] ${req.method} ${req.path} payload=${hash}`, pdfError, pdfError.cause)
+    void reportError(pdfError, { errorId, code: pdfError.code, route: `${req.method} ${req.path}`, payloadHash: hash, ...payloadMetadata(req.body) })
+    res.status(pdfError.status).json({
+        error: { code: pdfError.code, message: pdfError.message, details: pdfError.details, errorId },
+    })
+}
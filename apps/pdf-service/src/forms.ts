@@ -0,0 +1,66 @@
+import type { PDFDocument } from 'pdf-lib'
+
+export type FormPlacement = {
+    name: string
+    type: string
+    value?: string
+    pageIndex: number
+    // CSS px relative to the top-left corner of the paged.js page box.
+    x: number
+    y: number
+    width: number
+    height: number
+    pageWidth: number
+}
+
+// Runs in the browser after pagination; placeholders outside a page box (unpaginated render) are skipped.
+export const measureFormFields = (): FormPlacement[] => {
+    const pages = Array.from(document.querySelectorAll('.pagedjs_page'))
+    return Array.from(document.querySelectorAll<HTMLElement>('[data-form-field]')).flatMap((element) => {
+        const page = element.closest('.pagedjs_page')
+        if (!page) return []
+        const box = element.getBoundingClientRect()
+        const origin = page.getBoundingClientRect()
+        return [
+            {
+                name: element.dataset.formField ?? '',
+                type: element.dataset.formType ?? 'text',
+                value: element.dataset.formValue,
+                pageIndex: pages.indexOf(page),
+                x: box.left - origin.left,
+                y: box.top - origin.top,
+                width: box.width,
+                height: box.height,
+                pageWidth: origin.width,
+            },
+        ]
+    })
+}
+
+// Chrome prints inputs as static boxes, so real AcroForm widgets are laid over the measured placeholders.
+export function addFormFields(document: PDFDocument, placements: FormPlacement[]) {
+    const form = document.getForm()
+    const pages = document.getPages()
+    for (const placement of placements) {
+        const page = pages[placement.pageIndex]
+        if (!page) continue
+        const scale = page.getWidth() / placement.pageWidth
+        const rect = {
+            x: placement.x * scale,
+            y: page.getHeight() - (placement.y + placement.height) * scale,
+            width: placement.width * scale,
+            height: placement.height * scale,
+            borderWidth: 0,
+        }
+        if (placement.type === 'checkbox') {
+            const checkbox = form.createCheckBox(placement.name)
+            checkbox.addToPage(page, rect)
+            if (placement.value) checkbox.check()
+            continue
+        }
+        const field = form.createTextField(placement.name)
+        if (placement.type === 'multiline') field.enableMultiline()
+        if (placement.value) field.setText(placement.value)
+        field.addToPage(page, rect)
+    }
+}
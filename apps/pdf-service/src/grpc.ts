@@ -0,0 +1,160 @@
+import { Server, ServerCredentials, loadPackageDefinition, status, type ServerWritableStream, type ServiceError } from '@grpc/grpc-js'
+import { loadSync } from '@grpc/proto-loader'
+import { randomUUID } from 'node:crypto'
+import { readFileSync } from 'node:fs'
+import { fileURLToPath } from 'node:url'
+import type { Delivery } from './audit'
+import { tokenMatches } from './auth'
+import { PdfError, payloadHash, type PdfErrorCode } from './errors'
+import { idempotencyClient, idempotent } from './idempotency'
+import { renderPdf } from './render'
+import { payloadMetadata, reportError } from './reporting'
+import { recordReport } from './reports'
+import { readRequest } from './request'
+import type { Resources } from './resources'
+import { renderFailures } from './telemetry'
+
+const PROTO_FILE = fileURLToPath(new URL('../proto/reportflow.proto', import.meta.url))
+// Streamed responses stay well under grpc's default 4 MB message limit.
+const CHUNK_BYTES = 256 * 1024
+const MAX_BATCH_ITEMS = 50
+
+const STATUS: Record<PdfErrorCode, status> = {
+    invalid_payload: status.INVALID_ARGUMENT,
+    unauthorized: status.UNAUTHENTICATED,
+    not_found: status.NOT_FOUND,
+    idempotency_conflict: status.ALREADY_EXISTS,
+    link_expired: status.NOT_FOUND,
+    payload_too_large: status.RESOURCE_EXHAUSTED,
+    too_many_jobs: status.RESOURCE_EXHAUSTED,
+    resource_load_failed: status.UNAVAILABLE,
+    layout_failed: status.FAILED_PRECONDITION,
+    serialization_failed: status.INTERNAL,
+    render_timeout: status.DEADLINE_EXCEEDED,
+    generation_failed: status.INTERNAL,
+}
+
+const definition = loadPackageDefinition(loadSync(PROTO_FILE, { longs: Number, oneofs: true })) as any
+const service = definition.reportflow.v1.ReportService.service
+
+const toPdfError = (error: unknown) =>
+    error instanceof PdfError ? error : new PdfError('generation_failed', 'Unexpected error while generating the PDF', { cause: error })
+
+// Same bookkeeping as the HTTP error handler, so failures look alike in logs and reports whichever front end served them.
+const failure = (error: unknown, method: string, request: unknown) => {
+    const pdfError = toPdfError(error)
+    renderFailures.add(1, { code: pdfError.code })
+    if (pdfError.status >= 500) {
+        const errorId = randomUUID()
+        const hash = payloadHash(request)
+        console.error(`[${errorId}] grpc ${method} payload=${hash}`, pdfError, pdfError.cause)
+        void reportError(pdfError, { errorId, code: pdfError.code, route: `grpc ${method}`, payloadHash: hash, ...payloadMetadata(request) })
+    }
+    return pdfError
+}
+
+const serviceError = (error: PdfError): Partial<ServiceError> => ({ code: STATUS[error.code], details: error.message })
+
+const render = async (request: unknown, resources: Resources, delivery: Delivery) => {
+    const { html, ...options } = readRequest(request)
+    const result = await renderPdf(html, { resources, ...options })
+    const { pdf, pages, renderMs, warnings } = result
+    const { reportId, bytes, sha256, link } = await recordReport(delivery, request, result)
+    return { pdf, metadata: { reportId, pages, bytes, sha256, renderMs, warnings, url: link?.url } }
+}
+
+export type GrpcOptions = {
+    host: string
+    port: number
+    resources: () => Resources
+    // Required as `authorization: Bearer <token>` call metadata when set.
+    token?: string
+    tls?: { cert: string; key: string }
+}
+
+const authorized = (call: ServerWritableStream<unknown, unknown>, token?: string) => {
+    if (!token) return true
+    const [header] = call.metadata.get('authorization')
+    return header != null && tokenMatches(String(header).replace(/^Bearer\s+/i, ''), token)
+}
+
+const unauthorized = () => serviceError(new PdfError('unauthorized', 'Missing or invalid bearer token')) as ServiceError
+
+// Metadata first, then the PDF in chunks; batch items tag every message with their id.
+const writePdf = (call: ServerWritableStream<unknown, unknown>, pdf: Uint8Array, tag: { id?: string } = {}) => {
+    for (let offset = 0; offset < pdf.byteLength; offset += CHUNK_BYTES) {
+        if (call.cancelled) return
+        call.write({ ...tag, chunk: Buffer.from(pdf.subarray(offset, offset + CHUNK_BYTES)) })
+    }
+}
+
+// Same semantics as the HTTP Idempotency-Key header, sent as call metadata; batch items are keyed by key and item id.
+const idempotencyKey = (call: ServerWritableStream<unknown, unknown>) => {
+    const [key] = call.metadata.get('idempotency-key')
+    return key == null ? undefined : String(key)
+}
+
+// The peer's port changes with every connection, and a retry usually comes in on a new one.
+const caller = (call: ServerWritableStream<unknown, unknown>) => {
+    const [header] = call.metadata.get('authorization')
+    return idempotencyClient(header == null ? undefined : String(header), call.getPeer().replace(/:\d+$/, ''))
+}
+
+// Rejects rather than starting unauthenticated: the generator is reachable by anything that can reach the port.
+export function startGrpcServer({ host, port, resources, token, tls }: GrpcOptions) {
+    if (!token && !tls) return Promise.reject(new Error('gRPC needs TLS (grpc.tls_cert and grpc.tls_key), a render token, or both'))
+    const credentials = tls
+        ? ServerCredentials.createSsl(null, [{ cert_chain: readFileSync(tls.cert), private_key: readFileSync(tls.key) }])
+        : ServerCredentials.createInsecure()
+    const server = new Server()
+    server.addService(service, {
+        generateReport: async (call: ServerWritableStream<unknown, unknown>) => {
+            if (!authorized(call, token)) return call.destroy(unauthorized())
+            try {
+                const { value } = await idempotent(idempotencyKey(call), 'grpc', caller(call), call.request, () =>
+                    render(call.request, resources(), 'grpc')
+                )
+                const { pdf, metadata } = value
+                call.write({ metadata })
+                writePdf(call, pdf)
+                call.end()
+            } catch (error) {
+                call.destroy(serviceError(failure(error, 'GenerateReport', call.request)) as ServiceError)
+            }
+        },
+        // A failed item is reported in its own result; the rest of the batch still renders.
+        generateBatch: async (call: ServerWritableStream<{ items?: { id?: string; request?: unknown }[] }, unknown>) => {
+            if (!authorized(call as ServerWritableStream<unknown, unknown>, token)) return call.destroy(unauthorized())
+            const items = call.request.items ?? []
+            if (items.length > MAX_BATCH_ITEMS) {
+                call.destroy(serviceError(new PdfError('invalid_payload', `A batch holds at most ${MAX_BATCH_ITEMS} items`)) as ServiceError)
+                return
+            }
+            const key = idempotencyKey(call as ServerWritableStream<unknown, unknown>)
+            const client = caller(call as ServerWritableStream<unknown, unknown>)
+            for (const [index, item] of items.entries()) {
+                if (call.cancelled) return
+                const id = item.id || String(index)
+                try {
+                    const { value } = await idempotent(key && `${key} ${id}`, 'grpc-batch', client, item.request, () =>
+                        render(item.request, resources(), 'grpc-batch')
+                    )
+                    const { pdf, metadata } = value
+                    call.write({ id, metadata })
+                    writePdf(call as ServerWritableStream<unknown, unknown>, pdf, { id })
+                } catch (error) {
+                    const pdfError = failure(error, 'GenerateBatch', item.request)
+                    call.write({ id, error: { code: pdfError.code, message: pdfError.message } })
+                }
+            }
+            call.end()
+        },
+    })
+    return new Promise<Server>((resolve, reject) => {
+        server.bindAsync(`${host}:${port}`, credentials, (error) => {
+            if (error) return reject(error)
+            console.log(`pdf-service gRPC on ${host}:${port}${tls ? ' (TLS)' : ''}`)
+            resolve(server)
+        })
+    })
+}
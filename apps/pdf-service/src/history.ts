@@ -0,0 +1,133 @@
+import { SQL } from 'bun'
+import { Database } from 'bun:sqlite'
+import type { AuditRecord } from './audit'
+import { config } from './config'
+
+export type HistoryEntry = {
+    reportId: string
+    createdAt: string
+    clientName?: string
+    reportDate?: string
+    tenant?: string
+    template?: string
+    pages: number
+    bytes: number
+    sha256: string
+}
+
+export type HistoryQuery = { clientName?: string; limit: number }
+
+type Store = {
+    insert: (entry: HistoryEntry) => Promise<void>
+    list: (query: HistoryQuery) => Promise<HistoryEntry[]>
+}
+
+type Row = {
+    report_id: string
+    created_at: string
+    client_name: string | null
+    report_date: string | null
+    tenant: string | null
+    template: string | null
+    pages: number
+    bytes: number
+    sha256: string
+}
+
+const SCHEMA = `CREATE TABLE IF NOT EXISTS reports (
+    report_id TEXT PRIMARY KEY,
+    created_at TEXT NOT NULL,
+    client_name TEXT,
+    report_date TEXT,
+    tenant TEXT,
+    template TEXT,
+    pages INTEGER NOT NULL,
+    bytes INTEGER NOT NULL,
+    sha256 TEXT NOT NULL
+)`
+const INDEX = 'CREATE INDEX IF NOT EXISTS reports_client_name ON reports (client_name, created_at)'
+
+const toRow = (entry: HistoryEntry): Row => ({
+    report_id: entry.reportId,
+    created_at: entry.createdAt,
+    client_name: entry.clientName ?? null,
+    report_date: entry.reportDate ?? null,
+    tenant: entry.tenant ?? null,
+    template: entry.template ?? null,
+    pages: entry.pages,
+    bytes: entry.bytes,
+    sha256: entry.sha256,
+})
+
+const fromRow = (row: Row): HistoryEntry => ({
+    reportId: row.report_id,
+    createdAt: row.created_at,
+    clientName: row.client_name ?? undefined,
+    reportDate: row.report_date ?? undefined,
+    tenant: row.tenant ?? undefined,
+    template: row.template ?? undefined,
+    pages: Number(row.pages),
+    bytes: Number(row.bytes),
+    sha256: row.sha256,
+})
+
+const sqliteStore = (file: string): Store => {
+    const db = new Database(file, { create: true, strict: true })
+    db.run('PRAGMA journal_mode = WAL')
+    db.run(SCHEMA)
+    db.run(INDEX)
+    const insert = db.query(
+        `INSERT INTO reports VALUES ($report_id, $created_at, $client_name, $report_date, $tenant, $template, $pages, $bytes, $sha256)`
+    )
+    const all = db.query<Row, { limit: number }>('SELECT * FROM reports ORDER BY created_at DESC LIMIT $limit')
+    const byClient = db.query<Row, { client_name: string; limit: number }>(
+        'SELECT * FROM reports WHERE client_name = $client_name ORDER BY created_at DESC LIMIT $limit'
+    )
+    return {
+        insert: async (entry) => void insert.run(toRow(entry)),
+        list: async ({ clientName, limit }) =>
+            (clientName == null ? all.all({ limit }) : byClient.all({ client_name: clientName, limit })).map(fromRow),
+    }
+}
+
+const postgresStore = (url: string): Store => {
+    const sql = new SQL(url)
+    const ready = sql.unsafe(SCHEMA).then(() => sql.unsafe(INDEX))
+    return {
+        insert: async (entry) => {
+            await ready
+            await sql`INSERT INTO reports ${sql(toRow(entry))}`
+        },
+        list: async ({ clientName, limit }) => {
+            await ready
+            const rows: Row[] =
+                clientName == null
+                    ? await sql`SELECT * FROM reports ORDER BY created_at DESC LIMIT ${limit}`
+                    : await sql`SELECT * FROM reports WHERE client_name = ${clientName} ORDER BY created_at DESC LIMIT ${limit}`
+            return rows.map(fromRow)
+        },
+    }
+}
+
+// Postgres for shared deployments, a local SQLite file otherwise; there is no history at all unless a database is configured.
+const open = (database: string | undefined): Store | undefined => {
+    if (!database) return undefined
+    return /^postgres(ql)?:\/\//.test(database) ? postgresStore(database) : sqliteStore(database)
+}
+
+const store = open(config.reports.database)
+
+export const historyEnabled = () => Boolean(store)
+
+// Losing a history row must not fail a delivery that already rendered, so write errors are only logged.
+export async function recordHistory(record: AuditRecord, details: { clientName?: string; reportDate?: string }) {
+    if (!store) return
+    const { reportId, timestamp, tenant, template, pages, bytes, sha256 } = record
+    await store
+        .insert({ reportId, createdAt: timestamp, ...details, tenant, template, pages, bytes, sha256 })
+        .catch((error) => console.error('failed to record report history', error, reportId))
+}
+
+export async function listHistory(query: HistoryQuery) {
+    return (await store?.list(query)) ?? []
+}
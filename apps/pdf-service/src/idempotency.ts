@@ -0,0 +1,82 @@
+import { createHash } from 'node:crypto'
+import { PdfError, payloadHash } from './errors'
+
+type Entry = {
+    fingerprint: string
+    result: Promise<unknown>
+    expiresAt: number
+    // Counted once the result is in; a render still running holds no PDF yet.
+    bytes: number
+}
+
+// Long enough to cover a client's retry window; bounded by count and by the bytes of the cached PDFs, so a burst of
+// large reports can't grow memory without limit.
+const TTL_MS = 24 * 60 * 60_000
+const MAX_ENTRIES = 200
+const MAX_CACHED_BYTES = 256 * 1024 * 1024
+const MAX_KEY_LENGTH = 255
+
+const entries = new Map<string, Entry>()
+let cachedBytes = 0
+
+const drop = (id: string) => {
+    cachedBytes -= entries.get(id)?.bytes ?? 0
+    entries.delete(id)
+}
+
+const evict = (now: number, maxEntries: number) => {
+    for (const [id, entry] of entries) if (entry.expiresAt <= now) drop(id)
+    // Map iteration is insertion order, so this drops the oldest keys first. Renders still running hold no bytes,
+    // so they only go when there are too many keys.
+    for (const [id, entry] of entries) {
+        const tooMany = entries.size > maxEntries
+        if (!tooMany && cachedBytes <= MAX_CACHED_BYTES) break
+        if (tooMany || entry.bytes) drop(id)
+    }
+}
+
+// PDFs dominate a cached result, so only its binary fields count towards the budget.
+const resultBytes = (value: unknown) => {
+    if (value instanceof Uint8Array) return value.byteLength
+    if (!value || typeof value !== 'object') return 0
+    return Object.values(value).reduce((sum: number, field) => sum + (field instanceof Uint8Array ? field.byteLength : 0), 0)
+}
+
+// Keys belong to the caller that sent them: the bearer credential when there is one, otherwise the remote address.
+// Two clients picking the same key never see each other's reports or conflicts.
+export const idempotencyClient = (credential: string | undefined, address: string | undefined) =>
+    credential ? `token:${createHash('sha256').update(credential).digest('hex').slice(0, 16)}` : `address:${address ?? 'unknown'}`
+
+// A retry with the same key gets the original result, including while the first attempt is still running, so a
+// flaky connection never produces (or emails) a second report. Failures aren't cached, so a retry can succeed.
+// Reusing a key for a different payload is a client bug and is rejected rather than silently answered.
+export async function idempotent<T>(key: string | undefined, scope: string, client: string, body: unknown, run: () => Promise<T>) {
+    if (!key) return { value: await run(), replayed: false }
+    if (key.length > MAX_KEY_LENGTH) throw new PdfError('invalid_payload', `Idempotency-Key must be at most ${MAX_KEY_LENGTH} characters`)
+    const now = Date.now()
+    const id = `${client} ${scope} ${key}`
+    const fingerprint = payloadHash(body)
+    const existing = entries.get(id)
+    if (existing && existing.expiresAt > now) {
+        if (existing.fingerprint !== fingerprint) {
+            throw new PdfError('idempotency_conflict', 'Idempotency-Key was already used with a different payload')
+        }
+        return { value: (await existing.result) as T, replayed: true }
+    }
+    evict(now, MAX_ENTRIES - 1)
+    const result = run()
+    const entry: Entry = { fingerprint, result, expiresAt: now + TTL_MS, bytes: 0 }
+    entries.set(id, entry)
+    result.then(
+        (value) => {
+            if (entries.get(id) !== entry) return
+            entry.bytes = resultBytes(value)
+            cachedBytes += entry.bytes
+            evict(Date.now(), MAX_ENTRIES)
+        },
+        () => {
+            if (entries.get(id) === entry) drop(id)
+        }
+    )
+    return { value: await result, replayed: false }
+}
@@ -0,0 +1,100 @@
+import { EventEmitter } from 'node:events'
+import { randomUUID } from 'node:crypto'
+import { PdfError } from './errors'
+import type { RenderProgress } from './render'
+import { reportError } from './reporting'
+import { renderFailures } from './telemetry'
+
+export type JobStatus = 'running' | 'done' | 'failed'
+
+export type JobEvent =
+    | { type: 'progress'; progress: RenderProgress }
+    | { type: 'done'; reportId: string; pages: number; bytes: number; renderMs: number }
+    | { type: 'failed'; error: { code: string; message: string } }
+
+type Job = {
+    id: string
+    status: JobStatus
+    progress?: RenderProgress
+    pdf?: Uint8Array
+    final?: JobEvent
+    events: EventEmitter
+}
+
+// Finished jobs are kept long enough for the client to fetch the PDF, then dropped; nothing is persisted. Each one
+// holds its PDF in memory, so only so many may run or wait for pickup at once.
+const JOB_TTL_MS = 10 * 60_000
+const MAX_RUNNING_JOBS = 4
+const MAX_RETAINED_JOBS = 100
+
+const jobs = new Map<string, Job>()
+
+type JobResult = { pdf: Uint8Array; pages: number; renderMs: number; reportId: string }
+
+const running = () => Array.from(jobs.values()).filter((job) => job.status === 'running').length
+
+// Map iteration is insertion order, so the oldest finished jobs make room first.
+const dropFinished = () => {
+    let retained = jobs.size - running()
+    for (const [id, job] of jobs) {
+        if (retained <= MAX_RETAINED_JOBS) break
+        if (job.status === 'running') continue
+        jobs.delete(id)
+        retained--
+    }
+}
+
+export function startJob(run: (onProgress: (progress: RenderProgress) => void) => Promise<JobResult>) {
+    if (running() >= MAX_RUNNING_JOBS) {
+        throw new PdfError('too_many_jobs', `At most ${MAX_RUNNING_JOBS} jobs can run at once; retry when one finishes`)
+    }
+    const job: Job = { id: randomUUID(), status: 'running', events: new EventEmitter() }
+    jobs.set(job.id, job)
+    const emit = (event: JobEvent) => job.events.emit('event', event)
+    const finish = (event: JobEvent) => {
+        job.final = event
+        emit(event)
+        dropFinished()
+        setTimeout(() => jobs.delete(job.id), JOB_TTL_MS).unref()
+    }
+    run((progress) => {
+        job.progress = progress
+        emit({ type: 'progress', progress })
+    }).then(
+        ({ pdf, pages, renderMs, reportId }) => {
+            job.status = 'done'
+            job.pdf = pdf
+            finish({ type: 'done', reportId, pages, bytes: pdf.byteLength, renderMs })
+        },
+        (error) => {
+            job.status = 'failed'
+            const pdfError = error instanceof PdfError ? error : new PdfError('generation_failed', 'Unexpected error while generating the PDF')
+            renderFailures.add(1, { code: pdfError.code })
+            if (pdfError.status >= 500) {
+                console.error(`[job ${job.id}]`, error)
+                void reportError(error, { code: pdfError.code, route: 'job' })
+            }
+            finish({ type: 'failed', error: { code: pdfError.code, message: pdfError.message } })
+        }
+    )
+    return job.id
+}
+
+export function findJob(id: string) {
+    const job = jobs.get(id)
+    if (!job) throw new PdfError('not_found', `Unknown job "${id}"`)
+    return job
+}
+
+export const describeJob = ({ id, status, progress, final }: Job) => ({ id, status, progress, result: final })
+
+// Replays the latest progress first so a client that connects late still draws the bar at the right place.
+export function subscribeJob(job: Job, listener: (event: JobEvent) => void) {
+    if (job.final) {
+        listener(job.final)
+        return () => undefined
+    }
+    if (job.progress) listener({ type: 'progress', progress: job.progress })
+    job.events.on('event', listener)
+    return () => job.events.off('event', listener)
+}
@@ -0,0 +1,18 @@
+import { decode } from '@msgpack/msgpack'
+import express, { type RequestHandler } from 'express'
+import { PdfError } from './errors'
+
+const MSGPACK_TYPES = ['application/msgpack', 'application/x-msgpack', 'application/vnd.msgpack']
+
+const decodeBody: RequestHandler = (req, _res, next) => {
+    if (!req.is(MSGPACK_TYPES) || !Buffer.isBuffer(req.body)) return next()
+    try {
+        req.body = decode(req.body)
+    } catch (error) {
+        throw new PdfError('invalid_payload', 'Body is not valid MessagePack', { cause: error })
+    }
+    next()
+}
+
+// Same payload shape as JSON; batch generators use it to skip JSON encode/decode on large bodies.
+export const msgpackBody = (limit: string): RequestHandler[] => [express.raw({ type: MSGPACK_TYPES, limit }), decodeBody]
@@ -0,0 +1,316 @@
+import { PDFDocument } from 'pdf-lib'
+import { type Page, type PDFOptions, TimeoutError } from 'puppeteer'
+import { createHash, randomUUID } from 'node:crypto'
+import { rm, writeFile } from 'node:fs/promises'
+import { createRequire } from 'node:module'
+import { tmpdir } from 'node:os'
+import { join } from 'node:path'
+import { pathToFileURL } from 'node:url'
+import { config } from './config'
+import { PdfError, stage } from './errors'
+import { type FormPlacement, addFormFields, measureFormFields } from './forms'
+import { renderDuration, renderPages } from './telemetry'
+import { createTrace, logTrace, measureSections } from './timing'
+import { type Attachment, embedAttachments } from './attachments'
+import { removeBlankPages } from './blankPages'
+import { sharedBrowser } from './browser'
+import { DETERMINISTIC_DATE, makeDeterministic } from './deterministic'
+import { applyBrandProfile, resolveBrandProfile } from './brand'
+import { type Resources, brandProfiles, fontFaceCss } from './resources'
+import { downgradeUncoveredPunctuation } from './typography'
+
+const require = createRequire(import.meta.url)
+export const PAGED_POLYFILL = require.resolve('pagedjs/dist/paged.polyfill.js')
+
+export type RenderWarning = {
+    source: 'console' | 'page-error' | 'request' | 'layout' | 'content'
+    message: string
+}
+
+export type RenderProgress = {
+    stage: 'loading' | 'layout' | 'output'
+    // Only reported for paged documents, where paged.js lays pages out one at a time.
+    pages?: number
+    sections?: { completed: number; total: number }
+}
+
+export type RenderOptions = {
+    resources: Resources
+    template?: string
+    brandProfile?: string
+    attachments?: Attachment[]
+    forcePaged?: boolean
+    // Load from a temp file instead of about:blank so absolute asset paths (bun's file imports) resolve.
+    fromFile?: boolean
+    onProgress?: (progress: RenderProgress) => void
+    // Fixed dates and input-derived ids, so the same input always yields the same bytes.
+    deterministic?: boolean
+}
+
+const PDF_OPTIONS: PDFOptions = {
+    format: 'Letter',
+    printBackground: true,
+    preferCSSPageSize: true,
+    tagged: true,
+    outline: true,
+}
+
+// Cap raster images at their natural size for the DPI requested by the template (CSS px are 96 per inch).
+const applyImageDpi = () =>
+    document.querySelectorAll<HTMLImageElement>('img[data-dpi]').forEach((img) => {
+        const dpi = Number(img.dataset.dpi)
+        if (!dpi || !img.naturalWidth) return
+        img.style.maxWidth = `${(img.naturalWidth * 96) / dpi}px`
+    })
+
+// Runs in the browser before preview(). A section counts as completed once a later section has started on a page.
+const trackLayout = () => {
+    const total = Array.from(document.querySelectorAll('main [id]')).filter((element) => !element.parentElement?.closest('main [id]')).length
+    const { Paged, reportflowProgress } = window as any
+    let pages = 0
+    const started = new Set<string>()
+    Paged.registerHandlers(
+        class extends Paged.Handler {
+            afterPageLayout(fragment: HTMLElement) {
+                pages += 1
+                fragment.querySelectorAll<HTMLElement>('main [data-ref]').forEach((element) => {
+                    if (element.id && !element.parentElement?.closest('main [id]')) started.add(element.id)
+                })
+                reportflowProgress(pages, Math.max(0, started.size - 1), total)
+            }
+        }
+    )
+}
+
+// Templates that need page-aware layout (cross-references, page counters) opt in via <html data-paged>;
+// paged.js lays out the pages in the browser so target-counter() and friends resolve before printing.
+const paginate = async (page: Page, force = false, trackProgress = false) => {
+    if (!force && !(await page.$('html[data-paged], html[data-fit-to-pages]'))) return
+    await page.evaluate('window.PagedConfig = { auto: false }')
+    await page.addScriptTag({ path: PAGED_POLYFILL })
+    if (trackProgress) await page.evaluate(trackLayout)
+    await page.evaluate('window.PagedPolyfill.preview()')
+}
+
+const layoutProgress = (onProgress: NonNullable<RenderOptions['onProgress']>) => (pages: number, completed: number, total: number) =>
+    onProgress({ stage: 'layout', pages, sections: { completed, total } })
+
+const FIT_STEP = 0.05
+const FIT_MIN_SCALE = 0.8
+
+// <html data-fit-to-pages="N"> asks for the report to be squeezed onto N pages. Type and spacing shrink in small
+// steps, re-paginating each time, and stop at 80% so the text stays readable; past that it ships over budget.
+const fitToPages = async (page: Page, layout: (scale: number) => Promise<void>, warnings: RenderWarning[]) => {
+    const budget = await page.$eval('html', (html) => Number(html.dataset.fitToPages) || undefined)
+    if (!budget) return
+    const pageCount = () => page.$$eval('.pagedjs_page', (pages) => pages.length)
+    let scale = 1
+    while ((await pageCount()) > budget && scale - FIT_STEP >= FIT_MIN_SCALE - 1e-9) {
+        scale -= FIT_STEP
+        await layout(Number(scale.toFixed(2)))
+    }
+    const pages = await pageCount()
+    if (pages > budget) {
+        warnings.push({ source: 'layout', message: `Report needs ${pages} pages at minimum scale; fitToPages asked for ${budget}` })
+    }
+}
+
+// Non-fatal problems (failed asset loads, script errors, console warnings) that would otherwise ship silently.
+const collectWarnings = (page: Page) => {
+    const warnings: RenderWarning[] = []
+    page.on('console', (msg) => {
+        if (msg.type() === 'warn' || msg.type() === 'error') warnings.push({ source: 'console', message: msg.text() })
+    })
+    page.on('pageerror', (error) => warnings.push({ source: 'page-error', message: String(error) }))
+    page.on('requestfailed', (request) =>
+        warnings.push({ source: 'request', message: `${request.url()} failed: ${request.failure()?.errorText ?? 'unknown'}` })
+    )
+    page.on('response', (response) => {
+        if (response.status() >= 400) warnings.push({ source: 'request', message: `${response.url()} returned ${response.status()}` })
+    })
+    return warnings
+}
+
+// No navigation timeout of its own: the render deadline in withPage covers loading, and a slow load is a render_timeout
+// rather than Puppeteer's 30s default surfacing as resource_load_failed.
+const loadHtml = async (page: Page, html: string, fromFile?: boolean) => {
+    if (!fromFile) return page.setContent(html, { waitUntil: 'networkidle0', timeout: 0 })
+    const file = join(tmpdir(), `reportflow-${randomUUID()}.html`)
+    await writeFile(file, html)
+    try {
+        await page.goto(pathToFileURL(file).href, { waitUntil: 'networkidle0', timeout: 0 })
+    } finally {
+        await rm(file, { force: true })
+    }
+}
+
+// Any installed font whose file name mentions emoji (e.g. NotoColorEmoji.ttf) is used for the template's emoji fallback.
+const emojiFont = (resources: Resources) => resources.fonts.find((font) => /emoji/i.test(font.name))?.name
+
+const applyResources = async (page: Page, { resources, template, brandProfile }: RenderOptions) => {
+    const fonts = fontFaceCss(resources.fonts)
+    if (fonts) await page.addStyleTag({ content: fonts })
+    const emoji = emojiFont(resources)
+    if (emoji) await page.addStyleTag({ content: `:root { --rf-emoji-font: "${emoji}"; }` })
+    if (template) {
+        const stylesheet = resources.templates.find((candidate) => candidate.name === template)
+        if (!stylesheet) throw new PdfError('invalid_payload', `Unknown template "${template}"`)
+        await page.addStyleTag({ content: stylesheet.data })
+    }
+    // Applied last so a template stylesheet can't override the tenant's branding either.
+    const profile = await resolveBrandProfile(page, brandProfiles(resources), brandProfile)
+    if (profile) await applyBrandProfile(page, profile)
+}
+
+// The template applies the bundle's emoji policy and records it on <html>; say what happened so nobody is surprised by
+// missing or substituted emoji.
+const emojiWarning = async (page: Page, resources: Resources): Promise<RenderWarning | undefined> => {
+    const { policy, count, dropped } = await page.$eval('html', (root) => ({
+        policy: root.dataset.emoji,
+        count: Number(root.dataset.emojiCount) || 0,
+        dropped: Number(root.dataset.emojiDropped) || 0,
+    }))
+    if (!policy || !count) return undefined
+    const emoji = `${count} emoji`
+    if (policy === 'strip') return { source: 'content', message: `Removed ${emoji} (emoji policy "strip")` }
+    if (policy === 'shortcode') {
+        const replaced = count - dropped
+        const message = dropped
+            ? `Replaced ${replaced} emoji with text shortcodes and removed ${dropped} that have none (emoji policy "shortcode")`
+            : `Replaced ${emoji} with text shortcodes (emoji policy "shortcode")`
+        return { source: 'content', message }
+    }
+    const font = emojiFont(resources)
+    return font
+        ? { source: 'content', message: `Drew ${emoji} with the "${font}" font (emoji policy "font")` }
+        : { source: 'content', message: `Drew ${emoji} with the system's emoji font; none is installed in the assets fonts directory (emoji policy "font")` }
+}
+
+// Legacy fields sent with a blocks document, which the template names on <html> rather than rendering.
+const ignoredFieldsWarning = async (page: Page): Promise<RenderWarning | undefined> => {
+    const fields = await page.$eval('html', (root) => root.dataset.ignoredFields)
+    if (!fields) return undefined
+    return { source: 'content', message: `Ignored ${fields.split(' ').join(', ')}: a document with blocks renders only its blocks` }
+}
+
+// Closing the render's browser context is the only reliable way to abort a stuck render; the timeout then reports how far it got.
+export const withPage = async <T>(html: string, options: RenderOptions, run: (page: Page) => Promise<T>) => {
+    const startedAt = Date.now()
+    const { trace, span } = createTrace()
+    const browser = await span('launch', () => stage('generation_failed', sharedBrowser))
+    const context = await stage('generation_failed', () => browser.createBrowserContext())
+    let progress: RenderProgress['stage'] = 'loading'
+    const report = (next: RenderProgress['stage']) => {
+        progress = next
+        options.onProgress?.({ stage: next })
+    }
+    let warnings: RenderWarning[] = []
+    let timedOut = false
+    const timer = setTimeout(() => {
+        timedOut = true
+        void context.close().catch(() => undefined)
+    }, config.renderTimeoutMs)
+    try {
+        const page = await context.newPage()
+        warnings = collectWarnings(page)
+        if (options.onProgress) await page.exposeFunction('reportflowProgress', layoutProgress(options.onProgress))
+        const layout = async (scale?: number) => {
+            // Each attempt reloads from scratch, so only the last attempt's warnings are kept.
+            warnings.length = 0
+            report('loading')
+            await stage('resource_load_failed', async () => {
+                await span('load', () => loadHtml(page, html, options.fromFile))
+                await span('resources', () => applyResources(page, options))
+                if (scale) await page.addStyleTag({ content: `:root { --rf-fit-scale: ${scale}; }` })
+            })
+            report('layout')
+            await stage('layout_failed', async () => {
+                await span('images', () => page.evaluate(applyImageDpi))
+                const punctuation = await page.evaluate(downgradeUncoveredPunctuation)
+                if (punctuation) {
+                    warnings.push({
+                        source: 'content',
+                        message: `Replaced ${punctuation.characters.join(' ')} with ASCII; the "${punctuation.font}" font has no glyphs for them`,
+                    })
+                }
+                await span('paginate', () => paginate(page, options.forcePaged, Boolean(options.onProgress)))
+            })
+        }
+        await layout()
+        await fitToPages(page, layout, warnings)
+        const emoji = await emojiWarning(page, options.resources)
+        if (emoji) warnings.push(emoji)
+        const ignored = await ignoredFieldsWarning(page)
+        if (ignored) warnings.push(ignored)
+        trace.sections = await page.evaluate(measureSections).catch(() => [])
+        report('output')
+        const result = await span('output', () => run(page))
+        logTrace('render', trace)
+        return { result, warnings, trace }
+    } catch (error) {
+        // Any other Puppeteer wait that gives up is a timeout too, whichever stage wrapped it.
+        const waitTimedOut = error instanceof TimeoutError || (error instanceof PdfError && error.cause instanceof TimeoutError)
+        if (!timedOut && !waitTimedOut) throw error
+        const message = timedOut ? `Render exceeded ${config.renderTimeoutMs}ms` : `Render timed out while ${progress}: ${(error as Error).message}`
+        throw new PdfError('render_timeout', message, {
+            cause: error,
+            details: { stage: progress, elapsedMs: Date.now() - startedAt, stages: trace.stages, warnings },
+        })
+    } finally {
+        clearTimeout(timer)
+        await context.close().catch(() => undefined)
+    }
+}
+
+type Finishing = {
+    attachments: Attachment[]
+    formFields: FormPlacement[]
+    trimBlankPages: boolean
+    // Set in deterministic mode; the hash of the input the ids are derived from.
+    seed?: string
+}
+
+// Chrome can't emit embedded files or form widgets, so they are added to the finished PDF;
+// the tag tree and outline survive pdf-lib's rewrite. The document is always parsed, for the page count.
+const finishPdf = async (pdf: Uint8Array, { attachments, formFields, trimBlankPages, seed }: Finishing) => {
+    const document = await PDFDocument.load(pdf, { updateMetadata: false })
+    await embedAttachments(document, attachments, seed ? DETERMINISTIC_DATE : new Date())
+    addFormFields(document, formFields)
+    // Runs last so page indexes measured for form fields still match; widget pages are never blank.
+    const removed = trimBlankPages ? removeBlankPages(document) : 0
+    const pages = document.getPageCount()
+    if (seed) makeDeterministic(document, seed)
+    if (!attachments.length && !formFields.length && !removed && !seed) return { pdf, pages }
+    return { pdf: await document.save(), pages }
+}
+
+// Everything that shapes the output, so two renders share a seed exactly when they should share bytes.
+const renderSeed = (html: string, { template, brandProfile, attachments }: RenderOptions) =>
+    createHash('sha256').update(JSON.stringify([html, template, brandProfile, attachments])).digest('hex')
+
+export const renderPdf = async (html: string, options: RenderOptions) => {
+    const startedAt = performance.now()
+    const { result, warnings } = await withPage(html, options, async (page) => {
+        const formFields = await stage('layout_failed', () => page.evaluate(measureFormFields))
+        const names = formFields.map((field) => field.name)
+        const duplicate = names.find((name, idx) => names.indexOf(name) !== idx)
+        if (duplicate != null) throw new PdfError('invalid_payload', `Form field "${duplicate}" appears more than once; field names must be unique`)
+        // Deliberate blank versos (duplex sections starting on the right) are marked by the template.
+        const trimBlankPages = !(await page.$('html[data-keep-blank-pages]'))
+        // Recorded by the report template; kept with the report history so past reports can be found by client.
+        const subject = await page.$eval('html', (root) => ({ clientName: root.dataset.clientName, reportDate: root.dataset.reportDate }))
+        const finished = await stage('serialization_failed', async () =>
+            finishPdf(await page.pdf(PDF_OPTIONS), {
+                attachments: options.attachments ?? [],
+                formFields,
+                trimBlankPages,
+                seed: options.deterministic ? renderSeed(html, options) : undefined,
+            })
+        )
+        return { ...finished, subject }
+    })
+    const renderMs = Math.round(performance.now() - startedAt)
+    renderDuration.record(renderMs)
+    renderPages.record(result.pages)
+    return { ...result, renderMs, warnings }
+}
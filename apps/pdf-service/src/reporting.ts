@@ -0,0 +1,52 @@
+import { config } from './config'
+
+const REPORT_TIMEOUT_MS = 5000
+
+export type ErrorContext = {
+    errorId?: string
+    code?: string
+    route?: string
+    payloadHash?: string
+    // Shape of the payload only, never its content.
+    tenant?: string
+    template?: string
+    htmlBytes?: number
+    sections?: number
+    attachments?: number
+}
+
+const describeError = (error: unknown) =>
+    error instanceof Error
+        ? { name: error.name, message: error.message, stack: error.stack, cause: error.cause ? describeError(error.cause) : undefined }
+        : { name: 'NonError', message: String(error) }
+
+export function payloadMetadata(body: unknown): ErrorContext {
+    const { html, template, brandProfile, attachments } = (body ?? {}) as Record<string, unknown>
+    return {
+        tenant: typeof brandProfile === 'string' ? brandProfile : undefined,
+        template: typeof template === 'string' ? template : undefined,
+        htmlBytes: typeof html === 'string' ? Buffer.byteLength(html) : undefined,
+        sections: typeof html === 'string' ? (html.match(/<section[\s>]/g) ?? []).length : undefined,
+        attachments: Array.isArray(attachments) ? attachments.length : undefined,
+    }
+}
+
+// Fire-and-forget: a slow or failing reporting backend must never delay or break the response. Only the crash handler
+// awaits it, so the report is sent before the process exits.
+export async function reportError(error: unknown, context: ErrorContext = {}) {
+    const { url, environment } = config.errorReporting
+    if (!url) return
+    const event = {
+        service: 'pdf-service',
+        environment,
+        timestamp: new Date().toISOString(),
+        error: describeError(error),
+        context,
+    }
+    await fetch(url, {
+        method: 'POST',
+        headers: { 'Content-Type': 'application/json' },
+        body: JSON.stringify(event),
+        signal: AbortSignal.timeout(REPORT_TIMEOUT_MS),
+    }).catch((reportFailure) => console.error('failed to send error report', reportFailure))
+}
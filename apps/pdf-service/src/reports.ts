@@ -0,0 +1,59 @@
+import { createHmac, timingSafeEqual } from 'node:crypto'
+import { mkdir, readFile, writeFile } from 'node:fs/promises'
+import { join } from 'node:path'
+import { type Delivery, auditReport } from './audit'
+import { config } from './config'
+import { PdfError } from './errors'
+import { recordHistory } from './history'
+
+const REPORT_ID = /^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$/
+
+const signature = (id: string, expires: number) =>
+    createHmac('sha256', config.reports.signingSecret ?? '').update(`${id}.${expires}`).digest('base64url')
+
+// Only stored reports can be linked to, and only when a secret is configured to sign the links.
+export function signedUrl(id: string, ttlSeconds = config.reports.linkTtlSeconds) {
+    if (!config.reports.dir || !config.reports.signingSecret) return undefined
+    const expires = Math.floor(Date.now() / 1000) + ttlSeconds
+    return {
+        url: `${config.reports.publicUrl}/reports/${id}?expires=${expires}&sig=${signature(id, expires)}`,
+        expiresAt: new Date(expires * 1000).toISOString(),
+    }
+}
+
+export function verifySignature(id: string, expires: unknown, sig: unknown) {
+    if (!config.reports.signingSecret) throw new PdfError('not_found', 'Report links are not enabled')
+    if (typeof expires !== 'string' || typeof sig !== 'string' || !/^\d+$/.test(expires)) {
+        throw new PdfError('unauthorized', 'Link is missing its signature')
+    }
+    const expected = Buffer.from(signature(id, Number(expires)))
+    const provided = Buffer.from(sig)
+    if (expected.length !== provided.length || !timingSafeEqual(expected, provided)) throw new PdfError('unauthorized', 'Invalid link signature')
+    if (Number(expires) * 1000 < Date.now()) throw new PdfError('link_expired', 'Link has expired')
+}
+
+const reportFile = (id: string) => {
+    if (!config.reports.dir || !REPORT_ID.test(id)) throw new PdfError('not_found', `Unknown report "${id}"`)
+    return join(config.reports.dir, `${id}.pdf`)
+}
+
+export async function readStoredReport(id: string) {
+    try {
+        return await readFile(reportFile(id))
+    } catch (error) {
+        if ((error as NodeJS.ErrnoException).code === 'ENOENT') throw new PdfError('not_found', `Unknown report "${id}"`)
+        throw error
+    }
+}
+
+type Rendered = { pdf: Uint8Array; pages: number; subject: { clientName?: string; reportDate?: string } }
+
+// Audits the delivery and, with storage configured, keeps the PDF and returns a download link for it.
+export async function recordReport(delivery: Delivery, body: unknown, { pdf, pages, subject }: Rendered) {
+    const record = auditReport(delivery, body, pdf, pages)
+    await recordHistory(record, subject)
+    if (!config.reports.dir) return { ...record, link: undefined }
+    await mkdir(config.reports.dir, { recursive: true })
+    await writeFile(reportFile(record.reportId), pdf)
+    return { ...record, link: signedUrl(record.reportId) }
+}
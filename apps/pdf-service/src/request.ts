@@ -0,0 +1,60 @@
+import { type DraftBundle, isCurrencyCode, redactionPattern, renderComparisonReport } from 'report-template'
+import { readAttachments } from './attachments'
+import { PdfError } from './errors'
+
+// Shared by the HTTP and gRPC front ends so both validate payloads identically.
+export const readRequest = (body: unknown) => {
+    const { html, template, brandProfile, attachments, deterministic } = (body ?? {}) as Record<string, unknown>
+    if (typeof html !== 'string' || !html.trim()) throw new PdfError('invalid_payload', 'Body must include a non-empty "html" string')
+    if (template != null && typeof template !== 'string') throw new PdfError('invalid_payload', '"template" must be a string')
+    if (brandProfile != null && typeof brandProfile !== 'string') throw new PdfError('invalid_payload', '"brandProfile" must be a string')
+    if (deterministic != null && typeof deterministic !== 'boolean') throw new PdfError('invalid_payload', '"deterministic" must be a boolean')
+    return {
+        html,
+        template: template ?? undefined,
+        brandProfile: brandProfile ?? undefined,
+        attachments: readAttachments(attachments),
+        deterministic: deterministic ?? undefined,
+    }
+}
+
+const readBundle = (value: unknown, field: string) => {
+    const bundle = value as DraftBundle | undefined
+    if (!bundle || typeof bundle !== 'object' || Array.isArray(bundle)) throw new PdfError('invalid_payload', `"${field}" must be a report bundle`)
+    if (typeof bundle.clientName !== 'string' || typeof bundle.date !== 'string') {
+        throw new PdfError('invalid_payload', `"${field}" must include "clientName" and "date" strings`)
+    }
+    if (!Array.isArray(bundle.kpis)) throw new PdfError('invalid_payload', `"${field}.kpis" must be an array`)
+    if (bundle.growthCategories != null && !Array.isArray(bundle.growthCategories)) {
+        throw new PdfError('invalid_payload', `"${field}.growthCategories" must be an array`)
+    }
+    const kpis = [...bundle.kpis, ...(bundle.growthCategories ?? []).flatMap((category) => category?.kpis ?? [])]
+    for (const kpi of kpis) {
+        if (kpi?.valueType === 'currency' && kpi.unit != null && !(typeof kpi.unit === 'string' && isCurrencyCode(kpi.unit))) {
+            throw new PdfError('invalid_payload', `"${field}": KPI "${kpi.name}" has unit "${kpi.unit}", which is not an ISO 4217 currency code`)
+        }
+    }
+    if (bundle.formFields != null && !Array.isArray(bundle.formFields)) {
+        throw new PdfError('invalid_payload', `"${field}.formFields" must be an array`)
+    }
+    // Each id names an AcroForm field, and a PDF form can't hold two fields with the same name.
+    const fieldIds = (bundle.formFields ?? []).map((formField) => formField?.id)
+    const duplicate = fieldIds.find((id, idx) => fieldIds.indexOf(id) !== idx)
+    if (duplicate != null) throw new PdfError('invalid_payload', `"${field}.formFields" uses the id "${duplicate}" more than once`)
+    // Compiled here so a broken or runaway pattern is a 400 rather than a failure halfway through rendering.
+    for (const pattern of bundle.redaction?.patterns ?? []) {
+        try {
+            redactionPattern(pattern)
+        } catch (error) {
+            throw new PdfError('invalid_payload', `"${field}.redaction.patterns": ${(error as Error).message}`)
+        }
+    }
+    return bundle
+}
+
+// Comparisons arrive as two report bundles rather than HTML; the rest of the payload is read like POST /pdf.
+export const readComparison = (body: unknown) => {
+    const { current, previous, ...rest } = (body ?? {}) as Record<string, unknown>
+    const html = renderComparisonReport(readBundle(current, 'current'), readBundle(previous, 'previous'))
+    return readRequest({ ...rest, html })
+}
@@ -0,0 +1,141 @@
+import { createHash } from 'node:crypto'
+import { watch } from 'node:fs'
+import { readFile, readdir, stat } from 'node:fs/promises'
+import { basename, dirname, extname, join } from 'node:path'
+import { type LoadedBrandProfile, parseBrandProfile } from './brand'
+
+export type ResourceFile = {
+    name: string
+    file: string
+    bytes: number
+    sha256: string
+    modifiedAt: string
+}
+
+type Loaded<T> = ResourceFile & { data: T }
+
+export type Resources = {
+    fonts: Loaded<Buffer>[]
+    templates: Loaded<string>[]
+    profiles: Loaded<LoadedBrandProfile>[]
+    loadedAt: string
+}
+
+const RELOAD_DEBOUNCE_MS = 200
+
+const FONT_FORMATS: Record<string, string> = {
+    '.woff2': 'woff2',
+    '.woff': 'woff',
+    '.ttf': 'truetype',
+    '.otf': 'opentype',
+}
+
+const IMAGE_TYPES: Record<string, string> = {
+    '.svg': 'image/svg+xml',
+    '.png': 'image/png',
+    '.jpg': 'image/jpeg',
+    '.jpeg': 'image/jpeg',
+    '.webp': 'image/webp',
+}
+
+const listFiles = async (dir: string, extensions: string[]) => {
+    try {
+        const entries = await readdir(dir, { withFileTypes: true })
+        return entries
+            .filter((entry) => entry.isFile() && extensions.includes(extname(entry.name).toLowerCase()))
+            .map((entry) => join(dir, entry.name))
+            .sort()
+    } catch (error) {
+        if ((error as NodeJS.ErrnoException).code === 'ENOENT') return []
+        throw error
+    }
+}
+
+const loadFile = async (file: string) => {
+    const [data, info] = await Promise.all([readFile(file), stat(file)])
+    return {
+        name: basename(file, extname(file)),
+        file,
+        bytes: data.length,
+        sha256: createHash('sha256').update(data).digest('hex'),
+        modifiedAt: info.mtime.toISOString(),
+        data,
+    }
+}
+
+export const loadProfile = async (file: string) => {
+    const loaded = await loadFile(file)
+    const profile: LoadedBrandProfile = parseBrandProfile(file, loaded.data.toString('utf8'))
+    if (profile.logo) {
+        const logoFile = join(dirname(file), basename(profile.logo))
+        const type = IMAGE_TYPES[extname(logoFile).toLowerCase()]
+        if (!type) throw new Error(`Brand profile ${file} has an unsupported logo type "${profile.logo}"`)
+        profile.logoDataUrl = `data:${type};base64,${(await readFile(logoFile)).toString('base64')}`
+    }
+    return { ...loaded, data: profile }
+}
+
+// Assets layout: <dir>/fonts/*.{woff2,woff,ttf,otf}, <dir>/templates/*.css and <dir>/profiles/<tenant>.json.
+export async function loadResources(dir: string): Promise<Resources> {
+    const [fontFiles, templateFiles, profileFiles] = await Promise.all([
+        listFiles(join(dir, 'fonts'), Object.keys(FONT_FORMATS)),
+        listFiles(join(dir, 'templates'), ['.css']),
+        listFiles(join(dir, 'profiles'), ['.json']),
+    ])
+    const fonts = await Promise.all(fontFiles.map(loadFile))
+    const templates = (await Promise.all(templateFiles.map(loadFile))).map((template) => ({
+        ...template,
+        data: template.data.toString('utf8'),
+    }))
+    const profiles = await Promise.all(profileFiles.map(loadProfile))
+    return { fonts, templates, profiles, loadedAt: new Date().toISOString() }
+}
+
+export const brandProfiles = (resources: Resources) =>
+    Object.fromEntries(resources.profiles.map((profile) => [profile.name, profile.data]))
+
+// Fonts are exposed to templates under their file name, e.g. fonts/Inter.woff2 → font-family: "Inter".
+export function fontFaceCss(fonts: Resources['fonts']) {
+    return fonts
+        .map((font) => {
+            const format = FONT_FORMATS[extname(font.file).toLowerCase()] ?? 'truetype'
+            return `@font-face { font-family: "${font.name}"; src: url(data:font/${format};base64,${font.data.toString('base64')}) format("${format}"); }`
+        })
+        .join('\n')
+}
+
+const strip = ({ data: _data, ...file }: Loaded<unknown>): ResourceFile => file
+
+export const describeFile = async (file: string) => strip(await loadFile(file))
+
+export function describeResources(resources: Resources) {
+    return {
+        loadedAt: resources.loadedAt,
+        fonts: resources.fonts.map(strip),
+        templates: resources.templates.map(strip),
+        profiles: resources.profiles.map(strip),
+    }
+}
+
+// Editors tend to emit several events per save, so reloads are debounced; a failed reload keeps the previous set.
+export function watchResources(dir: string, onReload: (resources: Resources) => void) {
+    let timer: ReturnType<typeof setTimeout> | undefined
+    const reload = () => {
+        clearTimeout(timer)
+        timer = setTimeout(async () => {
+            try {
+                const next = await loadResources(dir)
+                onReload(next)
+                console.log(`reloaded assets from ${dir}: ${next.fonts.length} fonts, ${next.templates.length} templates, ${next.profiles.length} brand profiles`)
+            } catch (error) {
+                console.error(`failed to reload assets from ${dir}`, error)
+            }
+        }, RELOAD_DEBOUNCE_MS)
+    }
+    try {
+        return watch(dir, { recursive: true }, reload)
+    } catch (error) {
+        console.error(`cannot watch assets directory ${dir}`, error)
+        return undefined
+    }
+}
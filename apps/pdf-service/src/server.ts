@@ -1,21 +1,43 @@
-import express from 'express'
-import cors from 'cors'
-import puppeteer from 'puppeteer'
+import type { Server } from '@grpc/grpc-js'
+import { shutdownTelemetry } from './telemetry'
+import { app, currentResources } from './app'
+import { closeBrowser } from './browser'
+import { config } from './config'
+import { startGrpcServer } from './grpc'
+import { reportError } from './reporting'
 
-const app = express()
-app.use(cors())
-app.use(express.json({ limit: '4mb' }))
+// Whatever threw has left the process in an unknown state: report it, then exit non-zero so the supervisor restarts it.
+const crash = (code: string) => (error: unknown) => {
+    console.error(`[${code}]`, error)
+    void reportError(error, { code }).finally(() => process.exit(1))
+}
+process.on('uncaughtException', crash('uncaught_exception'))
+process.on('unhandledRejection', crash('unhandled_rejection'))
 
-app.post('/pdf', async (req, res) => {
-    const { html } = req.body as { html: string }
-    const browser = await puppeteer.launch({ args: ['--no-sandbox'] })
-    const page = await browser.newPage()
-    await page.setContent(html, { waitUntil: 'networkidle0' })
-    const pdf = await page.pdf({ format: 'Letter', printBackground: true, preferCSSPageSize: true })
-    await browser.close()
-    res.setHeader('Content-Type', 'application/pdf')
-    res.setHeader('Content-Disposition', 'inline; filename="report.pdf"')
-    res.send(pdf)
-})
+const http = app.listen(config.port, config.host, () => console.log(`pdf-service on ${config.host}:${config.port}`))
+let grpc: Promise<Server | undefined> = Promise.resolve(undefined)
+if (config.grpc.port != null) {
+    const { port, tlsCert, tlsKey } = config.grpc
+    const tls = tlsCert && tlsKey ? { cert: tlsCert, key: tlsKey } : undefined
+    grpc = startGrpcServer({ host: config.host, port, resources: currentResources, token: config.renderToken, tls }).catch((error) => {
+        console.error('[grpc] failed to start', error)
+        process.exit(1)
+    })
+}
 
-app.listen(3001, () => console.log('pdf-service on :3001'))
+// In-flight renders finish before the browser goes away, and telemetry is flushed last so their spans are exported.
+const SHUTDOWN_GRACE_MS = 30_000
+
+const shutdown = async (signal: string) => {
+    console.log(`pdf-service received ${signal}, shutting down`)
+    setTimeout(() => process.exit(1), SHUTDOWN_GRACE_MS).unref()
+    await Promise.allSettled([
+        new Promise((resolve) => http.close(resolve)),
+        grpc.then((server) => server && new Promise((resolve) => server.tryShutdown(resolve))),
+    ])
+    await closeBrowser().catch((error) => console.error('[shutdown] closing the browser failed', error))
+    await shutdownTelemetry().catch((error) => console.error('[shutdown] flushing telemetry failed', error))
+    process.exit(0)
+}
+process.once('SIGTERM', () => void shutdown('SIGTERM'))
+process.once('SIGINT', () => void shutdown('SIGINT'))
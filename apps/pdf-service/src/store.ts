@@ -0,0 +1,86 @@
+import { copyFile, mkdir, mkdtemp, rename, rm, writeFile } from 'node:fs/promises'
+import { dirname, join } from 'node:path'
+import type { BrandProfile } from './brand'
+import { PdfError } from './errors'
+import { loadProfile } from './resources'
+
+// Ids become file names, so anything that could escape the assets directory is rejected outright.
+const ASSET_ID = /^[a-z0-9][a-z0-9_-]{0,63}$/i
+
+const LOGO_EXTENSIONS: Record<string, string> = {
+    'image/svg+xml': '.svg',
+    'image/png': '.png',
+    'image/jpeg': '.jpg',
+    'image/webp': '.webp',
+}
+
+export function assetId(id: string) {
+    if (!ASSET_ID.test(id)) throw new PdfError('invalid_payload', `Invalid id "${id}"`)
+    return id
+}
+
+const write = async (file: string, data: string | Buffer) => {
+    await mkdir(dirname(file), { recursive: true })
+    await writeFile(file, data)
+}
+
+// Logos arrive inline as data URLs and are stored next to the profile, which then references the file by name. Any
+// other value must be the logo already stored for this same profile, so one profile can never point at another's files.
+const stageLogo = async (dir: string, staging: string, id: string, logo: string) => {
+    const match = /^data:([\w/+.-]+);base64,(.*)$/s.exec(logo)
+    if (!match) {
+        if (!new RegExp(`^${id}\\.(?:svg|png|jpe?g|webp)$`, 'i').test(logo)) {
+            throw new PdfError('invalid_payload', `"logo" must be a data URL or this profile's stored logo "${id}.<ext>"`)
+        }
+        await copyFile(join(dir, 'profiles', logo), join(staging, logo)).catch((error) => {
+            throw new PdfError('invalid_payload', `Logo "${logo}" is not stored for profile "${id}"`, { cause: error })
+        })
+        return logo
+    }
+    const extension = LOGO_EXTENSIONS[match[1]]
+    if (!extension) throw new PdfError('invalid_payload', `Unsupported logo type "${match[1]}"`)
+    const file = `${id}${extension}`
+    await writeFile(join(staging, file), Buffer.from(match[2], 'base64'))
+    return file
+}
+
+export async function saveProfile(dir: string, id: string, body: unknown) {
+    if (!body || typeof body !== 'object' || Array.isArray(body)) throw new PdfError('invalid_payload', 'Profile must be a JSON object')
+    const { logoDataUrl: _inlined, ...profile } = body as BrandProfile & { logoDataUrl?: unknown }
+    if (profile.logo != null && typeof profile.logo !== 'string') throw new PdfError('invalid_payload', '"logo" must be a string')
+    // Profile and logo are staged in a directory the loader never lists, and only renamed into place once the profile
+    // loads the way a reload would, so a bad profile can never break the next reload or startup, nor replace the live logo.
+    const profiles = join(dir, 'profiles')
+    await mkdir(profiles, { recursive: true })
+    const staging = await mkdtemp(join(profiles, `.${id}-`))
+    try {
+        if (profile.logo) profile.logo = await stageLogo(dir, staging, id, profile.logo)
+        const staged = join(staging, `${id}.json`)
+        await writeFile(staged, `${JSON.stringify(profile, null, 4)}\n`)
+        try {
+            await loadProfile(staged)
+        } catch (error) {
+            throw new PdfError('invalid_payload', error instanceof Error ? error.message.replace(staged, `profile "${id}"`) : 'Invalid profile', {
+                cause: error,
+            })
+        }
+        if (profile.logo) await rename(join(staging, profile.logo), join(profiles, profile.logo))
+        await rename(staged, join(profiles, `${id}.json`))
+    } finally {
+        await rm(staging, { recursive: true, force: true })
+    }
+}
+
+export async function deleteProfile(dir: string, id: string, profile: BrandProfile) {
+    await rm(join(dir, 'profiles', `${id}.json`))
+    if (profile.logo?.startsWith(`${id}.`)) await rm(join(dir, 'profiles', profile.logo), { force: true })
+}
+
+export async function saveTemplate(dir: string, id: string, css: unknown) {
+    if (typeof css !== 'string' || !css.trim()) throw new PdfError('invalid_payload', 'Template body must be a non-empty text/css document')
+    await write(join(dir, 'templates', `${id}.css`), css)
+}
+
+export async function deleteTemplate(dir: string, id: string) {
+    await rm(join(dir, 'templates', `${id}.css`))
+}
@@ -0,0 +1,51 @@
+import { ROOT_CONTEXT, SpanKind, SpanStatusCode, context, metrics, propagation, trace } from '@opentelemetry/api'
+import { OTLPMetricExporter } from '@opentelemetry/exporter-metrics-otlp-http'
+import { OTLPTraceExporter } from '@opentelemetry/exporter-trace-otlp-http'
+import { PeriodicExportingMetricReader } from '@opentelemetry/sdk-metrics'
+import { NodeSDK } from '@opentelemetry/sdk-node'
+import type { RequestHandler } from 'express'
+
+// Off unless an OTLP endpoint is configured; endpoint, headers, service name and sampling all come from the
+// standard OTEL_* variables, which the exporters and the SDK read themselves.
+const enabled =
+    process.env.OTEL_SDK_DISABLED !== 'true' &&
+    Boolean(process.env.OTEL_EXPORTER_OTLP_ENDPOINT || process.env.OTEL_EXPORTER_OTLP_TRACES_ENDPOINT)
+
+const sdk = enabled
+    ? new NodeSDK({
+          serviceName: process.env.OTEL_SERVICE_NAME ?? 'pdf-service',
+          traceExporter: new OTLPTraceExporter(),
+          metricReader: new PeriodicExportingMetricReader({ exporter: new OTLPMetricExporter() }),
+          // require-in-the-middle patching never sees Bun's ESM imports, so requests are traced by traceRequests below.
+          instrumentations: [],
+      })
+    : undefined
+sdk?.start()
+
+// Flushes buffered spans and metrics; server.ts calls it as part of its own shutdown.
+export const shutdownTelemetry = () => sdk?.shutdown() ?? Promise.resolve()
+
+// Both resolve to no-ops when the SDK isn't started, so callers never need to check `enabled`.
+export const tracer = trace.getTracer('pdf-service')
+const meter = metrics.getMeter('pdf-service')
+
+// The caller's traceparent becomes the parent of a server span, which the render spans then nest under.
+export const traceRequests: RequestHandler = (req, res, next) => {
+    const parent = propagation.extract(ROOT_CONTEXT, req.headers)
+    const span = tracer.startSpan(`${req.method} ${req.path}`, { kind: SpanKind.SERVER, attributes: { 'http.request.method': req.method } }, parent)
+    res.once('close', () => {
+        // The matched route is only known once the router has run; it keeps ids out of span names.
+        if (req.route?.path) span.updateName(`${req.method} ${req.baseUrl}${req.route.path}`)
+        span.setAttribute('http.response.status_code', res.statusCode)
+        if (res.statusCode >= 500) span.setStatus({ code: SpanStatusCode.ERROR })
+        span.end()
+    })
+    context.with(trace.setSpan(parent, span), next)
+}
+
+export const renderDuration = meter.createHistogram('reportflow.render.duration', {
+    unit: 'ms',
+    description: 'Wall-clock time to produce a PDF',
+})
+export const renderPages = meter.createHistogram('reportflow.render.pages', { description: 'Pages per rendered PDF' })
+export const renderFailures = meter.createCounter('reportflow.render.failures', { description: 'Failed renders by error code' })
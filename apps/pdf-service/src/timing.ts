@@ -0,0 +1,60 @@
+import { SpanStatusCode } from '@opentelemetry/api'
+import { tracer } from './telemetry'
+
+export type SectionCost = {
+    id: string
+    // Element count is the closest thing HTML has to an op count; charts and big tables dominate it.
+    nodes: number
+    images: number
+    // Only known after paged.js has laid out the document.
+    pages?: number
+}
+
+export type RenderTrace = {
+    stages: Record<string, number>
+    sections: SectionCost[]
+}
+
+export function createTrace() {
+    const trace: RenderTrace = { stages: {}, sections: [] }
+    const span = <T>(name: string, run: () => Promise<T>) =>
+        tracer.startActiveSpan(`render.${name}`, async (otelSpan) => {
+            const startedAt = performance.now()
+            try {
+                return await run()
+            } catch (error) {
+                otelSpan.setStatus({ code: SpanStatusCode.ERROR, message: error instanceof Error ? error.message : String(error) })
+                throw error
+            } finally {
+                trace.stages[name] = Math.round(performance.now() - startedAt)
+                otelSpan.end()
+            }
+        })
+    return { trace, span }
+}
+
+// Runs in the browser. Top-level blocks with an id are the report's sections (summary, questions, tables, ...).
+export const measureSections = (): SectionCost[] =>
+    Array.from(document.querySelectorAll<HTMLElement>('main [id]'))
+        .filter((element) => !element.parentElement?.closest('main [id]'))
+        .map((element) => {
+            // paged.js tags every fragment of a split element with the same data-ref.
+            const ref = element.dataset.ref
+            const fragments = ref ? Array.from(document.querySelectorAll(`[data-ref="${ref}"]`)) : [element]
+            const pages = new Set(fragments.map((fragment) => fragment.closest('.pagedjs_page')).filter(Boolean))
+            return {
+                id: element.id,
+                nodes: element.getElementsByTagName('*').length + 1,
+                images: element.querySelectorAll('img, svg').length,
+                pages: pages.size || undefined,
+            }
+        })
+
+const HEAVIEST_SECTIONS = 5
+
+// Goes to stderr so `reportflow-pdf render -o -` still writes nothing but the PDF to stdout.
+export function logTrace(label: string, { stages, sections }: RenderTrace) {
+    const total = Object.values(stages).reduce((sum, ms) => sum + ms, 0)
+    const heaviest = [...sections].sort((a, b) => b.nodes - a.nodes).slice(0, HEAVIEST_SECTIONS)
+    console.error(JSON.stringify({ event: label, totalMs: total, stages, sections: heaviest }))
+}
@@ -0,0 +1,50 @@
+export type PunctuationFallback = {
+    font: string
+    characters: string[]
+}
+
+// Runs in the page once fonts have loaded, before pagination so the substitutes are laid out like any other text.
+// Typographic punctuation is only replaced with ASCII when the report's own font has no glyph for it; the browser
+// would otherwise draw it from whatever system font it finds, which rarely matches and sometimes isn't there at all.
+export const downgradeUncoveredPunctuation = async (): Promise<PunctuationFallback | null> => {
+    const SUBSTITUTES: Record<string, string> = {
+        '—': '--',
+        '–': '-',
+        '‘': "'",
+        '’': "'",
+        '“': '"',
+        '”': '"',
+        '…': '...',
+    }
+    const GENERIC = /^(serif|sans-serif|monospace|cursive|fantasy|system-ui|ui-\w+|-apple-system|emoji|math)$/i
+    await document.fonts.ready
+    const font = getComputedStyle(document.body).fontFamily.split(',')[0]?.trim().replace(/^["']|["']$/g, '')
+    // Generic and platform families come with full punctuation coverage.
+    if (!font || GENERIC.test(font)) return null
+
+    // A glyph the font lacks is drawn by the fallback, so two different fallbacks give two different widths.
+    const context = document.createElement('canvas').getContext('2d')
+    if (!context) return null
+    const width = (family: string, text: string) => {
+        context.font = `40px ${family}`
+        return context.measureText(text).width
+    }
+    const covered = (char: string) =>
+        width(`"${font}", monospace`, char) === width(`"${font}", serif`, char) || width('monospace', char) === width('serif', char)
+    const missing = Object.keys(SUBSTITUTES).filter((char) => !covered(char))
+    if (!missing.length) return null
+
+    const pattern = new RegExp(`[${missing.join('')}]`, 'g')
+    const used = new Set<string>()
+    const walker = document.createTreeWalker(document.body, NodeFilter.SHOW_TEXT)
+    for (let node = walker.nextNode(); node; node = walker.nextNode()) {
+        if (node.parentElement?.closest('style, script')) continue
+        const text = node.textContent ?? ''
+        const replaced = text.replace(pattern, (char) => {
+            used.add(char)
+            return SUBSTITUTES[char]!
+        })
+        if (replaced !== text) node.textContent = replaced
+    }
+    return used.size ? { font, characters: [...used] } : null
+}
@@ -0,0 +1,84 @@
+export type LayoutWarning = {
+    code: 'text-truncated' | 'card-split' | 'stranded-content' | 'legal-placement' | 'unsupported-character'
+    message: string
+    target?: string
+}
+
+export type LayoutReport = {
+    estimatedPages: number
+    warnings: LayoutWarning[]
+}
+
+// Runs inside the page after paged.js has laid it out, so it can only use browser globals.
+export const inspectLayout = (): LayoutReport => {
+    const CARD_SELECTOR = '.kpi-card, .question-item, .section, .executive-block, .summary-table tr'
+    const UNSUPPORTED = /[\uFFFD\u0000-\u0008\u000B\u000C\u000E-\u001F]/
+    const warnings: LayoutWarning[] = []
+    const describe = (el: Element) =>
+        el.id || el.getAttribute('data-id') || el.textContent?.trim().slice(0, 40) || el.tagName.toLowerCase()
+
+    const seenSplits = new Set<string>()
+    document.querySelectorAll(`[data-split-from]`).forEach((el) => {
+        const card = el.matches(CARD_SELECTOR) ? el : el.closest(CARD_SELECTOR)
+        const ref = card?.getAttribute('data-ref')
+        if (!card || !ref || seenSplits.has(ref)) return
+        seenSplits.add(ref)
+        warnings.push({ code: 'card-split', message: 'Card is split across pages', target: describe(card) })
+    })
+
+    document.querySelectorAll<HTMLElement>('.pagedjs_page_content *').forEach((el) => {
+        const style = getComputedStyle(el)
+        const clips = ['hidden', 'clip'].includes(style.overflowX) || style.textOverflow === 'ellipsis'
+        if (clips && el.scrollWidth > el.clientWidth + 1 && el.textContent?.trim()) {
+            warnings.push({ code: 'text-truncated', message: 'Text overflows its box and is clipped', target: describe(el) })
+        }
+    })
+
+    // The template's keep-with-next rules are requests, not guarantees; report where the layout still left a heading,
+    // a paragraph's first line or a table's first row alone at the foot of a page.
+    document.querySelectorAll<HTMLElement>('.pagedjs_page_content').forEach((content) => {
+        const followed = (el: Element) => {
+            for (let node: Element | null = el; node && node !== content; node = node.parentElement) {
+                if (node.nextElementSibling) return true
+            }
+            return false
+        }
+        content.querySelectorAll('h2, h3, h4').forEach((heading) => {
+            if (!followed(heading)) warnings.push({ code: 'stranded-content', message: 'Heading is the last thing on its page', target: describe(heading) })
+        })
+        content.querySelectorAll<HTMLElement>('p[data-split-to]').forEach((paragraph) => {
+            const lineHeight = parseFloat(getComputedStyle(paragraph).lineHeight) || 12
+            if (paragraph.getBoundingClientRect().height < lineHeight * 1.5) {
+                warnings.push({ code: 'stranded-content', message: 'Paragraph leaves a single line before the page break', target: describe(paragraph) })
+            }
+        })
+        content.querySelectorAll('table[data-split-to]').forEach((table) => {
+            if (table.querySelectorAll('tbody tr').length <= 1) {
+                warnings.push({ code: 'stranded-content', message: 'Table leaves a single row before the page break', target: describe(table) })
+            }
+        })
+    })
+
+    // Legal text must sit whole on the final page; it only breaks when it is longer than a page.
+    const pages = document.querySelectorAll('.pagedjs_page')
+    document.querySelectorAll('.legal').forEach((legal) => {
+        const page = legal.closest('.pagedjs_page')
+        if (legal.hasAttribute('data-split-to') || (page && page !== pages[pages.length - 1])) {
+            warnings.push({ code: 'legal-placement', message: 'Legal text is not entirely on the final page', target: describe(legal) })
+        }
+    })
+
+    const walker = document.createTreeWalker(document.body, NodeFilter.SHOW_TEXT)
+    for (let node = walker.nextNode(); node; node = walker.nextNode()) {
+        const text = node.textContent ?? ''
+        const match = text.match(UNSUPPORTED)
+        if (!match) continue
+        warnings.push({
+            code: 'unsupported-character',
+            message: `Unsupported character U+${match[0].charCodeAt(0).toString(16).toUpperCase().padStart(4, '0')} will render in a fallback font or as a missing-glyph box`,
+            target: text.trim().slice(0, 40),
+        })
+    }
+
+    return { estimatedPages: pages.length, warnings }
+}
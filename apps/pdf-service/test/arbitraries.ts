@@ -0,0 +1,97 @@
+import fc from 'fast-check'
+import type { DraftBundle, DraftSection, GrowthCategory, KPI } from 'report-template'
+
+type Values = {
+    text: (maxWords: number) => fc.Arbitrary<string>
+    number: (min: number, max: number) => fc.Arbitrary<number>
+    integer: (min: number, max: number) => fc.Arbitrary<number>
+}
+
+// Prose-like text: mostly ordinary words, with the occasional unbreakable run or markup-looking fragment.
+const word = fc.oneof(
+    { weight: 8, arbitrary: fc.constantFrom('patient', 'recall', 'hygiene', 'production', 'schedule', 'the', 'and', 'of', 'growth') },
+    { weight: 1, arbitrary: fc.string({ minLength: 20, maxLength: 60, unit: 'grapheme-ascii' }) },
+    { weight: 1, arbitrary: fc.constantFrom('<b>', '&amp;', '**bold**', '[^note]', '$1,200', '–', '🦷') }
+)
+
+export const prose = (maxWords: number) => fc.array(word, { maxLength: maxWords }).map((words) => words.join(' '))
+
+// Realistic ranges, for properties about layout.
+const realistic: Values = {
+    text: prose,
+    number: (min, max) => fc.double({ min, max, noNaN: true }),
+    integer: (min, max) => fc.integer({ min, max }),
+}
+
+// Well-typed but absurd: NaN, infinities, negative counts, huge and control-character strings. For fuzzing.
+const hostile: Values = {
+    text: (maxWords) =>
+        fc.oneof(
+            prose(maxWords),
+            fc.string({ unit: 'binary' }),
+            fc.string({ minLength: 1, maxLength: 4 }).chain((chunk) => fc.integer({ min: 1, max: 20_000 }).map((times) => chunk.repeat(times)))
+        ),
+    number: () => fc.oneof(fc.double(), fc.constantFrom(0, -0, -1, Number.MAX_VALUE, -Number.MAX_VALUE, Number.EPSILON)),
+    integer: () => fc.oneof(fc.integer(), fc.constantFrom(0, -1, Number.MAX_SAFE_INTEGER, Number.NaN)),
+}
+
+const id = fc.stringMatching(/^[a-z][a-z0-9-]{0,15}$/)
+
+const bundleOf = ({ text, number, integer }: Values): fc.Arbitrary<DraftBundle> => {
+    const kpi: fc.Arbitrary<KPI> = fc.record(
+        {
+            name: text(6),
+            value: number(-50, 10_000),
+            practice: number(0, 10_000),
+            previousValue: number(0, 10_000),
+            valueType: fc.constantFrom('percent' as const, 'currency' as const, 'count' as const, 'ratio' as const),
+        },
+        { requiredKeys: ['name', 'value'] }
+    )
+
+    const section: fc.Arbitrary<DraftSection> = fc.record(
+        {
+            id,
+            title: text(8),
+            options: fc.array(fc.record({ id, text: text(400) }), { minLength: 1, maxLength: 2 }),
+            group: fc.constantFrom('question' as const, 'summary' as const, 'general' as const),
+            align: fc.constantFrom('left' as const, 'justify' as const),
+            orientation: fc.constantFrom('portrait' as const, 'landscape' as const),
+        },
+        { requiredKeys: ['id', 'title', 'options'] }
+    )
+
+    const category: fc.Arbitrary<GrowthCategory> = fc.record(
+        {
+            id,
+            name: text(4),
+            score: integer(0, 100),
+            explanation: text(60),
+            confidence: integer(0, 5),
+            scored: integer(0, 20),
+            total: integer(0, 20),
+            previousScore: integer(0, 100),
+            group: fc.constantFrom('Clinical', 'Operations', 'Marketing'),
+        },
+        { requiredKeys: ['id', 'name', 'score', 'confidence', 'scored', 'total'] }
+    )
+
+    return fc.record(
+        {
+            clientName: text(5),
+            date: fc.constantFrom('2024-01-31', 'March 2025', ''),
+            kpis: fc.array(kpi, { maxLength: 12 }),
+            sections: fc.uniqueArray(section, { maxLength: 12, selector: (value) => value.id }),
+            growthCategories: fc.uniqueArray(category, { maxLength: 20, selector: (value) => value.id }),
+            executiveSummary: fc.boolean(),
+            numberHeadings: fc.boolean(),
+            tableOfContents: fc.boolean(),
+            runningHeaders: fc.boolean(),
+            kpiDisplay: fc.constantFrom('gradient' as const, 'bullet' as const),
+        },
+        { requiredKeys: ['clientName', 'date', 'kpis', 'sections'] }
+    )
+}
+
+export const bundle = bundleOf(realistic)
+export const hostileBundle = bundleOf(hostile)
@@ -0,0 +1,21 @@
+import { describe, expect, test } from 'bun:test'
+import { PDFDocument, rgb } from 'pdf-lib'
+import { removeBlankPages } from '../src/blankPages'
+
+const SIZE: [number, number] = [612, 792]
+
+describe('blank page trimming', () => {
+    test('keeps pages drawn only with filled shapes and drops white-background ones', async () => {
+        const source = await PDFDocument.create()
+        source.addPage(SIZE).drawRectangle({ x: 0, y: 0, width: SIZE[0], height: SIZE[1], color: rgb(1, 1, 1) })
+        const chart = source.addPage(SIZE)
+        chart.drawRectangle({ x: 0, y: 0, width: SIZE[0], height: SIZE[1], color: rgb(1, 1, 1) })
+        chart.drawRectangle({ x: 72, y: 72, width: 200, height: 120, color: rgb(0.2, 0.4, 0.8) })
+        source.addPage(SIZE)
+        // Trimming runs on Chrome's output, so the pages are read back from bytes like the service does.
+        const document = await PDFDocument.load(await source.save())
+
+        expect(removeBlankPages(document)).toBe(2)
+        expect(document.getPageCount()).toBe(1)
+    })
+})
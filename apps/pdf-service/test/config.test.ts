@@ -0,0 +1,31 @@
+import { describe, expect, test } from 'bun:test'
+import { join } from 'node:path'
+
+const CONFIG = join(import.meta.dir, '..', 'src', 'config.ts')
+
+// config.ts reads the environment once at import time, so each case loads it in a fresh process.
+const load = (env: Record<string, string>) => {
+    const result = Bun.spawnSync([process.execPath, '-e', `await import(${JSON.stringify(CONFIG)})`], {
+        env: { ...process.env, REPORTFLOW_CONFIG: join(import.meta.dir, 'fixtures', 'reportflow.test.toml'), ...env },
+    })
+    return { ok: result.exitCode === 0, stderr: result.stderr.toString() }
+}
+
+describe('config', () => {
+    test('loads with the defaults', () => {
+        expect(load({}).ok).toBe(true)
+    })
+
+    test('refuses ports that are not whole numbers in range', () => {
+        for (const value of ['abc', '0', '70000', '3001.5']) {
+            expect(load({ PORT: value }).stderr).toContain('server.port must be a whole number between 1 and 65535')
+        }
+        expect(load({ GRPC_PORT: '-1' }).stderr).toContain('grpc.port must be')
+    })
+
+    test('refuses render timeouts that would fire at once', () => {
+        for (const value of ['0', '-5', 'soon', '1e10']) {
+            expect(load({ RENDER_TIMEOUT_MS: value }).stderr).toContain('limits.render_timeout_ms must be a whole number of milliseconds')
+        }
+    })
+})
@@ -0,0 +1,22 @@
+import { afterAll, expect, test } from 'bun:test'
+import { type DraftBundle, renderReport, sourceAttachment } from 'report-template'
+import { readFile } from 'node:fs/promises'
+import { join } from 'node:path'
+import { closeBrowser } from '../src/browser'
+import { renderPdf } from '../src/render'
+import { loadResources } from '../src/resources'
+
+afterAll(closeBrowser)
+
+test(
+    'deterministic renders are byte-identical',
+    async () => {
+        const resources = await loadResources(join(import.meta.dir, 'fixtures', 'assets'))
+        const bundle = JSON.parse(await readFile(join(import.meta.dir, 'fixtures', 'bundles', 'paged.json'), 'utf8')) as DraftBundle
+        const options = { resources, attachments: [sourceAttachment({ ...bundle, attachSource: 'json' })!], deterministic: true }
+        const first = await renderPdf(renderReport(bundle), options)
+        const second = await renderPdf(renderReport(bundle), options)
+        expect(Buffer.from(second.pdf).equals(Buffer.from(first.pdf))).toBe(true)
+    },
+    { timeout: 60_000 }
+)
@@ -0,0 +1,40 @@
+import { afterAll, describe, expect, test } from 'bun:test'
+import { type DraftBundle, renderReport } from 'report-template'
+import { existsSync } from 'node:fs'
+import { readFile, readdir } from 'node:fs/promises'
+import { basename, join } from 'node:path'
+import { closeBrowser } from '../src/browser'
+import { renderPdf } from '../src/render'
+import { loadResources } from '../src/resources'
+import { extractLayout } from './pdfText'
+
+const FIXTURES = join(import.meta.dir, 'fixtures', 'bundles')
+const SNAPSHOTS = join(import.meta.dir, '__snapshots__', 'golden.test.ts.snap')
+// Set by `bun run test:bless`. Otherwise a missing golden fails instead of toMatchSnapshot writing what just rendered.
+const BLESSING = process.env.BLESS_GOLDENS === '1'
+// Until the first goldens are blessed and committed there is nothing to compare against, so the suite sits out.
+const ENABLED = BLESSING || existsSync(SNAPSHOTS)
+
+const resources = await loadResources(join(import.meta.dir, 'fixtures', 'assets'))
+const files = (await readdir(FIXTURES)).filter((file) => file.endsWith('.json')).sort()
+const goldens = ENABLED && !BLESSING ? await readFile(SNAPSHOTS, 'utf8') : ''
+
+afterAll(closeBrowser)
+
+// Goldens live in __snapshots__/golden.test.ts.snap, one per fixture bundle. A new fixture, or an intended layout
+// change, is blessed with `bun run test:bless`; the snapshot diff is then reviewed like any other code change.
+describe.skipIf(!ENABLED)('golden layouts', () => {
+    for (const file of files) {
+        const fixture = basename(file, '.json')
+        test(
+            fixture,
+            async () => {
+                if (!BLESSING) expect(goldens).toContain(`exports[\`golden layouts ${fixture} 1\`]`)
+                const bundle = JSON.parse(await readFile(join(FIXTURES, file), 'utf8')) as DraftBundle
+                const { pdf } = await renderPdf(renderReport(bundle), { resources, template: 'golden', deterministic: true })
+                expect(await extractLayout(pdf)).toMatchSnapshot()
+            },
+            { timeout: 60_000 }
+        )
+    }
+})
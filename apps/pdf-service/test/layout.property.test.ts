@@ -0,0 +1,53 @@
+import { afterAll, describe, expect, test } from 'bun:test'
+import fc from 'fast-check'
+import { renderReport } from 'report-template'
+import { join } from 'node:path'
+import { closeBrowser } from '../src/browser'
+import { withPage } from '../src/render'
+import { type Resources, loadResources } from '../src/resources'
+import { bundle } from './arbitraries'
+
+type PageProblem = { page: number; problem: string; target?: string }
+
+// Runs in the browser after paged.js has laid the document out.
+const pageProblems = (): PageProblem[] => {
+    const problems: PageProblem[] = []
+    document.querySelectorAll<HTMLElement>('.pagedjs_page').forEach((page, index) => {
+        const area = page.querySelector('.pagedjs_area')?.getBoundingClientRect()
+        const content = page.querySelector<HTMLElement>('.pagedjs_page_content')
+        if (!area || !content) return
+        if (!content.innerText.trim() && !content.querySelector('img, svg, input, [data-form-field]')) {
+            problems.push({ page: index + 1, problem: 'empty page' })
+        }
+        content.querySelectorAll<HTMLElement>('*').forEach((element) => {
+            const box = element.getBoundingClientRect()
+            if (!box.width || !box.height) return
+            if (box.top < area.top - 1 || box.bottom > area.bottom + 1 || box.left < area.left - 1 || box.right > area.right + 1) {
+                problems.push({ page: index + 1, problem: 'outside content area', target: element.className || element.tagName.toLowerCase() })
+            }
+        })
+    })
+    return problems
+}
+
+let resources: Resources
+
+afterAll(closeBrowser)
+
+// Each case boots a Chrome page and paginates, so the run count stays small; failures shrink to a minimal bundle.
+describe('layout invariants', () => {
+    test(
+        'every page has content and nothing is drawn outside the content area',
+        async () => {
+            resources ??= await loadResources(join(import.meta.dir, 'fixtures', 'assets'))
+            await fc.assert(
+                fc.asyncProperty(bundle, async (payload) => {
+                    const { result } = await withPage(renderReport(payload), { resources, forcePaged: true }, (page) => page.evaluate(pageProblems))
+                    expect(result).toEqual([])
+                }),
+                { numRuns: Number(process.env.LAYOUT_RUNS ?? 12) }
+            )
+        },
+        { timeout: 600_000 }
+    )
+})
@@ -0,0 +1,61 @@
+import { describe, expect, setDefaultTimeout, test } from 'bun:test'
+import fc from 'fast-check'
+import { renderReport } from 'report-template'
+import { PdfError } from '../src/errors'
+import { readRequest } from '../src/request'
+import { hostileBundle } from './arbitraries'
+
+// Not part of `bun test`: run `bun run fuzz` (FUZZ_RUNS sets the campaign length). A failure prints the shrunk
+// counterexample and a seed; replay it with FUZZ_SEED=<seed> FUZZ_PATH=<path>.
+const settings = {
+    numRuns: Number(process.env.FUZZ_RUNS ?? 10_000),
+    seed: process.env.FUZZ_SEED ? Number(process.env.FUZZ_SEED) : undefined,
+    path: process.env.FUZZ_PATH,
+}
+
+setDefaultTimeout(30 * 60_000)
+
+// Anything a client can send must come back as a 4xx-style PdfError, never a TypeError or RangeError.
+const rejectsCleanly = (run: () => unknown) => {
+    try {
+        run()
+    } catch (error) {
+        if (!(error instanceof PdfError)) throw error
+        expect(error.status).toBeLessThan(500)
+    }
+}
+
+const parse = (bytes: Uint8Array) => {
+    try {
+        return JSON.parse(new TextDecoder().decode(bytes)) as unknown
+    } catch {
+        return undefined
+    }
+}
+
+describe('payload fuzzing', () => {
+    test('arbitrary bytes never crash request parsing', () => {
+        fc.assert(
+            fc.property(fc.uint8Array({ maxLength: 4096 }), (bytes) => rejectsCleanly(() => readRequest(parse(bytes)))),
+            settings
+        )
+    })
+
+    test('arbitrary JSON never crashes request parsing', () => {
+        fc.assert(
+            fc.property(fc.oneof(fc.jsonValue(), fc.record({ html: fc.jsonValue(), attachments: fc.jsonValue(), template: fc.jsonValue() })), (body) =>
+                rejectsCleanly(() => readRequest(body))
+            ),
+            settings
+        )
+    })
+
+    test('hostile bundles still render to markup', () => {
+        fc.assert(
+            fc.property(hostileBundle, (payload) => {
+                expect(typeof renderReport(payload)).toBe('string')
+            }),
+            { ...settings, numRuns: Math.ceil(settings.numRuns / 10) }
+        )
+    })
+})
@@ -0,0 +1,30 @@
+import { getDocument } from 'pdfjs-dist/legacy/build/pdf.mjs'
+
+export type PageLayout = {
+    width: number
+    height: number
+    // One entry per text run: "x,y text", in whole PDF points from the bottom-left corner.
+    text: string[]
+}
+
+// Whole points absorb sub-pixel differences between machines while still catching anything a reader would notice.
+const point = (value: number) => Math.round(value)
+
+export async function extractLayout(pdf: Uint8Array): Promise<PageLayout[]> {
+    const document = await getDocument({ data: new Uint8Array(pdf), useSystemFonts: false, verbosity: 0 }).promise
+    try {
+        const pages: PageLayout[] = []
+        for (let number = 1; number <= document.numPages; number++) {
+            const page = await document.getPage(number)
+            const [, , width = 0, height = 0] = page.view
+            const content = await page.getTextContent()
+            const text = content.items.flatMap((item) =>
+                'str' in item && item.str.trim() ? [`${point(item.transform[4])},${point(item.transform[5])} ${item.str}`] : []
+            )
+            pages.push({ width: point(width), height: point(height), text })
+        }
+        return pages
+    } finally {
+        await document.destroy()
+    }
+}
@@ -0,0 +1,406 @@
+import { afterAll, beforeAll, describe, expect, test } from 'bun:test'
+import { encode } from '@msgpack/msgpack'
+import type { Server } from 'node:http'
+import type { AddressInfo } from 'node:net'
+import { mkdtempSync } from 'node:fs'
+import { tmpdir } from 'node:os'
+import { join } from 'node:path'
+import { extractLayout } from './pdfText'
+
+// config.ts reads the environment once at import time, so it is set up before the app module loads.
+process.env.ASSETS_DIR = join(import.meta.dir, 'fixtures', 'assets')
+process.env.ADMIN_TOKEN = 'test-token'
+process.env.BODY_LIMIT = '256kb'
+process.env.WATCH_ASSETS = 'false'
+process.env.REPORTS_DIR = mkdtempSync(join(tmpdir(), 'reportflow-reports-'))
+process.env.REPORTS_DATABASE = join(process.env.REPORTS_DIR, 'reports.sqlite')
+process.env.REPORT_SIGNING_SECRET = 'test-secret'
+process.env.REPORTFLOW_CONFIG = join(import.meta.dir, 'fixtures', 'reportflow.test.toml')
+
+const { app } = await import('../src/app')
+const { closeBrowser } = await import('../src/browser')
+
+const HTML = '<!doctype html><html><body><h1>Integration</h1><p>Hello from the test harness.</p></body></html>'
+
+let server: Server
+let base: string
+
+beforeAll(async () => {
+    server = await new Promise<Server>((resolve) => {
+        const listening = app.listen(0, '127.0.0.1', () => resolve(listening))
+    })
+    base = `http://127.0.0.1:${(server.address() as AddressInfo).port}`
+})
+
+afterAll(async () => {
+    server.close()
+    await closeBrowser()
+})
+
+const post = (path: string, body: unknown, headers: Record<string, string> = { 'Content-Type': 'application/json' }) =>
+    fetch(`${base}${path}`, { method: 'POST', headers, body: typeof body === 'string' || body instanceof Uint8Array ? body : JSON.stringify(body) })
+
+const errorCode = async (response: Response) => ((await response.json()) as { error: { code: string } }).error.code
+
+const expectValidPdf = async (pdf: Uint8Array, pages: number) => {
+    expect(Buffer.from(pdf.subarray(0, 5)).toString('latin1')).toBe('%PDF-')
+    expect(Buffer.from(pdf.subarray(-8)).toString('latin1')).toContain('%%EOF')
+    const layout = await extractLayout(pdf)
+    expect(layout.length).toBe(pages)
+    expect(layout.flatMap((page) => page.text).join(' ')).toContain('Integration')
+}
+
+describe('POST /pdf', () => {
+    test(
+        'returns a PDF with report metadata headers',
+        async () => {
+            const response = await post('/pdf', { html: HTML })
+            expect(response.status).toBe(200)
+            expect(response.headers.get('content-type')).toBe('application/pdf')
+            expect(response.headers.get('x-report-id')).toMatch(/^[0-9a-f-]{36}$/)
+            expect(response.headers.get('x-report-sha256')).toMatch(/^[0-9a-f]{64}$/)
+            expect(Number(response.headers.get('x-render-ms'))).toBeGreaterThanOrEqual(0)
+            const pdf = new Uint8Array(await response.arrayBuffer())
+            expect(Number(response.headers.get('x-report-bytes'))).toBe(pdf.byteLength)
+            await expectValidPdf(pdf, Number(response.headers.get('x-report-pages')))
+        },
+        { timeout: 60_000 }
+    )
+
+    test(
+        'wraps the PDF in JSON with ?response=json',
+        async () => {
+            const response = await post('/pdf?response=json', { html: HTML })
+            expect(response.status).toBe(200)
+            const body = (await response.json()) as { pdfBase64: string; pages: number; reportId: string; warnings: unknown[] }
+            expect(body.warnings).toEqual([])
+            await expectValidPdf(Buffer.from(body.pdfBase64, 'base64'), body.pages)
+        },
+        { timeout: 60_000 }
+    )
+
+    test(
+        'accepts MessagePack bodies',
+        async () => {
+            const response = await post('/pdf', encode({ html: HTML }), { 'Content-Type': 'application/msgpack' })
+            expect(response.status).toBe(200)
+            expect(response.headers.get('content-type')).toBe('application/pdf')
+        },
+        { timeout: 60_000 }
+    )
+
+    test('rejects malformed JSON', async () => {
+        const response = await post('/pdf', '{"html": ', { 'Content-Type': 'application/json' })
+        expect(response.status).toBe(400)
+        expect(await errorCode(response)).toBe('invalid_payload')
+    })
+
+    test.each([
+        ['missing html', {}],
+        ['empty html', { html: '   ' }],
+        ['non-string template', { html: HTML, template: 42 }],
+        ['too many attachments', { html: HTML, attachments: Array.from({ length: 9 }, (_, idx) => ({ name: `${idx}.txt`, content: '' })) }],
+        ['non-boolean deterministic', { html: HTML, deterministic: 'yes' }],
+    ])('rejects %s', async (_name, body) => {
+        const response = await post('/pdf', body)
+        expect(response.status).toBe(400)
+        expect(await errorCode(response)).toBe('invalid_payload')
+    })
+
+    test('rejects an unknown response mode', async () => {
+        const response = await post('/pdf?response=xml', { html: HTML })
+        expect(response.status).toBe(400)
+    })
+
+    test('rejects an unknown template', async () => {
+        const response = await post('/pdf', { html: HTML, template: 'does-not-exist' })
+        expect(response.status).toBe(400)
+        expect(await errorCode(response)).toBe('invalid_payload')
+    })
+
+    test('rejects bodies over the size limit', async () => {
+        const response = await post('/pdf', { html: `<p>${'x'.repeat(300 * 1024)}</p>` })
+        expect(response.status).toBe(413)
+        expect(await errorCode(response)).toBe('payload_too_large')
+    })
+})
+
+describe('POST /pdf/validate', () => {
+    test(
+        'reports layout without printing',
+        async () => {
+            const response = await post('/pdf/validate', { html: HTML })
+            expect(response.status).toBe(200)
+            const body = (await response.json()) as { estimatedPages: number; warnings: unknown[] }
+            expect(body.estimatedPages).toBe(1)
+            expect(body.warnings).toEqual([])
+        },
+        { timeout: 60_000 }
+    )
+
+    test(
+        'flags a heading left at the foot of a page',
+        async () => {
+            const html =
+                '<!doctype html><html><body><p>Lead-in.</p><h2 id="stranded">Stranded</h2>' +
+                '<div style="break-before:page"><p>Continues on the next page.</p></div></body></html>'
+            const response = await post('/pdf/validate', { html })
+            expect(response.status).toBe(200)
+            const body = (await response.json()) as { estimatedPages: number; warnings: { code: string; message: string }[] }
+            expect(body.estimatedPages).toBe(2)
+            expect(body.warnings).toContainEqual(
+                expect.objectContaining({ code: 'stranded-content', message: 'Heading is the last thing on its page' })
+            )
+        },
+        { timeout: 60_000 }
+    )
+})
+
+describe('POST /pdf/compare', () => {
+    const current = {
+        clientName: 'Smile Co',
+        date: 'Q3 2026',
+        kpis: [
+            { name: 'Overall Practice Score', value: 74 },
+            { name: 'New Patients', value: 42, valueType: 'count' },
+        ],
+        growthCategories: [{ id: 'get-new', name: 'Get New Patients', score: 50, confidence: 60, scored: 40, total: 61 }],
+    }
+    const previous = {
+        ...current,
+        date: 'Q2 2026',
+        kpis: [
+            { name: 'Overall Practice Score', value: 70 },
+            { name: 'Recall Rate', value: 55 },
+        ],
+        growthCategories: [{ ...current.growthCategories[0], score: 45 }],
+    }
+
+    test(
+        'renders both periods side by side with deltas',
+        async () => {
+            const response = await post('/pdf/compare', { current, previous })
+            expect(response.status).toBe(200)
+            const layout = await extractLayout(new Uint8Array(await response.arrayBuffer()))
+            const text = layout.flatMap((page) => page.text).join(' ')
+            for (const expected of ['Period Comparison', 'Q2 2026', 'Q3 2026', '+4', 'Recall Rate', 'Get New Patients', '+5', 'Trend commentary']) {
+                expect(text).toContain(expected)
+            }
+        },
+        { timeout: 60_000 }
+    )
+
+    test('rejects a missing period', async () => {
+        const response = await post('/pdf/compare', { current })
+        expect(response.status).toBe(400)
+        expect(await errorCode(response)).toBe('invalid_payload')
+    })
+
+    test('rejects currency KPIs whose unit is not a currency code', async () => {
+        const kpis = [{ name: 'Production', value: 1250, valueType: 'currency', unit: 'dollars' }]
+        const response = await post('/pdf/compare', { current: { ...current, kpis }, previous })
+        expect(response.status).toBe(400)
+        expect(await errorCode(response)).toBe('invalid_payload')
+    })
+
+    test('rejects redaction patterns that do not compile or could backtrack without bound', async () => {
+        for (const pattern of ['(unclosed', '(a+)+$']) {
+            const response = await post('/pdf/compare', { current: { ...current, redact: true, redaction: { patterns: [pattern] } }, previous })
+            expect(response.status).toBe(400)
+            expect(await errorCode(response)).toBe('invalid_payload')
+        }
+    })
+
+    test('rejects form fields that share an id', async () => {
+        const formFields = [
+            { id: 'notes', label: 'Notes' },
+            { id: 'notes', label: 'More notes' },
+        ]
+        const response = await post('/pdf/compare', { current: { ...current, formFields }, previous })
+        expect(response.status).toBe(400)
+        expect(await errorCode(response)).toBe('invalid_payload')
+    })
+})
+
+describe('async jobs', () => {
+    test(
+        'streams progress and serves the finished PDF',
+        async () => {
+            const created = await post('/jobs', { html: HTML })
+            expect(created.status).toBe(202)
+            const job = (await created.json()) as { id: string; events: string; pdf: string }
+            expect(created.headers.get('location')).toBe(`/jobs/${job.id}`)
+            const events = await fetch(`${base}${job.events}`)
+            expect(events.headers.get('content-type')).toStartWith('text/event-stream')
+            const stream = await events.text()
+            expect(stream).toContain('event: done')
+            const pdf = await fetch(`${base}${job.pdf}`)
+            expect(pdf.status).toBe(200)
+            await expectValidPdf(new Uint8Array(await pdf.arrayBuffer()), 1)
+        },
+        { timeout: 60_000 }
+    )
+
+    test('404s for unknown jobs', async () => {
+        const response = await fetch(`${base}/jobs/nope`)
+        expect(response.status).toBe(404)
+        expect(await errorCode(response)).toBe('not_found')
+    })
+})
+
+describe('Idempotency-Key', () => {
+    test(
+        'replays the original report for a retried request',
+        async () => {
+            const headers = { 'Content-Type': 'application/json', 'Idempotency-Key': 'retry-1' }
+            const first = await post('/pdf', { html: HTML }, headers)
+            const retry = await post('/pdf', { html: HTML }, headers)
+            expect(retry.status).toBe(200)
+            expect(retry.headers.get('idempotent-replayed')).toBe('true')
+            expect(retry.headers.get('x-report-id')).toBe(first.headers.get('x-report-id'))
+            expect(Buffer.from(await retry.arrayBuffer()).equals(Buffer.from(await first.arrayBuffer()))).toBe(true)
+        },
+        { timeout: 60_000 }
+    )
+
+    test(
+        'rejects a key reused for a different payload',
+        async () => {
+            const headers = { 'Content-Type': 'application/json', 'Idempotency-Key': 'retry-2' }
+            expect((await post('/pdf', { html: HTML }, headers)).status).toBe(200)
+            const conflict = await post('/pdf', { html: `${HTML}<p>changed</p>` }, headers)
+            expect(conflict.status).toBe(409)
+            expect(await errorCode(conflict)).toBe('idempotency_conflict')
+        },
+        { timeout: 60_000 }
+    )
+
+    test(
+        'keeps keys apart for different callers',
+        async () => {
+            const headers = (token: string) => ({ 'Content-Type': 'application/json', 'Idempotency-Key': 'retry-3', Authorization: `Bearer ${token}` })
+            expect((await post('/pdf', { html: HTML }, headers('client-a'))).status).toBe(200)
+            const other = await post('/pdf', { html: `${HTML}<p>other client</p>` }, headers('client-b'))
+            expect(other.status).toBe(200)
+            expect(other.headers.get('idempotent-replayed')).toBeNull()
+        },
+        { timeout: 60_000 }
+    )
+})
+
+describe('signed report links', () => {
+    test(
+        'serve a stored report only with a valid signature',
+        async () => {
+            const response = await post('/pdf', { html: HTML })
+            const url = new URL(response.headers.get('x-report-url') ?? '', base)
+            expect(url.pathname).toBe(`/reports/${response.headers.get('x-report-id')}`)
+            const download = await fetch(url)
+            expect(download.status).toBe(200)
+            expect(Buffer.from(await download.arrayBuffer()).equals(Buffer.from(await response.arrayBuffer()))).toBe(true)
+
+            url.searchParams.set('sig', 'forged')
+            expect((await fetch(url)).status).toBe(401)
+            url.searchParams.delete('sig')
+            expect((await fetch(url)).status).toBe(401)
+        },
+        { timeout: 60_000 }
+    )
+
+    test(
+        'reject expired links',
+        async () => {
+            const id = (await post('/pdf', { html: HTML })).headers.get('x-report-id')
+            const minted = await post(`/reports/${id}/links`, { ttlSeconds: 1 }, { 'Content-Type': 'application/json', Authorization: 'Bearer test-token' })
+            expect(minted.status).toBe(201)
+            const { url } = (await minted.json()) as { url: string }
+            await Bun.sleep(2_100)
+            const expired = await fetch(new URL(url, base))
+            expect(expired.status).toBe(410)
+            expect(await errorCode(expired)).toBe('link_expired')
+        },
+        { timeout: 60_000 }
+    )
+
+    test(
+        'refuse link lifetimes that are fractional, unsafe or longer than 30 days',
+        async () => {
+            const id = (await post('/pdf', { html: HTML })).headers.get('x-report-id')
+            for (const ttlSeconds of [1.5, 1e300, 31 * 24 * 60 * 60]) {
+                const response = await post(`/reports/${id}/links`, { ttlSeconds }, { 'Content-Type': 'application/json', Authorization: 'Bearer test-token' })
+                expect(response.status).toBe(400)
+                expect(await errorCode(response)).toBe('invalid_payload')
+            }
+        },
+        { timeout: 60_000 }
+    )
+})
+
+describe('report history', () => {
+    const adminHeaders = { Authorization: 'Bearer test-token' }
+    const clientReport = (clientName: string) =>
+        HTML.replace('<html>', `<html data-client-name="${clientName}" data-report-date="2026-Q3">`)
+
+    test(
+        'lists prior reports for a client, newest first',
+        async () => {
+            const first = await post('/pdf', { html: clientReport('Smile Co') })
+            await post('/pdf', { html: clientReport('Other Dental') })
+            const second = await post('/pdf', { html: clientReport('Smile Co') })
+
+            const response = await fetch(`${base}/reports?clientName=${encodeURIComponent('Smile Co')}`, { headers: adminHeaders })
+            expect(response.status).toBe(200)
+            const { reports } = (await response.json()) as { reports: { reportId: string; clientName: string; reportDate: string; url: string }[] }
+            expect(reports.map((report) => report.reportId)).toEqual([second.headers.get('x-report-id'), first.headers.get('x-report-id')])
+            expect(reports[0]).toMatchObject({ clientName: 'Smile Co', reportDate: '2026-Q3', pages: 1 })
+
+            const download = await fetch(new URL(reports[1]!.url, base))
+            expect(Buffer.from(await download.arrayBuffer()).equals(Buffer.from(await first.arrayBuffer()))).toBe(true)
+        },
+        { timeout: 60_000 }
+    )
+
+    test('requires the bearer token', async () => {
+        expect((await fetch(`${base}/reports`)).status).toBe(401)
+        expect((await fetch(`${base}/reports?limit=0`, { headers: adminHeaders })).status).toBe(400)
+    })
+})
+
+describe('admin routes', () => {
+    test('require the bearer token', async () => {
+        expect((await fetch(`${base}/profiles`)).status).toBe(401)
+        expect((await fetch(`${base}/profiles`, { headers: { Authorization: 'Bearer wrong' } })).status).toBe(401)
+        const response = await fetch(`${base}/profiles`, { headers: { Authorization: 'Bearer test-token' } })
+        expect(response.status).toBe(200)
+        expect(await response.json()).toEqual({ profiles: [] })
+    })
+
+    test('reject ids that could escape the assets directory', async () => {
+        const response = await fetch(`${base}/profiles/..%2Fsecrets`, { headers: { Authorization: 'Bearer test-token' } })
+        expect(response.status).toBe(400)
+    })
+
+    test('reject a profile that would not load without keeping it on disk', async () => {
+        const headers = { Authorization: 'Bearer test-token', 'Content-Type': 'application/json' }
+        const response = await fetch(`${base}/profiles/broken`, {
+            method: 'PUT',
+            headers,
+            body: JSON.stringify({ name: 'Broken', logo: 'https://example.com/logo.gif' }),
+        })
+        expect(response.status).toBe(400)
+        expect(await errorCode(response)).toBe('invalid_payload')
+        const listed = await fetch(`${base}/profiles`, { headers })
+        expect(await listed.json()).toEqual({ profiles: [] })
+    })
+
+    test('reject logos that are neither inline nor stored for the same profile', async () => {
+        const headers = { Authorization: 'Bearer test-token', 'Content-Type': 'application/json' }
+        for (const logo of ['other.png', '../fonts/Inter.ttf', 'borrowed.png']) {
+            const response = await fetch(`${base}/profiles/borrowed`, { method: 'PUT', headers, body: JSON.stringify({ name: 'Borrowed', logo }) })
+            expect(response.status).toBe(400)
+            expect(await errorCode(response)).toBe('invalid_payload')
+        }
+        const listed = await fetch(`${base}/profiles`, { headers })
+        expect(await listed.json()).toEqual({ profiles: [] })
+    })
+})
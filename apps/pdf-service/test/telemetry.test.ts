@@ -0,0 +1,45 @@
+import { afterAll, describe, expect, test } from 'bun:test'
+import { node, tracing } from '@opentelemetry/sdk-node'
+import express from 'express'
+import type { Server } from 'node:http'
+import type { AddressInfo } from 'node:net'
+import { traceRequests, tracer } from '../src/telemetry'
+
+// Stands in for the SDK: the W3C propagator and async context manager that register() installs are what NodeSDK uses.
+const exporter = new tracing.InMemorySpanExporter()
+new node.NodeTracerProvider({ spanProcessors: [new tracing.SimpleSpanProcessor(exporter)] }).register()
+
+const app = express()
+app.use(traceRequests)
+app.get('/items/:id', async (_req, res) => {
+    await tracer.startActiveSpan('render.output', async (span) => span.end())
+    res.send('ok')
+})
+
+const server = await new Promise<Server>((resolve) => {
+    const listening = app.listen(0, '127.0.0.1', () => resolve(listening))
+})
+afterAll(() => void server.close())
+
+const TRACE_ID = '4bf92f3577b34da6a3ce929d0e0e4736'
+const CALLER_SPAN_ID = '00f067aa0ba902b7'
+
+describe('request tracing', () => {
+    test("joins the caller's trace and parents the render spans", async () => {
+        const response = await fetch(`http://127.0.0.1:${(server.address() as AddressInfo).port}/items/42`, {
+            headers: { traceparent: `00-${TRACE_ID}-${CALLER_SPAN_ID}-01` },
+        })
+        expect(await response.text()).toBe('ok')
+        // The server span ends on 'close', which can land just after the response body.
+        await Bun.sleep(10)
+
+        const spans = exporter.getFinishedSpans()
+        const request = spans.find((span) => span.name === 'GET /items/:id')!
+        const render = spans.find((span) => span.name === 'render.output')!
+        expect(request.spanContext().traceId).toBe(TRACE_ID)
+        expect(request.parentSpanContext?.spanId).toBe(CALLER_SPAN_ID)
+        expect(request.attributes['http.response.status_code']).toBe(200)
+        expect(render.spanContext().traceId).toBe(TRACE_ID)
+        expect(render.parentSpanContext?.spanId).toBe(request.spanContext().spanId)
+    })
+})
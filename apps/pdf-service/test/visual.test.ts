@@ -0,0 +1,102 @@
+import { afterAll, describe, expect, test } from 'bun:test'
+import pixelmatch from 'pixelmatch'
+import { PNG } from 'pngjs'
+import { type DraftBundle, renderReport } from 'report-template'
+import { execFile } from 'node:child_process'
+import { existsSync } from 'node:fs'
+import { mkdir, mkdtemp, readFile, readdir, rm, writeFile } from 'node:fs/promises'
+import { tmpdir } from 'node:os'
+import { basename, join } from 'node:path'
+import { promisify } from 'node:util'
+import { closeBrowser } from '../src/browser'
+import { renderPdf } from '../src/render'
+import { loadResources } from '../src/resources'
+
+// Opt-in: needs Ghostscript on PATH and is slow, so it only runs with VISUAL_REGRESSION=1. See README.md for baselines.
+const ENABLED = process.env.VISUAL_REGRESSION === '1'
+// `bun run test:visual:bless` rewrites the references instead of comparing against them.
+const UPDATE = process.env.UPDATE_VISUAL === '1'
+
+const FIXTURES = join(import.meta.dir, 'fixtures', 'bundles')
+const REFERENCES = join(import.meta.dir, 'fixtures', 'visual')
+const DIFFS = join(import.meta.dir, 'output')
+const DPI = 72
+// pixelmatch's per-pixel YIQ threshold ignores antialiasing noise; the page fails when more than 0.1% of it changed.
+const PIXEL_THRESHOLD = 0.1
+const MAX_CHANGED_RATIO = 0.001
+
+const run = promisify(execFile)
+
+const rasterize = async (pdf: Uint8Array) => {
+    const dir = await mkdtemp(join(tmpdir(), 'reportflow-visual-'))
+    try {
+        await writeFile(join(dir, 'report.pdf'), pdf)
+        await run('gs', [
+            '-q',
+            '-dNOPAUSE',
+            '-dBATCH',
+            '-dSAFER',
+            '-sDEVICE=png16m',
+            `-r${DPI}`,
+            '-dTextAlphaBits=4',
+            '-dGraphicsAlphaBits=4',
+            `-sOutputFile=${join(dir, 'page-%03d.png')}`,
+            join(dir, 'report.pdf'),
+        ])
+        const pages = (await readdir(dir)).filter((file) => file.endsWith('.png')).sort()
+        return Promise.all(pages.map((file) => readFile(join(dir, file))))
+    } finally {
+        await rm(dir, { recursive: true, force: true })
+    }
+}
+
+const changedRatio = async (name: string, actual: Buffer, reference: Buffer) => {
+    const a = PNG.sync.read(actual)
+    const b = PNG.sync.read(reference)
+    if (a.width !== b.width || a.height !== b.height) return 1
+    const diff = new PNG({ width: a.width, height: a.height })
+    const changed = pixelmatch(a.data, b.data, diff.data, a.width, a.height, { threshold: PIXEL_THRESHOLD })
+    if (changed) {
+        await mkdir(DIFFS, { recursive: true })
+        await writeFile(join(DIFFS, `${name}.diff.png`), PNG.sync.write(diff))
+        await writeFile(join(DIFFS, `${name}.actual.png`), actual)
+    }
+    return changed / (a.width * a.height)
+}
+
+const resources = await loadResources(join(import.meta.dir, 'fixtures', 'assets'))
+const files = (await readdir(FIXTURES)).filter((file) => file.endsWith('.json')).sort()
+
+afterAll(closeBrowser)
+
+describe.skipIf(!ENABLED)('visual regression', () => {
+    for (const file of files) {
+        const fixture = basename(file, '.json')
+        test(
+            fixture,
+            async () => {
+                const bundle = JSON.parse(await readFile(join(FIXTURES, file), 'utf8')) as DraftBundle
+                const { pdf } = await renderPdf(renderReport(bundle), { resources, template: 'golden' })
+                const pages = await rasterize(pdf)
+                if (UPDATE) {
+                    await mkdir(REFERENCES, { recursive: true })
+                    for (const old of (await readdir(REFERENCES)).filter((name) => name.startsWith(`${fixture}-`))) {
+                        await rm(join(REFERENCES, old))
+                    }
+                    await Promise.all(pages.map((png, idx) => writeFile(join(REFERENCES, `${fixture}-${idx + 1}.png`), png)))
+                    return
+                }
+                const references = (await readdir(REFERENCES).catch(() => [])).filter((name) => name.startsWith(`${fixture}-`))
+                if (!references.length) throw new Error(`No baselines for "${fixture}" in ${REFERENCES}; run \`bun run test:visual:bless\``)
+                expect(pages.length).toBe(references.length)
+                for (const [idx, png] of pages.entries()) {
+                    const name = `${fixture}-${idx + 1}`
+                    const reference = join(REFERENCES, `${name}.png`)
+                    expect(existsSync(reference)).toBe(true)
+                    expect(await changedRatio(name, png, await readFile(reference))).toBeLessThanOrEqual(MAX_CHANGED_RATIO)
+                }
+            },
+            { timeout: 120_000 }
+        )
+    }
+})
@@ -4,10 +4,55 @@ import {
     type DrilldownTable,
     type DraftBundle,
     type SectionSelection,
+    type SourceAttachment,
     renderDrilldownReport,
     renderReport,
+    sourceAttachment,
 } from 'report-template'
 
+type RenderProgress = {
+    stage: 'loading' | 'layout' | 'output'
+    pages?: number
+    sections?: { completed: number; total: number }
+}
+
+type LayoutReport = {
+    estimatedPages: number
+    warnings: { code: string; message: string; target?: string }[]
+}
+
+const PDF_SERVICE = 'http://localhost:3001'
+
+const progressLabel = (progress: RenderProgress) => {
+    if (progress.stage === 'loading') return 'Loading report…'
+    if (progress.stage === 'output') return 'Writing PDF…'
+    const sections = progress.sections ? ` — ${progress.sections.completed}/${progress.sections.total} sections` : ''
+    return `Laying out pages${progress.pages ? ` (${progress.pages} so far)` : ''}${sections}`
+}
+
+// Renders through the async job API so long reports can show live progress instead of a frozen button.
+const renderJob = async (body: unknown, onProgress: (progress: RenderProgress) => void) => {
+    const job = await axios.post<{ id: string; events: string; pdf: string }>(`${PDF_SERVICE}/jobs`, body)
+    await new Promise<void>((resolve, reject) => {
+        const events = new EventSource(`${PDF_SERVICE}${job.data.events}`)
+        events.addEventListener('progress', (event) => onProgress(JSON.parse((event as MessageEvent).data).progress))
+        events.addEventListener('done', () => {
+            events.close()
+            resolve()
+        })
+        events.addEventListener('failed', (event) => {
+            events.close()
+            reject(new Error(JSON.parse((event as MessageEvent).data).error.message))
+        })
+        events.onerror = () => {
+            events.close()
+            reject(new Error('Lost connection to the PDF service'))
+        }
+    })
+    const resp = await axios.get(`${PDF_SERVICE}${job.data.pdf}`, { responseType: 'blob' })
+    return resp.data as Blob
+}
+
 const REPORT_PLACEHOLDER = '<p>Upload a bundle JSON to begin.</p>'
 const DRILLDOWN_PLACEHOLDER = '<p>Upload a Drilldown CSV (see apps/reviewer/drilldown.csv for a sample) to preview.</p>'
 
@@ -114,6 +159,8 @@ export function App() {
     const [pdfUrl, setPdfUrl] = useState<string>('')
     const [drilldownTable, setDrilldownTable] = useState<DrilldownTable | null>(null)
     const [drilldownPdfUrl, setDrilldownPdfUrl] = useState<string>('')
+    const [layoutReport, setLayoutReport] = useState<LayoutReport | null>(null)
+    const [progress, setProgress] = useState<RenderProgress | null>(null)
 
     const onBundleUpload = async (file: File) => {
         try {
@@ -124,6 +171,7 @@ export function App() {
             }
             setBundle(parsed)
             setChosen({})
+            setLayoutReport(null)
         } catch (error) {
             console.error(error)
             alert('Unable to read bundle JSON. Ensure it matches the DraftBundle schema.')
@@ -151,10 +199,17 @@ export function App() {
         return renderDrilldownReport(drilldownTable)
     }, [drilldownTable])
 
-    const createPdf = async (html: string, filename: string, setUrl: (url: string) => void) => {
+    const createPdf = async (
+        html: string,
+        filename: string,
+        setUrl: (url: string) => void,
+        attachment?: SourceAttachment
+    ) => {
         try {
-            const resp = await axios.post('http://localhost:3001/pdf', { html }, { responseType: 'blob' })
-            const blob = new Blob([resp.data], { type: 'application/pdf' })
+            const attachments = attachment ? [attachment] : undefined
+            setProgress({ stage: 'loading' })
+            const pdf = await renderJob({ html, attachments }, setProgress)
+            const blob = new Blob([pdf], { type: 'application/pdf' })
             const url = URL.createObjectURL(blob)
             setUrl(url)
             const a = document.createElement('a')
@@ -164,13 +219,26 @@ export function App() {
         } catch (err) {
             console.error(err)
             alert('Unable to generate the PDF. Ensure the PDF service is running on :3001.')
+        } finally {
+            setProgress(null)
+        }
+    }
+
+    const checkLayout = async () => {
+        if (!bundle) return
+        try {
+            const resp = await axios.post<LayoutReport>(`${PDF_SERVICE}/pdf/validate`, { html: renderReport(bundle, chosen) })
+            setLayoutReport(resp.data)
+        } catch (err) {
+            console.error(err)
+            alert('Unable to check the layout. Ensure the PDF service is running on :3001.')
         }
     }
 
     const finalizeReport = () => {
         if (!bundle) return
         const html = renderReport(bundle, chosen)
-        void createPdf(html, 'report.pdf', setPdfUrl)
+        void createPdf(html, 'report.pdf', setPdfUrl, sourceAttachment(bundle))
     }
 
     const finalizeDrilldown = () => {
@@ -238,9 +306,39 @@ export function App() {
                             </div>
                         ))}
 
-                        <button onClick={finalizeReport} style={{ marginTop: 16 }}>
+                        <button onClick={checkLayout} style={{ marginTop: 16, marginRight: 8 }}>
+                            Check Layout
+                        </button>
+                        <button onClick={finalizeReport} disabled={progress != null} style={{ marginTop: 16 }}>
                             Finalize Summary → PDF
                         </button>
+                        {progress && (
+                            <div style={{ marginTop: 12, fontSize: 13 }}>
+                                <progress
+                                    max={progress.sections?.total || 1}
+                                    value={progress.stage === 'output' ? progress.sections?.total || 1 : (progress.sections?.completed ?? 0)}
+                                    style={{ width: '100%' }}
+                                />
+                                <p>{progressLabel(progress)}</p>
+                            </div>
+                        )}
+                        {layoutReport && (
+                            <div style={{ marginTop: 12, fontSize: 13 }}>
+                                <p>Estimated pages: {layoutReport.estimatedPages}</p>
+                                {layoutReport.warnings.length === 0 ? (
+                                    <p style={{ color: '#047857' }}>No layout warnings.</p>
+                                ) : (
+                                    <ul style={{ color: '#b45309' }}>
+                                        {layoutReport.warnings.map((warning, idx) => (
+                                            <li key={idx}>
+                                                {warning.message}
+                                                {warning.target && <em> — {warning.target}</em>}
+                                            </li>
+                                        ))}
+                                    </ul>
+                                )}
+                            </div>
+                        )}
                     </section>
                 )}
 
@@ -0,0 +1,40 @@
+import { renderReport, renderReportParallel } from '../src/render'
+import type { DraftBundle } from '../src/types'
+
+// Compares the inline and worker-pool renders of a chart-heavy report: `bun run bench [charts] [points]`.
+const [charts = 24, points = 5000] = process.argv.slice(2).map(Number)
+const RUNS = 5
+
+const bundle: DraftBundle = {
+    clientName: 'Smile Dental',
+    date: 'Q3 2026',
+    kpis: [],
+    sections: Array.from({ length: charts }, (_, idx) => ({
+        id: `chart-${idx}`,
+        title: `Chart ${idx + 1}`,
+        options: [{ id: 'a', text: 'Weekly production.' }],
+        chart: {
+            type: 'line' as const,
+            dataLabels: true,
+            series: [0, 1, 2].map((series) => ({
+                name: `Series ${series + 1}`,
+                points: Array.from({ length: points }, (_, x) => ({ x: `D${x}`, y: Math.sin((x + series * 40) / 30) * 100 + x / 10 })),
+            })),
+        },
+    })),
+}
+
+const median = async (render: () => unknown) => {
+    const times: number[] = []
+    for (let run = 0; run < RUNS; run++) {
+        const start = performance.now()
+        await render()
+        times.push(performance.now() - start)
+    }
+    return times.sort((a, b) => a - b)[Math.floor(RUNS / 2)]!
+}
+
+if ((await renderReportParallel(bundle)) !== renderReport(bundle)) throw new Error('Parallel output differs from renderReport')
+const inline = await median(() => renderReport(bundle))
+const parallel = await median(() => renderReportParallel(bundle))
+console.log(`${charts} charts x ${points} points: inline ${inline.toFixed(0)} ms, parallel ${parallel.toFixed(0)} ms (${(inline / parallel).toFixed(2)}x)`)
@@ -0,0 +1,173 @@
+import React from 'react'
+import { LEGAL_STYLES } from './components/Legal'
+import { ReportImage } from './components/ReportImage'
+import { formatKpiChange, formatKpiValue } from './kpi'
+import { REPORT_MARGIN_MM, pageRules } from './page'
+import { paginationRules } from './pagination'
+import { baselineRules, shapingRules, themeVariables } from './theme'
+import type { DraftBundle, GrowthCategory, KPI } from './types'
+
+type Props = {
+    current: DraftBundle
+    previous: DraftBundle
+}
+
+type Row<T> = { name: string; current?: T; previous?: T }
+
+// Rows follow the current period's order; anything dropped since the previous period is listed after it.
+const pairByName = <T extends { name: string }>(current: T[], previous: T[]): Row<T>[] => {
+    const earlier = new Map(previous.map((item) => [item.name, item]))
+    const rows: Row<T>[] = current.map((item) => ({ name: item.name, current: item, previous: earlier.get(item.name) }))
+    const seen = new Set(current.map((item) => item.name))
+    return [...rows, ...previous.filter((item) => !seen.has(item.name)).map((item) => ({ name: item.name, previous: item }))]
+}
+
+const changeOf = (current?: number, previous?: number) =>
+    current == null || previous == null ? undefined : Math.round((current - previous) * 10) / 10
+
+const trendClass = (change?: number) => (change == null ? undefined : change > 0 ? 'up' : change < 0 ? 'down' : 'flat')
+
+const MISSING = '—'
+
+function KpiComparison({ rows, dates }: { rows: Row<KPI>[]; dates: [string, string] }) {
+    return (
+        <table className="comparison-table">
+            <thead>
+                <tr>
+                    <th>KPI</th>
+                    <th className="numeric">{dates[0]}</th>
+                    <th className="numeric">{dates[1]}</th>
+                    <th className="numeric">Change</th>
+                </tr>
+            </thead>
+            <tbody>
+                {rows.map(({ name, current, previous }) => {
+                    // Formatting follows the current definition so a KPI whose unit changed still reads consistently.
+                    const kpi = (current ?? previous)!
+                    const change = changeOf(current?.value, previous?.value)
+                    return (
+                        <tr key={name}>
+                            <td>{name}</td>
+                            <td className="numeric">{previous ? formatKpiValue(kpi, previous.value) : MISSING}</td>
+                            <td className="numeric">{current ? formatKpiValue(kpi, current.value) : MISSING}</td>
+                            <td className={['numeric', 'delta', trendClass(change)].filter(Boolean).join(' ')}>
+                                {change == null ? MISSING : formatKpiChange(kpi, change)}
+                            </td>
+                        </tr>
+                    )
+                })}
+            </tbody>
+        </table>
+    )
+}
+
+function CategoryComparison({ rows }: { rows: Row<GrowthCategory>[] }) {
+    return (
+        <table className="comparison-table">
+            <thead>
+                <tr>
+                    <th>Growth category</th>
+                    <th className="numeric">Previous score</th>
+                    <th className="numeric">Current score</th>
+                    <th className="numeric">Change</th>
+                </tr>
+            </thead>
+            <tbody>
+                {rows.map(({ name, current, previous }) => {
+                    const change = changeOf(current?.score, previous?.score)
+                    return (
+                        <tr key={name}>
+                            <td>{name}</td>
+                            <td className="numeric">{previous ? previous.score : MISSING}</td>
+                            <td className="numeric">{current ? current.score : MISSING}</td>
+                            <td className={['numeric', 'delta', trendClass(change)].filter(Boolean).join(' ')}>
+                                {change == null ? MISSING : `${change > 0 ? '+' : ''}${change}`}
+                            </td>
+                        </tr>
+                    )
+                })}
+            </tbody>
+        </table>
+    )
+}
+
+// Left blank on purpose: consultants write the narrative for each movement after the numbers are laid out.
+function Commentary({ title }: { title: string }) {
+    return (
+        <div className="commentary">
+            <h4>{title}</h4>
+            <p className="commentary-placeholder">Trend commentary</p>
+        </div>
+    )
+}
+
+export function ComparisonReport({ current, previous }: Props) {
+    const kpis = pairByName(current.kpis ?? [], previous.kpis ?? [])
+    const categories = pairByName(current.growthCategories ?? [], previous.growthCategories ?? [])
+    const moved = kpis.filter((row) => {
+        const change = changeOf(row.current?.value, row.previous?.value)
+        return change != null && change !== 0
+    })
+
+    return (
+        <html lang="en" data-brand-profile={current.brandProfile} data-client-name={current.clientName} data-report-date={current.date}>
+            <head>
+                <meta charSet="utf-8" />
+                <style>{`
+                    ${pageRules({ margins: current.margins, duplex: current.duplex, printMarks: current.printMarks }, REPORT_MARGIN_MM)}
+                    ${themeVariables(current.theme)}
+                    ${shapingRules(current.theme)}
+                    ${paginationRules(current)}
+                    body { font-family: var(--rf-brand-font); color:#111; font-size:10px; line-height:var(--rf-line-height); letter-spacing:var(--rf-letter-spacing); }
+                    h1 { font-size:16px; margin:0; color:var(--rf-brand-primary); }
+                    h2 { font-size:14px; margin:var(--rf-space-xl) var(--rf-space-xs) var(--rf-space-s); color:var(--rf-brand-primary); }
+                    header { margin-bottom: var(--rf-space-s); display:flex; justify-content:space-between; align-items:center; gap:8px; }
+                    .header-brand{ display:flex; align-items:center; gap:8px; }
+                    .report-image{ display:block; }
+                    .report-image-svg svg{ width:100%; height:100%; display:block; }
+                    .header-meta{ text-align:right; font-size:10px; color:#475569; }
+                    .comparison-table{ width:100%; border-collapse:collapse; margin:var(--rf-space-s) 0; font-size:10px; }
+                    .comparison-table th,.comparison-table td{ padding:6px 8px; border-bottom:1px solid #e2e8f0; text-align:left; }
+                    .comparison-table th{ font-size:9.5px; background:#f8fafc; }
+                    .comparison-table thead{ display:table-header-group; }
+                    .comparison-table .numeric{ text-align:right; font-variant-numeric:tabular-nums; white-space:nowrap; }
+                    .delta{ font-weight:600; }
+                    .delta.up{ color:var(--rf-brand-accent); }
+                    .delta.down{ color:#b91c1c; }
+                    .delta.flat{ color:#475569; }
+                    .commentary{ margin:var(--rf-space-m) 0; padding:8px 10px; border-radius:8px; border:1px dashed #cbd5e1; break-inside:avoid; }
+                    .commentary h4{ margin:0 0 var(--rf-space-s); font-size:11px; }
+                    .commentary-placeholder{ margin:0; min-height:36px; color:#94a3b8; font-style:italic; }
+                    ${LEGAL_STYLES}
+                    ${baselineRules(current.theme)}
+                `}</style>
+            </head>
+            <body>
+                <header>
+                    <div className="header-brand">
+                        {current.logo && <ReportImage image={{ height: 32, ...current.logo }} className="header-logo" />}
+                        <h1>{current.clientName} — Period Comparison</h1>
+                    </div>
+                    <div className="header-meta">
+                        {previous.date} → {current.date}
+                    </div>
+                </header>
+                <main>
+                    <h2>Key metrics</h2>
+                    <KpiComparison rows={kpis} dates={[previous.date, current.date]} />
+                    {categories.length > 0 && (
+                        <>
+                            <h2>Growth categories</h2>
+                            <CategoryComparison rows={categories} />
+                        </>
+                    )}
+                    <h2>Trend commentary</h2>
+                    <Commentary title="Overall" />
+                    {moved.map((row) => (
+                        <Commentary key={row.name} title={row.name} />
+                    ))}
+                </main>
+            </body>
+        </html>
+    )
+}
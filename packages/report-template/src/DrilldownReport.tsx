@@ -1,4 +1,5 @@
 import React from 'react'
+import { autoFitColumns } from './columnFit'
 import type { DrilldownTable } from './types'
 
 type Props = {
@@ -73,6 +74,10 @@ export function DrilldownReport({ table }: Props) {
         return { label: column, headerLabel: column, width: '150px', className: 'detail-column' }
     })
 
+    const fitted = table.autoFit
+        ? autoFitColumns(columns, normalizedRows, table.autoFit === true ? undefined : table.autoFit).map((width) => `${(width * 100).toFixed(2)}%`)
+        : undefined
+
     return (
         <html>
             <head>
@@ -103,7 +108,7 @@ export function DrilldownReport({ table }: Props) {
                     <table className="drilldown-table">
                         <colgroup>
                             {columnMeta.map((meta, idx) => (
-                                <col key={`col-${idx}`} style={{ width: meta.width }} />
+                                <col key={`col-${idx}`} style={{ width: fitted?.[idx] ?? meta.width }} />
                             ))}
                         </colgroup>
                         <thead>
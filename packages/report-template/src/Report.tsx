@@ -1,21 +1,152 @@
 import React from 'react'
-import { GradientBar, GradientProgressBar } from './components/GradientProgressBar'
-import type { DraftBundle, GrowthCategory, ReportSection, SummaryDetail } from './types'
+import { BLOCK_STYLES, Blocks, blockCharts, blockLegal, blockOutline, blockTexts } from './components/Blocks'
+import { CATEGORY_DETAIL_STYLES, CategoryDetail, categoryAnchor } from './components/CategoryDetail'
+import { CATEGORY_TABLE_STYLES, CategoryTable } from './components/CategoryTable'
+import { CHART_STYLES, Chart } from './components/Chart'
+import { ExecutiveSummary } from './components/ExecutiveSummary'
+import { FOOTNOTE_STYLES, Footnotes } from './components/Footnotes'
+import { FORM_FIELD_STYLES, FormFields } from './components/FormFields'
+import { Icon } from './components/Icon'
+import { KpiGrid } from './components/KpiGrid'
+import { LEGAL_STYLES, Legal } from './components/Legal'
+import { METHODOLOGY_STYLES, Methodology } from './components/Methodology'
+import { PULL_QUOTE_STYLES, PullQuote } from './components/PullQuote'
+import { ReportImage } from './components/ReportImage'
+import { RICH_TEXT_STYLES, RichText } from './components/RichText'
+import { BAND_LABEL_STYLES, DEFAULT_SCORE_BANDS, SCORE_LEGEND_STYLES, ScoreLegend } from './components/ScoreLegend'
+import { SectionHeading } from './components/SectionHeading'
+import { TABLE_OF_CONTENTS_STYLES, TableOfContents } from './components/TableOfContents'
+import { groupCategories } from './categories'
+import { readableTextColor } from './contrast'
+import { numberOutline } from './outline'
+import { REPORT_MARGIN_MM, pageRules } from './page'
+import { paginationRules } from './pagination'
+import { ChartSlot } from './parallel'
+import { footnoteOffsets, hasCrossReferences } from './richText'
+import { ruleStyle } from './rules'
+import { baselineRules, shapingRules, themeVariables } from './theme'
+import type { BadgeStyle, Block, DraftBundle, FormField, FormatRule, GrowthCategory, ReportSection, SummaryDetail } from './types'
 
-type Props = Pick<DraftBundle, 'clientName' | 'date' | 'kpis'> & {
+type Props = Pick<
+    DraftBundle,
+    'clientName' | 'date' | 'logo' | 'kpis' | 'kpiDisplay' | 'categoryTable' | 'summaryBadges' | 'executiveSummary' | 'margins' | 'duplex' | 'sectionsStartRight' | 'printMarks' | 'grayscale' | 'theme' | 'numberHeadings' | 'tableOfContents' | 'runningHeaders' | 'debug' | 'brandProfile' | 'scoreLegend' | 'scoreBands' | 'bandLabels' | 'tableMinRows' | 'keepLines' | 'fitToPages' | 'continuationMarkers' | 'includeMethodology'
+> & {
     sections: ReportSection[]
     growthCategories: GrowthCategory[]
     summaryDetails: SummaryDetail[]
+    rules: FormatRule[]
+    formFields: FormField[]
+    lang?: string
+    // Appended to the title so each language edition gets its own top-level bookmark.
+    editionLabel?: string
+    // Chart SVG already rendered off-thread, keyed by section id; those sections leave a slot the caller fills in.
+    chartMarkup?: Record<string, string>
+    // Replaces the KPI grid and section layout; see DraftBundle.blocks.
+    blocks?: Block[]
 }
 
 const sectionGroup = (section?: ReportSection) => section?.group ?? 'general'
+const paragraphClass = (section: ReportSection) =>
+    section.align === 'justify' ? 'justified' : section.align === 'left' ? 'ragged' : undefined
 const currencyFormatter = new Intl.NumberFormat('en-US', {
     style: 'currency',
     currency: 'USD',
     maximumFractionDigits: 0,
 })
 
-export function Report({ clientName, date, kpis, sections, growthCategories, summaryDetails }: Props) {
+const BADGE_SIZE = 25
+
+// Per-item badge styling on top of the default green, applied after format rules since it was set for this item.
+const badgeStyle = (badge?: BadgeStyle): React.CSSProperties | undefined => {
+    if (!badge) return undefined
+    const color = badge.color ?? (badge.fill ? readableTextColor(badge.fill) : undefined)
+    return {
+        ...(badge.fill && { backgroundColor: badge.fill, borderColor: badge.fill }),
+        ...(color && { color }),
+        ...(badge.size && { width: badge.size, height: badge.size, fontSize: Math.round((badge.size * 12) / BADGE_SIZE) }),
+    }
+}
+
+function BadgeContent({ detail, position, fallback }: { detail: SummaryDetail; position: number; fallback?: DraftBundle['summaryBadges'] }) {
+    const icon = detail.icon ?? (!detail.label && fallback === 'icon' ? 'check' : undefined)
+    if (icon) return <Icon name={icon} size={Math.round(((detail.badge?.size ?? BADGE_SIZE) * 14) / BADGE_SIZE)} />
+    return <>{detail.label || (fallback === 'number' ? position : '•')}</>
+}
+
+// The score gradient is a raster fill, so grayscale mode swaps it for a light→dark ramp instead of desaturating it.
+const GRAYSCALE_RULES = `
+    .gradient-fill{ background-image: linear-gradient(90deg, #e5e5e5, #1f1f1f) !important; background-size: 100% 100% !important; }
+    img.report-image{ filter: grayscale(1); }
+`
+
+// `first-except` leaves the header empty on the page where a section starts and repeats it on continuation pages.
+const RUNNING_HEADER_RULES = `
+    [data-running]{ string-set: running-section attr(data-running); }
+    @page { @top-left { content: string(running-section, first-except); font-size:8px; color:#64748b; vertical-align:bottom; padding-bottom:2mm; } }
+`
+
+// Outlines don't affect layout, so the debug render paginates exactly like the real one.
+const DEBUG_RULES = `
+    .kpi-card,.question-item,.section,.executive-block,.toc,.category-table,.summary-table{ outline:0.5pt solid rgba(220,38,38,0.7); }
+    p,h1,h2,h3,h4,li,.summary-copy,.summary-badge{ outline:0.5pt dashed rgba(37,99,235,0.7); }
+    th,td{ outline:0.5pt dotted rgba(22,163,74,0.8); }
+    .debug-margins{ position:fixed; inset:0; border:0.5pt dashed rgba(217,70,239,0.8); pointer-events:none; z-index:9999; }
+`
+
+// Zoom shrinks type and spacing together while auto-width blocks still fill the page; the PDF service sets the scale.
+const FIT_RULES = `
+    header, main{ zoom: var(--rf-fit-scale, 1); }
+`
+
+// paged.js tags the fragments of a split element with data-split-to (before the break) and data-split-from (after it).
+const CONTINUATION_RULES = `
+    :is(.question-item, .section, .executive-block, .block-card, .form-fields, .category-detail){ position:relative; }
+    :is(.question-item, .section, .executive-block, .block-card, .form-fields, .category-detail)[data-split-to]::after{
+        content:'continued →'; position:absolute; right:6px; bottom:2px; font-size:7px; color:#94a3b8; font-style:italic;
+    }
+    :is(.question-item, .section, .executive-block, .block-card, .form-fields, .category-detail)[data-split-from]::before{
+        content:'← continued'; display:block; font-size:7px; color:#94a3b8; font-style:italic; margin-bottom:2px;
+    }
+`
+
+export function Report({
+    clientName,
+    date,
+    logo,
+    kpis,
+    kpiDisplay,
+    sections,
+    growthCategories,
+    categoryTable,
+    summaryBadges,
+    summaryDetails,
+    rules,
+    executiveSummary,
+    margins,
+    duplex,
+    sectionsStartRight,
+    printMarks,
+    grayscale,
+    theme,
+    numberHeadings,
+    tableOfContents,
+    runningHeaders,
+    debug,
+    brandProfile,
+    formFields,
+    scoreLegend,
+    scoreBands,
+    bandLabels,
+    tableMinRows,
+    keepLines,
+    fitToPages,
+    continuationMarkers,
+    includeMethodology,
+    lang = 'en',
+    editionLabel,
+    chartMarkup = {},
+    blocks,
+}: Props) {
     const questionSections = sections.filter((section) => sectionGroup(section) === 'question')
     const generalSections = sections.filter((section) => !['question', 'summary'].includes(sectionGroup(section)))
     const sectionLookup = Object.fromEntries(sections.map((section) => [section.id, section]))
@@ -25,177 +156,352 @@ export function Report({ clientName, date, kpis, sections, growthCategories, sum
         return linked?.text || detail.text || ''
     }
     
-    const kpiValues = kpis.map((k) => (k.practice ? `${k.value}% vs ${k.practice}% (${clientName})` : `${k.value}%`))
+    const sectionStart = duplex && sectionsStartRight ? 'section-start' : undefined
+
+    const detailCategories = categoryTable?.detailPages
+        ? groupCategories(growthCategories, categoryTable).flatMap((group) => group.categories)
+        : []
+
+    const summaryTexts = summaryDetails.map(resolveSummaryText)
+    const flowTexts = [
+        ...questionSections.map((section) => section.text),
+        ...summaryTexts,
+        ...generalSections.map((section) => section.text),
+        ...detailCategories.map((category) => category.narrative ?? ''),
+    ]
+    const footnoteStarts = footnoteOffsets(flowTexts)
+    const questionFootnotes = footnoteStarts.slice(0, questionSections.length)
+    const summaryFootnotes = footnoteStarts.slice(questionSections.length, questionSections.length + summaryTexts.length)
+    const generalStart = questionSections.length + summaryTexts.length
+    const generalFootnotes = footnoteStarts.slice(generalStart, generalStart + generalSections.length)
+    const detailFootnotes = footnoteStarts.slice(generalStart + generalSections.length)
+
+    // A blocks document is outlined by its headings; the legacy layout by its fixed sections.
+    const outline = numberOutline([
+        ...(blocks
+            ? blockOutline(blocks)
+            : [
+                  executiveSummary && { id: 'executive-summary', title: 'Executive Summary' },
+                  questionSections.length > 0 && {
+                      id: 'key-questions',
+                      title: 'Key Questions',
+                      children: questionSections.map((section) => ({ id: section.id, title: section.title })),
+                  },
+                  growthCategories.length > 0 && {
+                      id: 'breakdown',
+                      title: 'Breakdown by Category',
+                      children: detailCategories.map((category) => ({ id: categoryAnchor(category), title: category.name })),
+                  },
+                  summaryDetails.length > 0 && { id: 'summary-details', title: 'Summary Details' },
+                  ...generalSections.map((section) => ({ id: section.id, title: section.title })),
+              ]),
+        includeMethodology && { id: 'methodology', title: 'Methodology' },
+    ])
+    const headingNumbers = Object.fromEntries(outline.map((entry) => [entry.id, entry.number]))
+    const headingNumber = (id: string) => (numberHeadings ? headingNumbers[id] : undefined)
+
+    // Cross-reference and contents page numbers only resolve once the PDF service paginates the document itself;
+    // form widgets need the same pass to know which page each placeholder landed on, and split cards to be marked.
+    const paged =
+        Boolean(tableOfContents || runningHeaders || continuationMarkers) ||
+        formFields.length > 0 ||
+        [...flowTexts, ...blockTexts(blocks ?? [])].some(hasCrossReferences)
+    const running = (title: string) => (runningHeaders ? `${title} — continued` : undefined)
+    const legal = blockLegal(blocks ?? [])
+    // A blocks document renders only its blocks; legacy fields sent alongside are named on <html> so the PDF service
+    // can warn about them instead of dropping them silently.
+    const legacyFields = { executiveSummary: executiveSummary ? 1 : 0, kpis: kpis.length, sections: sections.length, growthCategories: growthCategories.length, summaryDetails: summaryDetails.length }
+    const ignoredFields = blocks ? Object.keys(legacyFields).filter((field) => legacyFields[field as keyof typeof legacyFields]) : []
+    // After the body in both layouts, closing quote included, so the body reads the same with or without it.
+    const methodology = includeMethodology && (
+        <Methodology scoreBands={scoreBands} number={headingNumber('methodology')} running={running('Methodology')} />
+    )
 
     return (
-        <html>
+        <html
+            lang={lang}
+            data-paged={paged ? 'true' : undefined}
+            data-brand-profile={brandProfile}
+            data-keep-blank-pages={sectionStart ? 'true' : undefined}
+            data-fit-to-pages={fitToPages}
+            data-client-name={clientName}
+            data-report-date={date}
+            data-ignored-fields={ignoredFields.length ? ignoredFields.join(' ') : undefined}
+        >
             <head>
                 <meta charSet="utf-8" />
                 <style>{`
-                    @page { size: Letter; margin: 0.35in; }
-                    body { font-family: system-ui, -apple-system, Segoe UI, Roboto, sans-serif; color:#111; font-size:10px; line-height:1.25; }
-                    h1 { font-size:16px; color:#111; }
-                    h2 { font-size:14px; margin:2px; color:#111; }
-                    h3 { font-size:12px; margin:2px; color:#111; }
-                    p { margin: 2px; }
-                    header { margin-bottom: 4px; display:flex; justify-content:space-between; align-items:center; gap:8px; }
+                    ${pageRules({ margins, duplex, printMarks }, REPORT_MARGIN_MM)}
+                    ${themeVariables(theme)}
+                    ${shapingRules(theme)}
+                    .section-start{ break-before: right; }
+                    main > :last-child, main > :last-child > :last-child{ margin-bottom:0; padding-bottom:0; }
+                    body { font-family: var(--rf-brand-font); color:#111; font-size:10px; line-height:var(--rf-line-height); letter-spacing:var(--rf-letter-spacing); }
+                    h1 { font-size:16px; color:var(--rf-brand-primary); }
+                    h2 { font-size:14px; margin:var(--rf-space-xs); color:var(--rf-brand-primary); }
+                    h3 { font-size:12px; margin:var(--rf-space-xs); color:var(--rf-brand-primary); }
+                    p { margin: var(--rf-paragraph-spacing) 2px; text-align: var(--rf-text-align); }
+                    p.justified{ text-align: justify; text-justify: inter-word; hyphens: auto; }
+                    p.ragged{ text-align: left; }
+                    header { margin-bottom: var(--rf-space-s); display:flex; justify-content:space-between; align-items:center; gap:8px; }
                     header h1{ margin:0; }
+                    .heading-number{ margin-right:6px; font-variant-numeric:tabular-nums; color:#475569; }
+                    .header-brand{ display:flex; align-items:center; gap:8px; }
+                    .report-image{ display:block; }
+                    .report-image-svg svg{ width:100%; height:100%; display:block; }
+                    .header-logo svg{ width:auto; }
+                    .section-image{ margin:var(--rf-space-m) 0; }
                     .header-meta{ text-align:right; font-size:10px; color:#475569; }
-                    .kpi { display:grid; grid-template-columns: repeat(auto-fit,minmax(165px,1fr)); gap:4px; margin-bottom: 4px; }
+                    .kpi { display:grid; grid-template-columns: repeat(auto-fit,minmax(165px,1fr)); gap:var(--rf-space-s); margin-bottom: var(--rf-space-s); }
                     .kpi-card{ padding:6px; border-radius:8px; background:#f8fafc; }
                     .kpi-card strong{ display:inline; font-size:10px; color:#111; }
-                    .questions{ margin: 6px 0; display:flex; flex-direction:column; gap:4px; }
+                    .kpi-delta{ margin-left:6px; font-size:9px; font-weight:600; white-space:nowrap; }
+                    .kpi-percentile{ margin:3px 0 0; font-size:8.5px; color:#475569; text-align:left; }
+                    .kpi-delta.up{ color:var(--rf-brand-accent); }
+                    .kpi-delta.down{ color:#b91c1c; }
+                    .kpi-delta.flat{ color:#475569; }
+                    .questions{ margin: var(--rf-space-m) 0; display:flex; flex-direction:column; gap:var(--rf-space-s); }
                     .question-item{ background:#f8fafc; border-radius:8px; padding:8px 10px; page-break-inside:avoid; }
-                    .question-item h4{ margin:0 0 4px; font-size:11px; color:#111; }
-                    .question-item p{ margin:0; color:#111; font-size:10px; line-height:1.25; }
-                    .category-table{ width:100%; border-collapse:collapse; margin:6px 0; font-size:10px; }
-                    .category-table th,.category-table td{ text-align:center; padding:6px; border-bottom:1px solid #e2e8f0; }
-                    .category-table th{ font-size:10px; color:#111; background:#f8fafc; }
-                    .category-table td:first-child{ font-weight:600; color:#111; text-align:left; }
-                    .category-name{ color:#111; font-weight:400; }
-                    .category-initial{ font-weight:700; color:#0f172a; }
-                    .category-note{ font-size:9px; color:#475569; margin:4px 0 8px; text-align: center; }
-                    .category-score{ min-width:130px; }
-                    .summary-table{ width:100%; border-collapse:collapse; margin:4px 0 4px; font-size:10.5px; }
+                    .question-item h4{ margin:0 0 var(--rf-space-s); font-size:11px; color:#111; }
+                    .question-item p{ margin:0; color:#111; font-size:10px; line-height:var(--rf-line-height); letter-spacing:var(--rf-letter-spacing); }
+                    .summary-table{ width:100%; border-collapse:collapse; margin:var(--rf-space-s) 0; font-size:10.5px; }
                     .summary-table th,.summary-table td{ padding:8px; border-bottom:1px solid #e2e8f0; vertical-align:middle; }
                     .summary-table th{ font-size:10px; color:#111; background:#f8fafc; text-align:left; }
+                    .summary-table tr{ break-inside:avoid; }
                     .summary-details-header{ display:flex; align-items:flex-start; justify-content:space-between; gap:12px; }
                     .summary-details-label{ font-weight:600; text-align:left; }
                     .summary-note-inline{ font-size:9px; color:#475569; text-align:right; white-space:nowrap; }
                     .summary-focus{ display:flex; align-items:center; gap:6px; }
                     .summary-badge{ flex-shrink: 0; width:25px; height:25px; border-radius:50%; background:rgba(4,120,87,0.15); color:#065f46; font-weight:600; display:flex; align-items:center; justify-content:center; font-size:12px; border:1px solid rgba(4,120,87,0.3); }
-                    .summary-copy{ margin:0; font-size:9.25px; color:#111; line-height:1.3; }
-                    .summary-profit{ text-align:right; font-weight:600; color:#047857; white-space:nowrap; }
-                    .section{ page-break-inside: avoid; margin: 10px 0; }
-                    .profit-callout{ margin:6px 0; padding:6px 8px; border-radius:8px; background:#f0fdf4; color:#065f46; font-size:10px; font-weight:500; border:1px solid rgba(6,95,70,0.2); }
-                    .quote-block{ font-style:italic; color:#111; margin:10px 0 2px; line-height: 1.8; }
-                    .quote-text{ display:inline; }
-                    .quote-signature{ font-family:"Zapfino"; font-size:9px; margin:0 0 20px 2rem; color:#111; font-weight:300; font-style:normal; white-space:nowrap; display:inline-block; letter-spacing:0.25px; }
+                    .summary-copy{ margin:0; font-size:9.25px; color:#111; line-height:calc(var(--rf-line-height) + 0.05); }
+                    .summary-profit{ text-align:right; font-weight:600; color:var(--rf-brand-accent); white-space:nowrap; }
+                    .section{ page-break-inside: avoid; margin: var(--rf-space-l) 0; }
+                    .profit-callout{ margin:var(--rf-space-m) 0; padding:6px 8px; border-radius:8px; background:#f0fdf4; color:#065f46; font-size:10px; font-weight:500; border:1px solid rgba(6,95,70,0.2); }
+                    .executive-summary{ page-break-after: always; }
+                    .executive-summary h2{ margin:var(--rf-space-s) 0 var(--rf-space-l); font-size:16px; }
+                    .executive-block{ margin:0 0 var(--rf-space-xl); padding:8px 10px; border-radius:8px; background:#f8fafc; }
+                    .executive-block h3{ margin:0 0 var(--rf-space-m); }
+                    .executive-kpis{ list-style:none; margin:0; padding:0; }
+                    .executive-kpis li{ display:flex; justify-content:space-between; padding:3px 0; border-bottom:1px solid #e2e8f0; }
+                    .executive-kpis li:last-child{ border-bottom:none; }
+                    .executive-focus{ background:#fef2f2; }
+                    .executive-excerpt{ margin:0 0 var(--rf-space-m); line-height:calc(var(--rf-line-height) + 0.1); }
                     img { max-width: 100%; }
+                    ${paginationRules({ tableMinRows, keepLines })}
+                    ${RICH_TEXT_STYLES}
+                    ${FOOTNOTE_STYLES}
+                    ${PULL_QUOTE_STYLES}
+                    ${LEGAL_STYLES}
+                    ${growthCategories.length ? CATEGORY_TABLE_STYLES : ''}
+                    ${detailCategories.length ? CATEGORY_DETAIL_STYLES : ''}
+                    ${sections.some((section) => section.chart) || blockCharts(blocks ?? []).length ? CHART_STYLES : ''}
+                    ${blocks ? BLOCK_STYLES : ''}
+                    ${tableOfContents ? TABLE_OF_CONTENTS_STYLES : ''}
+                    ${runningHeaders ? RUNNING_HEADER_RULES : ''}
+                    ${debug ? DEBUG_RULES : ''}
+                    ${fitToPages ? FIT_RULES : ''}
+                    ${continuationMarkers ? CONTINUATION_RULES : ''}
+                    ${formFields.length ? FORM_FIELD_STYLES : ''}
+                    ${includeMethodology ? METHODOLOGY_STYLES : ''}
+                    ${scoreLegend ? SCORE_LEGEND_STYLES : ''}
+                    ${bandLabels ? BAND_LABEL_STYLES : ''}
+                    ${grayscale ? GRAYSCALE_RULES : ''}
+                    ${baselineRules(theme, debug)}
                 `}</style>
             </head>
             <body>
+                {debug && <div className="debug-margins" />}
                 <header>
-                    <h1>{clientName} — Online Analysis</h1>
+                    <div className="header-brand">
+                        {logo && <ReportImage image={{ height: 32, ...logo }} className="header-logo" />}
+                        <h1>
+                            {clientName} — Online Analysis{editionLabel && ` · ${editionLabel}`}
+                        </h1>
+                    </div>
                     <div className="header-meta">{date}</div>
                 </header>
                 <main>
-                    <div className="kpi">
-                        {kpis.map((k, i) => (
-                            <div className="kpi-card" key={k.name}>
-                                <strong>{k.name}</strong>
-                                <GradientProgressBar value={k.value} height={12} gradientString={kpiValues[i]} />
-                            </div>
-                        ))}
-                    </div>
-                    {/* <p className="profit-callout">
-                        Additional profitability a top 10% practice captures averages <strong>$162,548</strong> per year.
-                    </p> */}
-                    {questionSections.length > 0 && (
+                    {blocks ? (
                         <>
-                            <h2>Key Questions</h2>
-                            <div className="questions">
-                                {questionSections.map((section) => (
-                                    <div className="question-item" key={section.id}>
-                                        <h4>{section.title}</h4>
-                                        <p>{section.text}</p>
-                                    </div>
-                                ))}
-                            </div>
+                            {tableOfContents && <TableOfContents entries={outline} showNumbers={numberHeadings} />}
+                            <Blocks
+                                blocks={blocks}
+                                context={{ rules, scoreBands, bandLabels, kpiDisplay, clientName, chartMarkup, headingNumber, running }}
+                            />
+                            {formFields.length > 0 && <FormFields fields={formFields} />}
+                            {methodology}
+                            <Legal texts={legal.map((block) => block.text)} title={legal.find((block) => block.title)?.title} />
                         </>
-                    )}
-
-                    {growthCategories.length > 0 && (
+                    ) : (
                         <>
-                            <h3>Breakdown by Category</h3>
-                            <table className="category-table">
-                                <thead>
-                                    <tr>
-                                        <th>Category</th>
-                                        <th>Score</th>
-                                        <th>Confidence</th>
-                                        <th>KPIs Scored</th>
-                                    </tr>
-                                </thead>
-                                <tbody>
-                                    {growthCategories.map((category) => (
-                                        <tr key={category.id}>
-                                            <td>
-                                                <span className="category-name">
-                                                    <span className="category-initial">{category.name.charAt(0)}</span>
-                                                    {category.name.slice(1)}
-                                                </span>
-                                            </td>
-                                            <td>
-                                                <div className="category-score">
-                                                    <GradientBar value={category.score} gradientString={category.explanation} height={10} trackColor="#e2e8f0" />
-                                                </div>
-                                            </td>
-                                            <td>{category.confidence}%</td>
-                                            <td>
-                                                {category.scored} of {category.total}
-                                            </td>
-                                        </tr>
-                                    ))}
-                                </tbody>
-                            </table>
-                            <p className="category-note">
-                                Score represents only KPIs currently scored. Score will adjust after completion of part 2 and 3 of analysis.
-                            </p>
-                        </>
-                    )}
+                            {executiveSummary && (
+                                <ExecutiveSummary
+                                    kpis={kpis}
+                                    growthCategories={growthCategories}
+                                    questionSections={questionSections}
+                                    number={headingNumber('executive-summary')}
+                                    running={running('Executive Summary')}
+                                />
+                            )}
+                            {tableOfContents && <TableOfContents entries={outline} showNumbers={numberHeadings} />}
+                            <KpiGrid
+                                kpis={kpis}
+                                display={kpiDisplay}
+                                rules={rules}
+                                scoreBands={scoreBands}
+                                bandLabels={bandLabels}
+                                clientName={clientName}
+                            />
+                            {scoreLegend && kpis.length > 0 && <ScoreLegend bands={scoreBands} />}
+                            {/* <p className="profit-callout">
+                                Additional profitability a top 10% practice captures averages <strong>$162,548</strong> per year.
+                            </p> */}
+                            {questionSections.length > 0 && (
+                                <>
+                                    <SectionHeading
+                                        as="h2"
+                                        id="key-questions"
+                                        number={headingNumber('key-questions')}
+                                        className={sectionStart}
+                                        running={running('Key Questions')}
+                                    >
+                                        Key Questions
+                                    </SectionHeading>
+                                    <div className="questions">
+                                        {questionSections.map((section, idx) => (
+                                            <div className="question-item" id={section.id} key={section.id}>
+                                                <SectionHeading as="h4" number={headingNumber(section.id)}>
+                                                    {section.icon && <Icon name={section.icon} size={11} style={{ marginRight: 4 }} />}
+                                                    {section.title}
+                                                </SectionHeading>
+                                                <p className={paragraphClass(section)}>
+                                                    <RichText text={section.text} footnoteStart={questionFootnotes[idx]} />
+                                                </p>
+                                                <Footnotes texts={[section.text]} start={questionFootnotes[idx]} />
+                                            </div>
+                                        ))}
+                                    </div>
+                                </>
+                            )}
 
-                    {summaryDetails.length > 0 && (
-                        <>
-                            <table className="summary-table">
-                                <thead>
-                                    <tr>
-                                        <th className="summary-details-header">
-                                            <span className="summary-details-label">Summary Details</span>
-                                            {/* <span className="summary-note-inline">
-                                                * Ask about our Profit Accelerator to turn these projected gains into your actual profit.
-                                            </span> */}
-                                        </th>
-                                        {/* <th className="summary-profit">Avg Profit ↗</th> */}
-                                    </tr>
-                                </thead>
-                                <tbody>
-                                    {summaryDetails.map((detail) => (
-                                        <tr key={detail.id}>
-                                            <td>
-                                                <div className="summary-focus">
-                                                    <span className="summary-badge">{detail.label}</span>
-                                                    <p className="summary-copy">{resolveSummaryText(detail)}</p>
-                                                </div>
-                                            </td>
-                                            {/* <td className="summary-profit">
-                                                {detail.avgProfit != null ? currencyFormatter.format(detail.avgProfit) : '—'}
-                                            </td> */}
-                                        </tr>
-                                    ))}
-                                </tbody>
-                            </table>
+                            {growthCategories.length > 0 && (
+                                <>
+                                    <SectionHeading
+                                        as="h3"
+                                        id="breakdown"
+                                        number={headingNumber('breakdown')}
+                                        className={sectionStart}
+                                        running={running('Breakdown by Category')}
+                                    >
+                                        Breakdown by Category
+                                    </SectionHeading>
+                                    <CategoryTable
+                                        categories={growthCategories}
+                                        options={categoryTable}
+                                        rules={rules}
+                                        bands={bandLabels ? (scoreBands ?? DEFAULT_SCORE_BANDS) : undefined}
+                                    />
+                                    {scoreLegend && <ScoreLegend bands={scoreBands} />}
+                                    <p className="category-note">
+                                        Score represents only KPIs currently scored. Score will adjust after completion of part 2 and 3 of analysis.
+                                    </p>
+                                </>
+                            )}
+
+                            {summaryDetails.length > 0 && (
+                                <>
+                                    <table id="summary-details" className={['summary-table', sectionStart].filter(Boolean).join(' ')}>
+                                        <thead>
+                                            <tr>
+                                                <th className="summary-details-header">
+                                                    <span className="summary-details-label" data-running={running('Summary Details')}>
+                                                        {headingNumber('summary-details') && (
+                                                            <span className="heading-number">{headingNumber('summary-details')}</span>
+                                                        )}
+                                                        Summary Details
+                                                    </span>
+                                                    {/* <span className="summary-note-inline">
+                                                        * Ask about our Profit Accelerator to turn these projected gains into your actual profit.
+                                                    </span> */}
+                                                </th>
+                                                {/* <th className="summary-profit">Avg Profit ↗</th> */}
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            {summaryDetails.map((detail, idx) => (
+                                                <tr key={detail.id}>
+                                                    <td>
+                                                        <div className="summary-focus">
+                                                            <span
+                                                                className="summary-badge"
+                                                                style={{ ...ruleStyle(rules, 'summary', 'avgProfit', detail), ...badgeStyle(detail.badge) }}
+                                                            >
+                                                                <BadgeContent detail={detail} position={idx + 1} fallback={summaryBadges} />
+                                                            </span>
+                                                            <p className="summary-copy">
+                                                                <RichText text={summaryTexts[idx] ?? ''} footnoteStart={summaryFootnotes[idx]} />
+                                                            </p>
+                                                        </div>
+                                                        <Footnotes texts={[summaryTexts[idx] ?? '']} start={summaryFootnotes[idx]} />
+                                                    </td>
+                                                    {/* <td className="summary-profit">
+                                                        {detail.avgProfit != null ? currencyFormatter.format(detail.avgProfit) : '—'}
+                                                    </td> */}
+                                                </tr>
+                                            ))}
+                                        </tbody>
+                                    </table>
+                                </>
+                            )}
+
+                            {generalSections.map((section, idx) => (
+                                <section
+                                    className={['section', sectionStart, section.orientation === 'landscape' && 'landscape'].filter(Boolean).join(' ')}
+                                    id={section.id}
+                                    key={section.id}
+                                >
+                                    <SectionHeading as="h3" number={headingNumber(section.id)} running={running(section.title)}>
+                                        {section.icon && <Icon name={section.icon} size={12} style={{ marginRight: 4 }} />}
+                                        {section.title}
+                                    </SectionHeading>
+                                    <p className={paragraphClass(section)}>
+                                        <RichText text={section.text} footnoteStart={generalFootnotes[idx]} />
+                                    </p>
+                                    <Footnotes texts={[section.text]} start={generalFootnotes[idx]} />
+                                    {section.chart &&
+                                        (chartMarkup[section.id] ? (
+                                            <ChartSlot id={section.id} />
+                                        ) : (
+                                            <Chart chart={section.chart} />
+                                        ))}
+                                    {section.image && <ReportImage image={section.image} className="section-image" />}
+                                </section>
+                            ))}
+
+                            {detailCategories.map((category, idx) => (
+                                <CategoryDetail
+                                    key={category.id}
+                                    category={category}
+                                    number={headingNumber(categoryAnchor(category))}
+                                    running={running(category.name)}
+                                    footnoteStart={detailFootnotes[idx]}
+                                />
+                            ))}
+
+                            {formFields.length > 0 && <FormFields fields={formFields} />}
+
+                            <p>
+                                Learn more about GROWTH Practice Optimization Partnership, the new <u><i>Zero Risk</i></u> way to win in
+                                dentistry!
+                            </p>
+                            <PullQuote
+                                text="We love helping practices double their profitability risk free without having to come up with money out of their pocket. It's a game changer for the practice and unbelievably fulfilling for our team, for practices that qualify."
+                                attribution="Shawn Rowbotham"
+                                signature
+                            />
+                            {methodology}
                         </>
                     )}
-
-                    {generalSections.map((section) => (
-                        <section className="section" key={section.id}>
-                            <h3>{section.title}</h3>
-                            <p>{section.text}</p>
-                        </section>
-                    ))}
-
-                    <p>
-                        Learn more about GROWTH Practice Optimization Partnership, the new <u><i>Zero Risk</i></u> way to win in
-                        dentistry!
-                    </p>
-                    <blockquote className="quote-block">
-                        <span className="quote-text">
-                            &quot;We love helping practices double their profitability risk free without having to come up with money out of
-                            their pocket. It's a game changer for the practice and unbelievably fulfilling for our team, for practices that qualify.&quot;
-                        </span>
-                        <span className="quote-signature">Shawn Rowbotham</span>
-                    </blockquote>
                 </main>
             </body>
         </html>
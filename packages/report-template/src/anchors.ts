@@ -0,0 +1,13 @@
+import { blockAnchors } from './components/Blocks'
+import type { DraftBundle } from './types'
+
+// Anchors the template gives its own parts, which cross-references such as [[methodology]] can point at as well.
+const FIXED_ANCHORS = ['table-of-contents', 'executive-summary', 'key-questions', 'breakdown', 'summary-details', 'follow-up-notes', 'methodology']
+// Generated per growth category and per form field.
+const GENERATED_PREFIXES = ['category-', 'form-']
+
+const reserved = (id: string) => FIXED_ANCHORS.includes(id) || GENERATED_PREFIXES.some((prefix) => id.startsWith(prefix))
+
+// Section and heading ids that would land on the same anchor as one of the template's own.
+export const reservedAnchors = (bundle: DraftBundle) =>
+    [...(bundle.sections ?? []).map((section) => section.id), ...blockAnchors(bundle.blocks ?? [])].filter(reserved)
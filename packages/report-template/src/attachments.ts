@@ -0,0 +1,44 @@
+import { redactBundle } from './redact'
+import type { DraftBundle, KPI } from './types'
+
+export type SourceAttachment = {
+    name: string
+    mimeType: string
+    description: string
+    content: string
+}
+
+const csvCell = (value: string | number | undefined) => {
+    const text = value == null ? '' : String(value)
+    return /[",\n]/.test(text) ? `"${text.replace(/"/g, '""')}"` : text
+}
+
+export function kpiCsv(kpis: KPI[]) {
+    const rows = [
+        ['name', 'value', 'practice', 'previousValue', 'delta', 'min', 'max', 'valueType', 'unit'],
+        ...kpis.map((kpi) => [kpi.name, kpi.value, kpi.practice, kpi.previousValue, kpi.delta, kpi.min, kpi.max, kpi.valueType ?? 'percent', kpi.unit]),
+    ]
+    return `${rows.map((row) => row.map(csvCell).join(',')).join('\n')}\n`
+}
+
+// The PDF service embeds this as a file attachment so auditors can pull the underlying numbers out of the report.
+export function sourceAttachment(source: DraftBundle): SourceAttachment | undefined {
+    const bundle = redactBundle(source)
+    if (bundle.attachSource === 'csv') {
+        return {
+            name: 'kpis.csv',
+            mimeType: 'text/csv',
+            description: `KPIs for ${bundle.clientName} (${bundle.date})`,
+            content: kpiCsv(bundle.kpis || []),
+        }
+    }
+    if (bundle.attachSource === 'json') {
+        return {
+            name: 'report-data.json',
+            mimeType: 'application/json',
+            description: `Source data for ${bundle.clientName} (${bundle.date})`,
+            content: JSON.stringify(bundle, null, 2),
+        }
+    }
+    return undefined
+}
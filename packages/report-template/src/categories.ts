@@ -0,0 +1,59 @@
+import type { CategoryTableOptions, GrowthCategory } from './types'
+
+const byName = new Intl.Collator('en-US', { sensitivity: 'base', numeric: true })
+
+// Numbers read best highest-first, names alphabetically; ties keep the payload order (Array.prototype.sort is stable).
+export function sortCategories(categories: GrowthCategory[], options?: CategoryTableOptions) {
+    const sortBy = options?.sortBy
+    if (!sortBy) return categories
+    const direction = options.direction ?? (sortBy === 'name' ? 'asc' : 'desc')
+    const sign = direction === 'asc' ? 1 : -1
+    return [...categories].sort((a, b) =>
+        sign * (sortBy === 'name' ? byName.compare(a.name, b.name) : a[sortBy] - b[sortBy])
+    )
+}
+
+export type CategoryGroup = {
+    name?: string
+    categories: GrowthCategory[]
+    subtotal?: CategorySubtotal
+}
+
+export type CategorySubtotal = {
+    score: number
+    // Only set when every category in the group has a previous score.
+    previousScore?: number
+    confidence: number
+    scored: number
+    total: number
+}
+
+const average = (values: number[]) => Math.round((values.reduce((sum, value) => sum + value, 0) / values.length) * 10) / 10
+
+export const scoreChange = (score: number, previousScore?: number) =>
+    previousScore == null ? undefined : Math.round((score - previousScore) * 10) / 10
+
+export function subtotal(categories: GrowthCategory[]): CategorySubtotal {
+    return {
+        score: average(categories.map((category) => category.score)),
+        previousScore: categories.every((category) => category.previousScore != null)
+            ? average(categories.map((category) => category.previousScore ?? 0))
+            : undefined,
+        confidence: average(categories.map((category) => category.confidence)),
+        scored: categories.reduce((sum, category) => sum + category.scored, 0),
+        total: categories.reduce((sum, category) => sum + category.total, 0),
+    }
+}
+
+// Groups keep the order given in `groups`, then first appearance; ungrouped categories collect under "Other" at the end.
+export function groupCategories(categories: GrowthCategory[], options?: CategoryTableOptions): CategoryGroup[] {
+    const sorted = sortCategories(categories, options)
+    if (!sorted.some((category) => category.group)) return [{ categories: sorted }]
+    const names = [...new Set([...(options?.groups ?? []), ...sorted.map((category) => category.group ?? 'Other')])]
+    return names
+        .map((name) => {
+            const members = sorted.filter((category) => (category.group ?? 'Other') === name)
+            return { name, categories: members, subtotal: options?.subtotals === false ? undefined : subtotal(members) }
+        })
+        .filter((group) => group.categories.length > 0)
+}
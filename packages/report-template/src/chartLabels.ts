@@ -0,0 +1,67 @@
+const CHAR_WIDTH = 4.6
+const LABEL_HEIGHT = 8
+const GAP = 2
+
+export type LabelCandidate = {
+    text: string
+    x: number
+    y: number
+    // Bars also pass their extent so labels can sit inside tall bars.
+    bar?: { width: number; height: number }
+}
+
+export type LabelPlacement = {
+    text: string
+    x: number
+    y: number
+    anchor: 'middle' | 'start'
+    rotate: boolean
+    inside: boolean
+}
+
+export type Box = { left: number; right: number; top: number; bottom: number }
+
+const overlaps = (a: Box, b: Box) => a.left < b.right && b.left < a.right && a.top < b.bottom && b.top < a.bottom
+
+export const labelBox = (placement: LabelPlacement): Box => {
+    const width = placement.text.length * CHAR_WIDTH
+    if (placement.rotate) return { left: placement.x - LABEL_HEIGHT / 2, right: placement.x + LABEL_HEIGHT / 2, top: placement.y - width, bottom: placement.y }
+    return { left: placement.x - width / 2, right: placement.x + width / 2, top: placement.y - LABEL_HEIGHT, bottom: placement.y }
+}
+
+const barPlacements = ({ text, x, y, bar }: LabelCandidate & { bar: { width: number; height: number } }): LabelPlacement[] => {
+    const width = text.length * CHAR_WIDTH
+    const fitsAcross = width <= bar.width
+    return [
+        // Inside the top of the bar when it's tall and wide enough, otherwise above it, rotated for narrow bars.
+        ...(fitsAcross && bar.height >= LABEL_HEIGHT + GAP * 2
+            ? [{ text, x, y: y + LABEL_HEIGHT + GAP, anchor: 'middle' as const, rotate: false, inside: true }]
+            : []),
+        fitsAcross
+            ? { text, x, y: y - GAP, anchor: 'middle', rotate: false, inside: false }
+            : { text, x: x + LABEL_HEIGHT / 3, y: y - GAP, anchor: 'start', rotate: true, inside: false },
+    ]
+}
+
+const pointPlacements = ({ text, x, y }: LabelCandidate): LabelPlacement[] => [
+    { text, x, y: y - GAP - 1, anchor: 'middle', rotate: false, inside: false },
+    { text, x, y: y + LABEL_HEIGHT + GAP + 1, anchor: 'middle', rotate: false, inside: false },
+]
+
+// Greedy: each label takes the first candidate position that stays inside the plot and clears labels placed so far;
+// labels with no free position are dropped rather than drawn on top of each other.
+export function placeLabels(candidates: LabelCandidate[], bounds: Box): LabelPlacement[] {
+    const placed: { placement: LabelPlacement; box: Box }[] = []
+    for (const candidate of candidates) {
+        const options = candidate.bar ? barPlacements({ ...candidate, bar: candidate.bar }) : pointPlacements(candidate)
+        for (const placement of options) {
+            const box = labelBox(placement)
+            const inBounds = box.top >= bounds.top && box.bottom <= bounds.bottom && box.left >= bounds.left && box.right <= bounds.right
+            if (inBounds && !placed.some((other) => overlaps(box, other.box))) {
+                placed.push({ placement, box })
+                break
+            }
+        }
+    }
+    return placed.map(({ placement }) => placement)
+}
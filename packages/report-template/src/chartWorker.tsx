@@ -0,0 +1,9 @@
+import { renderToStaticMarkup } from 'react-dom/server'
+import { Chart } from './components/Chart'
+import type { ChartSpec } from './types'
+
+// One chart per message; the pool in parallel.tsx keeps each worker busy with the next one.
+self.onmessage = (event: MessageEvent<{ id: string; chart: ChartSpec }>) => {
+    const { id, chart } = event.data
+    self.postMessage({ id, markup: renderToStaticMarkup(<Chart chart={chart} />) })
+}
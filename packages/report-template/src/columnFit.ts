@@ -0,0 +1,33 @@
+import type { ColumnFit } from './types'
+
+const DEFAULT_MIN = 0.06
+const DEFAULT_MAX = 0.6
+// Beyond this a cell wraps anyway, so a single long note doesn't claim the whole table.
+const WRAP_CHARS = 48
+
+const demand = (values: string[]) => {
+    const longestWord = Math.max(0, ...values.flatMap((value) => value.split(/\s+/).map((word) => word.length)))
+    const longest = Math.max(0, ...values.map((value) => Math.min(value.length, WRAP_CHARS)))
+    return Math.max(1, longestWord, longest)
+}
+
+// Character counts stand in for rendered widths: the template has no layout engine, and relative widths are all
+// that matter here.
+export function autoFitColumns(columns: string[], rows: unknown[][], { min = DEFAULT_MIN, max = DEFAULT_MAX }: ColumnFit = {}) {
+    if (!columns.length) return []
+    const floor = Math.min(min, 1 / columns.length)
+    const ceiling = Math.max(max, 1 / columns.length)
+    const demands = columns.map((column, idx) => demand([column, ...rows.map((row) => (row?.[idx] == null ? '' : String(row[idx])))]))
+    const total = demands.reduce((sum, value) => sum + value, 0)
+    let widths = demands.map((value) => value / total)
+    // Each pass hands the width freed (or owed) by clamping to the columns that can still move, by demand.
+    for (let pass = 0; pass <= columns.length; pass++) {
+        widths = widths.map((width) => Math.min(ceiling, Math.max(floor, width)))
+        const excess = 1 - widths.reduce((sum, width) => sum + width, 0)
+        if (Math.abs(excess) < 1e-9) break
+        const free = widths.flatMap((width, idx) => ((excess > 0 ? width < ceiling : width > floor) ? [idx] : []))
+        const weight = free.reduce((sum, idx) => sum + demands[idx]!, 0)
+        free.forEach((idx) => (widths[idx]! += (excess * demands[idx]!) / weight))
+    }
+    return widths
+}
@@ -0,0 +1,349 @@
+import React from 'react'
+import { Chart } from './Chart'
+import { CONTACT_CARD_STYLES, ContactCard } from './ContactCard'
+import { Footnotes } from './Footnotes'
+import { Icon } from './Icon'
+import { KpiGrid } from './KpiGrid'
+import { PullQuote } from './PullQuote'
+import { ReportImage } from './ReportImage'
+import { RichText } from './RichText'
+import { SectionHeading } from './SectionHeading'
+import { autoFitColumns } from '../columnFit'
+import { readableTextColor } from '../contrast'
+import type { OutlineNode } from '../outline'
+import { ChartSlot } from '../parallel'
+import { footnoteOffsets } from '../richText'
+import { ruleStyle, rowStyle } from '../rules'
+import type { Block, ChartSpec, FormatRule, KpiDisplay, LegalBlock, ScoreBand, TableBlock, TableCell, TableRow } from '../types'
+
+export const BLOCK_STYLES = `
+    .block-table{ width:100%; border-collapse:collapse; margin:var(--rf-space-s) 0; font-size:10px; }
+    .block-table caption{ caption-side:top; text-align:left; font-weight:600; padding:0 2px 4px; }
+    .block-table th,.block-table td{ padding:6px 8px; border-bottom:1px solid #e2e8f0; text-align:left; vertical-align:middle; }
+    .block-table th{ font-size:9.5px; background:#f8fafc; }
+    .block-table.striped tbody tr.even{ background:#f8fafc; }
+    .block-table .sub-row td{ padding:3px 8px; font-size:9px; color:#475569; border-bottom:1px dotted #e2e8f0; }
+    .block-table .sub-row td:first-child{ padding-left:20px; }
+    .block-table tr:has(+ .sub-row){ break-after:avoid; }
+    .block-table.fitted{ table-layout:fixed; }
+    .block-table.fitted td{ overflow-wrap:anywhere; }
+    .block-table .numeric{ text-align:right; font-variant-numeric:tabular-nums; white-space:nowrap; }
+    .block-card{ margin:var(--rf-space-m) 0; padding:8px 10px; border-radius:8px; background:#f8fafc; break-inside:avoid; }
+    .block-card.positive{ background:#f0fdf4; border:1px solid rgba(6,95,70,0.2); }
+    .block-card.warning{ background:#fef2f2; border:1px solid rgba(185,28,28,0.2); }
+    .block-card > h4{ margin:0 0 var(--rf-space-s); font-size:11px; }
+    .block-card > :last-child{ margin-bottom:0; }
+    .block-divider{ border:0; }
+    ${CONTACT_CARD_STYLES}
+    .block-checklist{ margin:var(--rf-space-s) 0; break-inside:avoid; }
+    .block-checklist > h4{ margin:0 0 var(--rf-space-xs); font-size:11px; }
+    .block-checklist ul{ list-style:none; margin:0; padding:0; }
+    .block-checklist li{ display:flex; align-items:baseline; gap:6px; margin:var(--rf-space-xs) 0; break-inside:avoid; }
+    .block-checklist svg{ flex-shrink:0; position:relative; top:1px; }
+    .block-checklist li.done{ color:#475569; }
+    .block-checklist.strike li.done span{ text-decoration:line-through; text-decoration-color:#94a3b8; }
+    .block-timeline{ margin:var(--rf-space-m) 0; }
+    .block-timeline > h4{ margin:0 0 var(--rf-space-s); font-size:11px; }
+    .block-timeline ol{ list-style:none; margin:0; padding:0; }
+    .block-timeline li{ position:relative; display:grid; grid-template-columns:64px 1fr; column-gap:22px; padding-bottom:var(--rf-space-l); break-inside:avoid; }
+    .block-timeline li::before{ content:''; position:absolute; left:75px; top:9px; bottom:-3px; border-left:1.5px solid #cbd5e1; }
+    .block-timeline li:last-child::before{ display:none; }
+    .block-timeline li:last-child{ padding-bottom:0; }
+    .block-timeline li::after{ content:''; position:absolute; left:70px; top:2px; width:8px; height:8px; border-radius:50%; border:1.5px solid var(--rf-brand-accent); background:#fff; }
+    .block-timeline li.done::after{ background:var(--rf-brand-accent); }
+    .block-timeline li.current::after{ background:var(--rf-brand-accent); box-shadow:0 0 0 3px rgba(4,120,87,0.2); }
+    .block-timeline li.upcoming::after{ border-color:#94a3b8; }
+    .milestone-date{ font-size:9px; font-weight:600; color:#475569; text-align:right; font-variant-numeric:tabular-nums; }
+    .milestone-title{ font-weight:600; color:#0f172a; }
+    .milestone-description{ margin:2px 0 0; color:#334155; }
+`
+
+// Drawn rather than typed: ☐/☑ are missing from most embedded fonts.
+const CheckBox = ({ checked }: { checked?: boolean }) => (
+    <svg viewBox="0 0 12 12" width={10} height={10} aria-hidden="true">
+        <rect
+            x={0.75}
+            y={0.75}
+            width={10.5}
+            height={10.5}
+            rx={2}
+            fill={checked ? 'var(--rf-brand-accent)' : '#fff'}
+            stroke={checked ? 'var(--rf-brand-accent)' : '#94a3b8'}
+            strokeWidth={1.5}
+        />
+        {checked && <path d="M3 6.2 5.1 8.3 9 3.9" fill="none" stroke="#fff" strokeWidth={1.6} strokeLinecap="round" strokeLinejoin="round" />}
+    </svg>
+)
+
+export type BlockContext = {
+    rules: FormatRule[]
+    scoreBands?: ScoreBand[]
+    bandLabels?: boolean
+    kpiDisplay?: KpiDisplay
+    clientName: string
+    // Chart SVG rendered off-thread, keyed by blockKey; those charts leave a slot the caller fills in.
+    chartMarkup: Record<string, string>
+    headingNumber: (id: string) => string | undefined
+    running: (title: string) => string | undefined
+}
+
+type Placed = { block: Block; key: string }
+
+// Blocks are addressed by their position, e.g. "blocks.3.0" for the first block inside the fourth (a card).
+const flatten = (blocks: Block[], prefix = 'blocks'): Placed[] =>
+    blocks.flatMap((block, idx) => {
+        const key = `${prefix}.${idx}`
+        return [{ block, key }, ...(block.type === 'card' ? flatten(block.blocks ?? [], key) : [])]
+    })
+
+// Texts that can cite footnotes, in the order the block sets them.
+const footnoteTexts = (block: Block) => {
+    switch (block.type) {
+        case 'paragraph':
+        case 'quote':
+            return [block.text]
+        case 'checklist':
+            return (block.items ?? []).map((item) => item.text)
+        default:
+            return []
+    }
+}
+
+export const blockKey = (block: Block, key: string) => (block.type === 'chart' && block.id) || key
+
+export function blockCharts(blocks: Block[]): { id: string; chart: ChartSpec }[] {
+    return flatten(blocks).flatMap(({ block, key }) => (block.type === 'chart' ? [{ id: blockKey(block, key), chart: block.chart }] : []))
+}
+
+export function blockTexts(blocks: Block[]) {
+    return flatten(blocks).flatMap(({ block }) => (block.type === 'paragraph' ? [block.text] : []))
+}
+
+export function blockLegal(blocks: Block[]): LegalBlock[] {
+    return flatten(blocks).flatMap(({ block }) => (block.type === 'legal' ? [block] : []))
+}
+
+export function blockAnchors(blocks: Block[]) {
+    return flatten(blocks).flatMap(({ block }) => (block.type === 'heading' && block.id ? [block.id] : []))
+}
+
+// Only headings with an id are linkable, so only those reach the outline; h3/h4 nest under the preceding h2.
+export function blockOutline(blocks: Block[]): OutlineNode[] {
+    const nodes: OutlineNode[] = []
+    flatten(blocks).forEach(({ block }) => {
+        if (block.type !== 'heading' || !block.id) return
+        const node = { id: block.id, title: block.text }
+        const parent = nodes[nodes.length - 1]
+        if ((block.level ?? 2) > 2 && parent) parent.children = [...(parent.children ?? []), node]
+        else nodes.push(node)
+    })
+    return nodes
+}
+
+const cellValue = (cell: TableCell | undefined) => (cell != null && typeof cell === 'object' ? cell.value : cell)
+
+const cellText = (cell: TableCell | undefined) => {
+    const value = cellValue(cell)
+    return value == null || value === '' ? '\u00a0' : String(value)
+}
+
+const cellStyle = (cell: TableCell | undefined): React.CSSProperties | undefined => {
+    if (cell == null || typeof cell !== 'object') return undefined
+    const style: React.CSSProperties = {}
+    if (cell.background) style.backgroundColor = cell.background
+    const color = cell.color ?? (cell.background ? readableTextColor(cell.background) : undefined)
+    if (color) style.color = color
+    if (cell.bold) style.fontWeight = 700
+    return style
+}
+
+function TableRows({ block, context }: { block: TableBlock; context: BlockContext }) {
+    const numeric = new Set(block.numericColumns ?? [])
+    const row = (cells: TableRow, key: string, className?: string) => {
+        // Rules address cells by column header.
+        const item = Object.fromEntries(block.columns.map((column, col) => [column, cellValue(cells?.[col])]))
+        return (
+            <tr key={key} className={className} style={rowStyle(context.rules, 'table', item)}>
+                {block.columns.map((column, col) => (
+                    <td key={col} className={numeric.has(col) ? 'numeric' : undefined} style={{ ...ruleStyle(context.rules, 'table', column, item), ...cellStyle(cells?.[col]) }}>
+                        {cellText(cells?.[col])}
+                    </td>
+                ))}
+            </tr>
+        )
+    }
+    // Parity is counted over parent rows only; :nth-child would count the sub-rows in between too.
+    return (
+        <tbody>
+            {(block.rows ?? []).flatMap((cells, rowIdx) => [
+                row(cells, String(rowIdx), block.striped && rowIdx % 2 ? 'even' : undefined),
+                ...(block.subRows?.[rowIdx] ?? []).map((sub, subIdx) => row(sub, `${rowIdx}.${subIdx}`, 'sub-row')),
+            ])}
+        </tbody>
+    )
+}
+
+function BlockList({ blocks, prefix, context, footnotes }: { blocks: Block[]; prefix: string; context: BlockContext; footnotes: Map<string, number[]> }) {
+    return (
+        <>
+            {blocks.map((block, idx) => {
+                const key = `${prefix}.${idx}`
+                switch (block.type) {
+                    case 'heading': {
+                        const level = block.level ?? 2
+                        return (
+                            <SectionHeading
+                                key={key}
+                                as={`h${level}` as const}
+                                id={block.id}
+                                number={block.id ? context.headingNumber(block.id) : undefined}
+                                running={level === 2 ? context.running(block.text) : undefined}
+                            >
+                                {block.icon && <Icon name={block.icon} size={12} style={{ marginRight: 4 }} />}
+                                {block.text}
+                            </SectionHeading>
+                        )
+                    }
+                    case 'paragraph':
+                        return (
+                            <React.Fragment key={key}>
+                                <p className={block.align === 'justify' ? 'justified' : block.align === 'left' ? 'ragged' : undefined}>
+                                    <RichText text={block.text} footnoteStart={footnotes.get(key)?.[0]} />
+                                </p>
+                                <Footnotes texts={[block.text]} start={footnotes.get(key)?.[0]} />
+                            </React.Fragment>
+                        )
+                    case 'kpiGrid':
+                        return (
+                            <KpiGrid
+                                key={key}
+                                kpis={block.kpis ?? []}
+                                display={block.display ?? context.kpiDisplay}
+                                rules={context.rules}
+                                scoreBands={context.scoreBands}
+                                bandLabels={context.bandLabels}
+                                clientName={context.clientName}
+                            />
+                        )
+                    case 'table': {
+                        const numeric = new Set(block.numericColumns ?? [])
+                        const className = (idx: number) => (numeric.has(idx) ? 'numeric' : undefined)
+                        const widths = block.autoFit
+                            ? autoFitColumns(
+                                  block.columns,
+                                  [...(block.rows ?? []), ...Object.values(block.subRows ?? {}).flat()].map((row) => row.map(cellValue)),
+                                  block.autoFit === true ? undefined : block.autoFit,
+                              )
+                            : undefined
+                        return (
+                            <table key={key} className={['block-table', widths && 'fitted', block.striped && 'striped'].filter(Boolean).join(' ')}>
+                                {block.caption && <caption>{block.caption}</caption>}
+                                {widths && (
+                                    <colgroup>
+                                        {widths.map((width, col) => (
+                                            <col key={col} style={{ width: `${(width * 100).toFixed(2)}%` }} />
+                                        ))}
+                                    </colgroup>
+                                )}
+                                <thead>
+                                    <tr>
+                                        {block.columns.map((column, col) => (
+                                            <th key={col} className={className(col)}>
+                                                {column}
+                                            </th>
+                                        ))}
+                                    </tr>
+                                </thead>
+                                <TableRows block={block} context={context} />
+                            </table>
+                        )
+                    }
+                    case 'chart': {
+                        const id = blockKey(block, key)
+                        return context.chartMarkup[id] ? <ChartSlot key={key} id={id} /> : <Chart key={key} chart={block.chart} />
+                    }
+                    case 'card':
+                        return (
+                            <div key={key} className={['block-card', block.tone !== 'neutral' && block.tone].filter(Boolean).join(' ')}>
+                                {block.title && <h4>{block.title}</h4>}
+                                <BlockList blocks={block.blocks ?? []} prefix={key} context={context} footnotes={footnotes} />
+                            </div>
+                        )
+                    case 'spacer':
+                        return <div key={key} className="block-spacer" style={{ height: block.height != null ? `${block.height}mm` : 'var(--rf-space-xl)' }} aria-hidden />
+                    case 'divider':
+                        return (
+                            <hr
+                                key={key}
+                                className="block-divider"
+                                style={{
+                                    borderTop: `${block.thickness ?? 0.75}pt ${block.style ?? 'solid'} ${block.color ?? '#e2e8f0'}`,
+                                    margin: block.spacing != null ? `${block.spacing}mm 0` : 'var(--rf-space-l) 0',
+                                }}
+                            />
+                        )
+                    case 'image':
+                        return <ReportImage key={key} image={block.image} className="section-image" />
+                    case 'checklist':
+                        return (
+                            <div key={key} className={block.strikeCompleted ? 'block-checklist strike' : 'block-checklist'}>
+                                {block.title && <h4>{block.title}</h4>}
+                                <ul>
+                                    {(block.items ?? []).map((item, itemIdx) => (
+                                        <li key={itemIdx} className={item.checked ? 'done' : undefined}>
+                                            <CheckBox checked={item.checked} />
+                                            <span>
+                                                <RichText text={item.text} footnoteStart={footnotes.get(key)?.[itemIdx]} />
+                                            </span>
+                                        </li>
+                                    ))}
+                                </ul>
+                                <Footnotes texts={(block.items ?? []).map((item) => item.text)} start={footnotes.get(key)?.[0]} />
+                            </div>
+                        )
+                    case 'contactCard':
+                        return <ContactCard key={key} block={block} />
+                    case 'legal':
+                        // Collected by blockLegal and set after everything else in the report.
+                        return null
+                    case 'quote':
+                        return (
+                            <React.Fragment key={key}>
+                                <PullQuote text={block.text} attribution={block.attribution} role={block.role} footnoteStart={footnotes.get(key)?.[0]} />
+                                <Footnotes texts={[block.text]} start={footnotes.get(key)?.[0]} />
+                            </React.Fragment>
+                        )
+                    case 'timeline':
+                        return (
+                            <div key={key} className="block-timeline">
+                                {block.title && <h4>{block.title}</h4>}
+                                <ol>
+                                    {(block.milestones ?? []).map((milestone, itemIdx) => (
+                                        <li key={itemIdx} className={milestone.status}>
+                                            <div className="milestone-date">{milestone.date}</div>
+                                            <div>
+                                                <div className="milestone-title">{milestone.title}</div>
+                                                {milestone.description && <p className="milestone-description">{milestone.description}</p>}
+                                            </div>
+                                        </li>
+                                    ))}
+                                </ol>
+                            </div>
+                        )
+                    default:
+                        // Unknown types are skipped rather than failing the report, so newer payloads degrade gracefully.
+                        return null
+                }
+            })}
+        </>
+    )
+}
+
+export function Blocks({ blocks, context }: { blocks: Block[]; context: BlockContext }) {
+    // One numbering runs through every cited text in document order, cards included, so it never restarts at a block.
+    const cited = flatten(blocks).flatMap(({ block, key }) => footnoteTexts(block).map((text) => ({ key, text })))
+    const starts = footnoteOffsets(cited.map(({ text }) => text))
+    const footnotes = new Map<string, number[]>()
+    cited.forEach(({ key }, idx) => footnotes.set(key, [...(footnotes.get(key) ?? []), starts[idx]!]))
+    return <BlockList blocks={blocks} prefix="blocks" context={context} footnotes={footnotes} />
+}
@@ -0,0 +1,67 @@
+import React from 'react'
+
+const BAND_SHADES = ['#cbd5e1', '#e2e8f0', '#f1f5f9']
+const MEASURE_COLOR = '#1e293b'
+const TARGET_COLOR = '#0f172a'
+
+export type BulletGraphProps = {
+    value: number
+    min?: number
+    max?: number
+    // Lower bounds of the qualitative bands, ascending; the first band starts at `min`.
+    bands?: number[]
+    target?: number
+    height?: number
+    label?: string
+    valueStyle?: React.CSSProperties
+}
+
+const percentOf = (value: number, min: number, max: number) =>
+    max === min ? 0 : Math.min(100, Math.max(0, ((value - min) / (max - min)) * 100))
+
+// Stephen Few's bullet graph: darker bands are worse, the measure is a thin bar and the target a vertical tick.
+export function BulletGraph({ value, min = 0, max = 100, bands = [], target, height = 12, label, valueStyle }: BulletGraphProps) {
+    const starts = [min, ...bands.filter((band) => band > min && band < max)]
+    const shade = (idx: number) => BAND_SHADES[Math.round((idx / Math.max(1, starts.length - 1)) * (BAND_SHADES.length - 1))]
+    return (
+        <div style={{ display: 'flex', alignItems: 'center', gap: 6 }}>
+            <div role="img" aria-label={label} style={{ position: 'relative', flex: 1, height }}>
+                {starts.map((start, idx) => {
+                    const left = percentOf(start, min, max)
+                    const right = percentOf(starts[idx + 1] ?? max, min, max)
+                    return (
+                        <div
+                            key={start}
+                            style={{ position: 'absolute', top: 0, bottom: 0, left: `${left}%`, width: `${right - left}%`, background: shade(idx) }}
+                        />
+                    )
+                })}
+                <div
+                    className="bullet-measure"
+                    style={{
+                        position: 'absolute',
+                        top: '33%',
+                        bottom: '33%',
+                        left: 0,
+                        width: `${percentOf(value, min, max)}%`,
+                        background: MEASURE_COLOR
+                    }}
+                />
+                {target != null && (
+                    <div
+                        className="bullet-target"
+                        style={{
+                            position: 'absolute',
+                            top: '12%',
+                            bottom: '12%',
+                            left: `calc(${percentOf(target, min, max)}% - 1px)`,
+                            width: 2,
+                            background: TARGET_COLOR
+                        }}
+                    />
+                )}
+            </div>
+            <span style={{ fontSize: 10, fontWeight: 600, color: '#111', whiteSpace: 'nowrap', ...valueStyle }}>{label}</span>
+        </div>
+    )
+}
@@ -0,0 +1,76 @@
+import React from 'react'
+import { formatKpiValue } from '../kpi'
+import type { GrowthCategory } from '../types'
+import { Footnotes } from './Footnotes'
+import { GradientBar, ScoreWhisker } from './GradientProgressBar'
+import { ReportImage } from './ReportImage'
+import { RichText } from './RichText'
+import { SectionHeading } from './SectionHeading'
+
+export const CATEGORY_DETAIL_STYLES = `
+    .category-detail{ break-before:page; }
+    .category-detail-score{ position:relative; max-width:220px; margin:var(--rf-space-m) 0 var(--rf-space-l); }
+    .category-detail-meta{ font-size:9px; color:#475569; margin:0 0 8px; }
+    .category-detail-kpis{ width:100%; border-collapse:collapse; margin:0 0 10px; font-size:10px; }
+    .category-detail-kpis th,.category-detail-kpis td{ padding:5px 6px; border-bottom:1px solid #e2e8f0; text-align:right; }
+    .category-detail-kpis th:first-child,.category-detail-kpis td:first-child{ text-align:left; }
+    .category-detail-kpis th{ background:#f8fafc; color:#111; }
+    .category-detail p{ margin:0 0 8px; font-size:10px; line-height:var(--rf-line-height); }
+    .category-link{ color:inherit; text-decoration:none; }
+`
+
+export const categoryAnchor = (category: GrowthCategory) => `category-${category.id}`
+
+type Props = {
+    category: GrowthCategory
+    number?: string
+    running?: string
+    footnoteStart?: number
+}
+
+export function CategoryDetail({ category, number, running, footnoteStart }: Props) {
+    const kpis = category.kpis ?? []
+    return (
+        <section className="category-detail">
+            <SectionHeading as="h3" id={categoryAnchor(category)} number={number} running={running}>
+                {category.name}
+            </SectionHeading>
+            <div className="category-detail-score">
+                <GradientBar value={category.score} gradientString={category.explanation} height={12} trackColor="#e2e8f0" />
+                <ScoreWhisker low={category.scoreLow} high={category.scoreHigh} />
+            </div>
+            <p className="category-detail-meta">
+                Confidence {category.confidence}% · {category.scored} of {category.total} KPIs scored
+            </p>
+            {kpis.length > 0 && (
+                <table className="category-detail-kpis">
+                    <thead>
+                        <tr>
+                            <th>KPI</th>
+                            <th>Value</th>
+                            <th>Practice</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {kpis.map((kpi) => (
+                            <tr key={kpi.name}>
+                                <td>{kpi.name}</td>
+                                <td>{formatKpiValue(kpi, kpi.value)}</td>
+                                <td>{kpi.practice != null ? formatKpiValue(kpi, kpi.practice) : '—'}</td>
+                            </tr>
+                        ))}
+                    </tbody>
+                </table>
+            )}
+            {category.narrative && (
+                <>
+                    <p>
+                        <RichText text={category.narrative} footnoteStart={footnoteStart} />
+                    </p>
+                    <Footnotes texts={[category.narrative]} start={footnoteStart} />
+                </>
+            )}
+            {category.chart && <ReportImage image={category.chart} className="section-image" />}
+        </section>
+    )
+}
@@ -0,0 +1,164 @@
+import React from 'react'
+import { groupCategories, scoreChange } from '../categories'
+import { formatKpiChange, formatKpiValue } from '../kpi'
+import { ruleStyle, rowStyle } from '../rules'
+import type { CategoryTableOptions, ConfidenceDisplay, FormatRule, GrowthCategory, ScoreBand } from '../types'
+import { categoryAnchor } from './CategoryDetail'
+import { DotScale } from './DotScale'
+import { GradientBar, ScoreWhisker } from './GradientProgressBar'
+import { Icon } from './Icon'
+import { BandBar } from './ScoreLegend'
+
+export const CATEGORY_TABLE_STYLES = `
+    .category-table{ width:100%; border-collapse:collapse; margin:var(--rf-space-m) 0; font-size:10px; }
+    .category-table th,.category-table td{ text-align:center; padding:6px; border-bottom:1px solid #e2e8f0; }
+    .category-table th{ font-size:10px; color:#111; background:#f8fafc; }
+    .category-table td:first-child{ font-weight:600; color:#111; text-align:left; }
+    .category-name{ color:#111; font-weight:400; }
+    .category-initial{ font-weight:700; color:#0f172a; }
+    .category-note{ font-size:9px; color:#475569; margin:4px 0 8px; text-align: center; }
+    .category-score{ min-width:130px; position:relative; }
+    .category-table tbody.category-group-body{ break-inside:avoid; }
+    .category-table .category-group th{ text-align:left; background:#e2e8f0; color:#0f172a; font-size:9px; letter-spacing:0.04em; text-transform:uppercase; padding:4px 6px; }
+    .category-table .category-subtotal td{ background:#f8fafc; border-bottom:1px solid #cbd5e1; font-weight:600; }
+    .category-table .category-subtotal td:first-child{ font-weight:600; color:#475569; }
+    .category-change{ font-variant-numeric:tabular-nums; white-space:nowrap; font-weight:600; }
+    .category-change.up{ color:#047857; }
+    .category-change.down{ color:#b91c1c; }
+    .category-change.flat{ color:#475569; }
+    .category-table.striped tbody tr.even{ background:#f8fafc; }
+    .category-table .category-kpi td{ padding:3px 6px; font-size:9px; color:#475569; border-bottom:1px dotted #e2e8f0; }
+    .category-table .category-kpi td:first-child{ padding-left:18px; font-weight:400; color:#475569; }
+    .category-table tr:has(+ .category-kpi){ break-after:avoid; }
+`
+
+type Props = {
+    categories: GrowthCategory[]
+    options?: CategoryTableOptions
+    rules: FormatRule[]
+    // Set to print band labels after the score bars.
+    bands?: ScoreBand[]
+}
+
+const ScoreBar = ({ category, style }: { category: Pick<GrowthCategory, 'score' | 'explanation' | 'scoreLow' | 'scoreHigh'>; style?: React.CSSProperties }) => (
+    <div className="category-score">
+        <GradientBar value={category.score} gradientString={category.explanation} height={10} trackColor="#e2e8f0" valueStyle={style} />
+        <ScoreWhisker low={category.scoreLow} high={category.scoreHigh} />
+    </div>
+)
+
+// Rule colors apply to the dots too, since the cell's `color` drives the SVG through currentColor.
+const Confidence = ({ value, display }: { value: number; display?: ConfidenceDisplay }) =>
+    display === 'dots' ? <DotScale value={value} color="currentColor" /> : <>{value}%</>
+
+const Change = ({ value }: { value?: number }) => {
+    if (value == null) return <td className="category-change">—</td>
+    const direction = value > 0 ? 'up' : value < 0 ? 'down' : 'flat'
+    return (
+        <td className={`category-change ${direction}`}>
+            {value > 0 ? `+${value}` : value < 0 ? `−${Math.abs(value)}` : '±0'}
+        </td>
+    )
+}
+
+// Detail rows under a category: the KPI value sits in the score column and its change, if known, in the change column.
+const KpiRows = ({ category, showChange, rules }: { category: GrowthCategory; showChange: boolean; rules: FormatRule[] }) => (
+    <>
+        {(category.kpis ?? []).map((kpi) => {
+            const change = kpi.previousValue == null ? undefined : Math.round((kpi.value - kpi.previousValue) * 10) / 10
+            return (
+                <tr key={kpi.name} className="category-kpi">
+                    <td>{kpi.name}</td>
+                    <td style={ruleStyle(rules, 'kpi', 'value', kpi)}>{formatKpiValue(kpi, kpi.value)}</td>
+                    {showChange && <td>{change == null ? '—' : formatKpiChange(kpi, change)}</td>}
+                    <td colSpan={2} />
+                </tr>
+            )
+        })}
+    </>
+)
+
+const CategoryName = ({ category, linked }: { category: GrowthCategory; linked?: boolean }) => {
+    const name = (
+        <span className="category-name">
+            <span className="category-initial">{category.name.charAt(0)}</span>
+            {category.name.slice(1)}
+        </span>
+    )
+    return linked ? (
+        <a className="category-link" href={`#${categoryAnchor(category)}`}>
+            {name}
+        </a>
+    ) : (
+        name
+    )
+}
+
+// Each group is its own <tbody> so a band, its rows and the subtotal stay on one page; an ungrouped table breaks freely.
+// Stripes count categories within their group, so group headers, KPI rows and subtotals don't shift them.
+export function CategoryTable({ categories, options, rules, bands }: Props) {
+    const groups = groupCategories(categories, options)
+    const showChange = categories.some((category) => category.previousScore != null)
+    return (
+        <table className={options?.striped ? 'category-table striped' : 'category-table'}>
+            <thead>
+                <tr>
+                    <th>Category</th>
+                    <th>Score</th>
+                    {showChange && <th>Change</th>}
+                    <th>Confidence</th>
+                    <th>KPIs Scored</th>
+                </tr>
+            </thead>
+            {groups.map((group) => (
+                <tbody key={group.name ?? 'all'} className={group.name ? 'category-group-body' : undefined}>
+                    {group.name && (
+                        <tr className="category-group">
+                            <th colSpan={showChange ? 5 : 4} scope="rowgroup">
+                                {group.name}
+                            </th>
+                        </tr>
+                    )}
+                    {group.categories.map((category, idx) => (
+                        <React.Fragment key={category.id}>
+                            <tr className={options?.striped && idx % 2 ? 'even' : undefined} style={rowStyle(rules, 'category', category)}>
+                                <td>
+                                    {category.icon && <Icon name={category.icon} size={10} style={{ marginRight: 4 }} />}
+                                    <CategoryName category={category} linked={options?.detailPages} />
+                                </td>
+                                <td>
+                                    <BandBar value={category.score} bands={bands}>
+                                        <ScoreBar category={category} style={ruleStyle(rules, 'category', 'score', category)} />
+                                    </BandBar>
+                                </td>
+                                {showChange && <Change value={scoreChange(category.score, category.previousScore)} />}
+                                <td style={ruleStyle(rules, 'category', 'confidence', category)}>
+                                    <Confidence value={category.confidence} display={options?.confidence} />
+                                </td>
+                                <td style={ruleStyle(rules, 'category', 'scored', category)}>
+                                    {category.scored} of {category.total}
+                                </td>
+                            </tr>
+                            {options?.kpiRows && <KpiRows category={category} showChange={showChange} rules={rules} />}
+                        </React.Fragment>
+                    ))}
+                    {group.subtotal && (
+                        <tr className="category-subtotal">
+                            <td>{group.name} average</td>
+                            <td>
+                                <ScoreBar category={group.subtotal} />
+                            </td>
+                            {showChange && <Change value={scoreChange(group.subtotal.score, group.subtotal.previousScore)} />}
+                            <td>
+                                <Confidence value={group.subtotal.confidence} display={options?.confidence} />
+                            </td>
+                            <td>
+                                {group.subtotal.scored} of {group.subtotal.total}
+                            </td>
+                        </tr>
+                    )}
+                </tbody>
+            ))}
+        </table>
+    )
+}
@@ -0,0 +1,163 @@
+import React from 'react'
+import { type LabelCandidate, placeLabels } from '../chartLabels'
+import { DEFAULT_MAX_CHART_POINTS, downsample } from '../downsample'
+import type { ChartAnnotation, ChartSpec } from '../types'
+
+export const CHART_STYLES = `
+    .chart{ display:block; width:100%; height:auto; margin:var(--rf-space-m) 0; break-inside:avoid; }
+    .chart text{ font-family:inherit; font-size:8px; fill:#475569; }
+    .chart-legend{ display:flex; gap:10px; margin:0 0 4px; padding:0; list-style:none; font-size:8.5px; color:#475569; }
+    .chart-legend span{ display:inline-block; width:8px; height:8px; border-radius:2px; margin-right:3px; vertical-align:middle; }
+`
+
+const WIDTH = 480
+const HEIGHT = 180
+// The right margin is wide enough for annotation labels.
+const PLOT = { left: 34, right: WIDTH - 72, top: 10, bottom: HEIGHT - 22 }
+const Y_TICKS = 4
+const MAX_X_LABELS = 12
+const SERIES_COLORS = ['#0f766e', '#2563eb', '#d97706', '#7c3aed', '#dc2626']
+const DASH: Record<NonNullable<ChartAnnotation['style']>, string | undefined> = {
+    solid: undefined,
+    dashed: '5 3',
+    dotted: '1.5 2.5',
+}
+
+const tickFormatter = new Intl.NumberFormat('en-US', { notation: 'compact', maximumFractionDigits: 1 })
+
+type Props = {
+    chart: ChartSpec
+}
+
+const explicit = (value: number | undefined) => (Number.isFinite(value) ? value : undefined)
+
+// Annotation values are folded into the y-range so a goal above the data is still drawn inside the plot. An empty or
+// inverted explicit range would divide by zero or flip the chart, so the data range is used instead.
+const yDomain = (chart: ChartSpec) => {
+    const values = [
+        ...chart.series.flatMap((series) => series.points.map((point) => point.y)),
+        ...(chart.annotations ?? []).map((annotation) => annotation.value),
+    ]
+    const dataMin = Math.min(0, ...values)
+    const dataMax = Math.max(dataMin + 1, ...values)
+    const min = explicit(chart.yMin) ?? dataMin
+    const max = explicit(chart.yMax) ?? Math.max(min + 1, ...values)
+    return max > min ? { min, max } : { min: dataMin, max: dataMax }
+}
+
+export function Chart({ chart: spec }: Props) {
+    // Thousands of daily points can't be told apart at print size, so long series are thinned before layout.
+    const target = spec.maxPoints ?? DEFAULT_MAX_CHART_POINTS
+    const chart = { ...spec, series: spec.series.map((series) => ({ ...series, points: downsample(series.points, target) })) }
+    const labels = [...new Set(chart.series.flatMap((series) => series.points.map((point) => point.x)))]
+    const { min, max } = yDomain(chart)
+    const plotWidth = PLOT.right - PLOT.left
+    const slot = plotWidth / Math.max(1, labels.length)
+    const x = (label: string) => PLOT.left + slot * (labels.indexOf(label) + 0.5)
+    // Values outside an explicit range are pinned to the edge of the plot instead of drawn over the labels.
+    const y = (value: number) => PLOT.bottom - ((Math.min(max, Math.max(min, value)) - min) / (max - min)) * (PLOT.bottom - PLOT.top)
+    const ticks = Array.from({ length: Y_TICKS + 1 }, (_, idx) => min + ((max - min) * idx) / Y_TICKS)
+    const labelEvery = Math.ceil(labels.length / MAX_X_LABELS)
+    const barWidth = (slot * 0.7) / chart.series.length
+    const color = (idx: number) => chart.series[idx]?.color ?? SERIES_COLORS[idx % SERIES_COLORS.length]
+    const baseline = y(Math.max(min, 0))
+    const barX = (label: string, idx: number) => x(label) - (slot * 0.7) / 2 + barWidth * idx
+
+    const candidates: LabelCandidate[] = chart.dataLabels
+        ? chart.series.flatMap((series, idx) =>
+              series.points.map((point) => ({
+                  text: tickFormatter.format(point.y),
+                  x: chart.type === 'bar' ? barX(point.x, idx) + barWidth / 2 : x(point.x),
+                  y: Math.min(y(point.y), baseline),
+                  bar: chart.type === 'bar' ? { width: barWidth, height: Math.abs(y(point.y) - baseline) } : undefined,
+              }))
+          )
+        : []
+    const dataLabels = placeLabels(candidates, { left: PLOT.left, right: PLOT.right, top: 0, bottom: PLOT.bottom })
+
+    return (
+        <figure style={{ margin: 0 }}>
+            {chart.series.length > 1 && (
+                <ul className="chart-legend">
+                    {chart.series.map((series, idx) => (
+                        <li key={series.name}>
+                            <span style={{ background: color(idx) }} />
+                            {series.name}
+                        </li>
+                    ))}
+                </ul>
+            )}
+            <svg className="chart" viewBox={`0 0 ${WIDTH} ${HEIGHT}`} role="img" aria-label={chart.title}>
+                {ticks.map((tick) => (
+                    <g key={tick}>
+                        <line x1={PLOT.left} x2={PLOT.right} y1={y(tick)} y2={y(tick)} stroke="#e2e8f0" strokeWidth={0.5} />
+                        <text x={PLOT.left - 4} y={y(tick)} textAnchor="end" dominantBaseline="middle">
+                            {tickFormatter.format(tick)}
+                        </text>
+                    </g>
+                ))}
+                {labels.map((label, idx) =>
+                    idx % labelEvery === 0 ? (
+                        <text key={label} x={x(label)} y={PLOT.bottom + 12} textAnchor="middle">
+                            {label}
+                        </text>
+                    ) : null
+                )}
+                {chart.series.map((series, idx) =>
+                    chart.type === 'bar' ? (
+                        <g key={series.name} fill={color(idx)}>
+                            {series.points.map((point) => (
+                                <rect
+                                    key={point.x}
+                                    x={barX(point.x, idx)}
+                                    y={Math.min(y(point.y), baseline)}
+                                    width={barWidth}
+                                    height={Math.abs(y(point.y) - baseline)}
+                                />
+                            ))}
+                        </g>
+                    ) : (
+                        <polyline
+                            key={series.name}
+                            points={series.points.map((point) => `${x(point.x)},${y(point.y)}`).join(' ')}
+                            fill="none"
+                            stroke={color(idx)}
+                            strokeWidth={1.5}
+                            strokeLinejoin="round"
+                        />
+                    )
+                )}
+                {(chart.annotations ?? []).map((annotation) => (
+                    <g key={`${annotation.label}-${annotation.value}`} className="chart-annotation">
+                        <line
+                            x1={PLOT.left}
+                            x2={PLOT.right}
+                            y1={y(annotation.value)}
+                            y2={y(annotation.value)}
+                            stroke={annotation.color ?? '#0f172a'}
+                            strokeWidth={1}
+                            strokeDasharray={DASH[annotation.style ?? 'dashed']}
+                        />
+                        <text x={PLOT.right + 4} y={y(annotation.value)} dominantBaseline="middle" style={{ fill: annotation.color ?? '#0f172a' }}>
+                            {annotation.label}
+                        </text>
+                    </g>
+                ))}
+                {dataLabels.map((label) => (
+                    <text
+                        key={`${label.x}-${label.y}-${label.text}`}
+                        className="chart-data-label"
+                        x={label.x}
+                        y={label.y}
+                        textAnchor={label.anchor}
+                        transform={label.rotate ? `rotate(-90 ${label.x} ${label.y})` : undefined}
+                        style={label.inside ? { fill: '#fff', fontWeight: 600 } : { fill: '#111' }}
+                    >
+                        {label.text}
+                    </text>
+                ))}
+                <line x1={PLOT.left} x2={PLOT.right} y1={PLOT.bottom} y2={PLOT.bottom} stroke="#94a3b8" strokeWidth={0.75} />
+            </svg>
+        </figure>
+    )
+}
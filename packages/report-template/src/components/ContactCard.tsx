@@ -0,0 +1,55 @@
+import React from 'react'
+import { ReportImage } from './ReportImage'
+import type { ContactCardBlock } from '../types'
+
+export const CONTACT_CARD_STYLES = `
+    .contact-card{ display:flex; align-items:center; gap:12px; margin:var(--rf-space-m) 0; padding:10px 12px; border:1px solid #e2e8f0; border-radius:8px; background:#f8fafc; break-inside:avoid; }
+    .contact-photo{ flex-shrink:0; border-radius:50%; overflow:hidden; }
+    .contact-name{ margin:0; font-size:12px; font-weight:700; color:var(--rf-brand-primary); }
+    .contact-title{ margin:0 0 var(--rf-space-xs); font-size:9.5px; color:#475569; }
+    .contact-details{ list-style:none; margin:0; padding:0; font-size:9.5px; }
+    .contact-details li{ margin:1px 0; }
+    .contact-details .label{ display:inline-block; min-width:44px; color:#64748b; }
+    .contact-details a{ color:#0369a1; text-decoration:none; }
+`
+
+const EXTENSION = /\s*(?:ext(?:ension)?\.?|x|#)\s*(\d+)\s*$/i
+
+// Links keep the number's leading + and digits only, and a trailing extension becomes the RFC 3966 ";ext=" parameter,
+// so "(555) 010-2030 ext. 4" dials 5550102030 and then extension 4.
+const telHref = (phone: string) => {
+    const extension = EXTENSION.exec(phone)
+    const number = extension ? phone.slice(0, extension.index) : phone
+    return `tel:${number.trim().startsWith('+') ? '+' : ''}${number.replace(/\D/g, '')}${extension ? `;ext=${extension[1]}` : ''}`
+}
+
+const webHref = (website: string) => (/^https?:\/\//i.test(website) ? website : `https://${website}`)
+
+const webLabel = (website: string) => website.replace(/^https?:\/\//i, '').replace(/\/$/, '')
+
+export function ContactCard({ block }: { block: ContactCardBlock }) {
+    const details = [
+        block.phone && { label: 'Phone', href: telHref(block.phone), text: block.phone },
+        block.email && { label: 'Email', href: `mailto:${block.email}`, text: block.email },
+        block.website && { label: 'Web', href: webHref(block.website), text: webLabel(block.website) },
+    ].filter(Boolean) as { label: string; href: string; text: string }[]
+    return (
+        <div className="contact-card">
+            {block.photo && <ReportImage image={{ width: 56, height: 56, fit: 'cover', ...block.photo }} className="contact-photo" />}
+            <div>
+                <p className="contact-name">{block.name}</p>
+                {block.title && <p className="contact-title">{block.title}</p>}
+                {details.length > 0 && (
+                    <ul className="contact-details">
+                        {details.map((detail) => (
+                            <li key={detail.label}>
+                                <span className="label">{detail.label}</span>
+                                <a href={detail.href}>{detail.text}</a>
+                            </li>
+                        ))}
+                    </ul>
+                )}
+            </div>
+        </div>
+    )
+}
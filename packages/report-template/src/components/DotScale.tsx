@@ -0,0 +1,41 @@
+import React from 'react'
+
+export type DotScaleProps = {
+    value: number
+    max?: number
+    dots?: number
+    size?: number
+    color?: string
+    style?: React.CSSProperties
+}
+
+const GAP = 2
+
+// Filled/unfilled circles for a 0–max value; the exact number stays available to screen readers and text extraction.
+export function DotScale({ value, max = 100, dots = 5, size = 7, color = '#0f172a', style }: DotScaleProps) {
+    const filled = Math.max(0, Math.min(dots, Math.round((value / max) * dots)))
+    const radius = size / 2
+    const width = dots * size + (dots - 1) * GAP
+    return (
+        <svg
+            viewBox={`0 0 ${width} ${size}`}
+            width={width}
+            height={size}
+            role="img"
+            aria-label={`${value}%`}
+            style={{ display: 'inline-block', verticalAlign: 'middle', ...style }}
+        >
+            {Array.from({ length: dots }, (_, index) => (
+                <circle
+                    key={index}
+                    cx={radius + index * (size + GAP)}
+                    cy={radius}
+                    r={radius - 0.5}
+                    fill={index < filled ? color : 'none'}
+                    stroke={color}
+                    strokeWidth={1}
+                />
+            ))}
+        </svg>
+    )
+}
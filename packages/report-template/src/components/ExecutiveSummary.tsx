@@ -0,0 +1,71 @@
+import React from 'react'
+import { formatKpiValue, kpiFraction } from '../kpi'
+import { plainText } from '../richText'
+import type { GrowthCategory, KPI, ReportSection } from '../types'
+import { SectionHeading } from './SectionHeading'
+
+const EXCERPT_LENGTH = 180
+
+type Props = {
+    kpis: KPI[]
+    growthCategories: GrowthCategory[]
+    questionSections: ReportSection[]
+    number?: string
+    running?: string
+}
+
+const excerpt = (source: string) => {
+    const text = plainText(source)
+    const firstSentence = text.match(/^.*?[.!?](\s|$)/)?.[0]?.trim() ?? text.trim()
+    if (firstSentence.length <= EXCERPT_LENGTH) return firstSentence
+    return `${firstSentence.slice(0, EXCERPT_LENGTH).replace(/\s+\S*$/, '')}…`
+}
+
+export function ExecutiveSummary({ kpis, growthCategories, questionSections, number, running }: Props) {
+    const topKpis = [...kpis].sort((a, b) => kpiFraction(b) - kpiFraction(a)).slice(0, 3)
+    const lowestCategory = growthCategories.reduce<GrowthCategory | undefined>(
+        (lowest, category) => (!lowest || category.score < lowest.score ? category : lowest),
+        undefined
+    )
+    const excerpts = questionSections.filter((section) => section.text.trim().length > 0)
+
+    return (
+        <section className="executive-summary" id="executive-summary">
+            <SectionHeading as="h2" number={number} running={running}>
+                Executive Summary
+            </SectionHeading>
+            {topKpis.length > 0 && (
+                <div className="executive-block">
+                    <h3>Top Scores</h3>
+                    <ul className="executive-kpis">
+                        {topKpis.map((kpi) => (
+                            <li key={kpi.name}>
+                                <span>{kpi.name}</span>
+                                <strong>{formatKpiValue(kpi, kpi.value)}</strong>
+                            </li>
+                        ))}
+                    </ul>
+                </div>
+            )}
+            {lowestCategory && (
+                <div className="executive-block executive-focus">
+                    <h3>Biggest Opportunity</h3>
+                    <p>
+                        <strong>{lowestCategory.name}</strong> scored {lowestCategory.score}% ({lowestCategory.scored} of{' '}
+                        {lowestCategory.total} KPIs scored).
+                    </p>
+                </div>
+            )}
+            {excerpts.length > 0 && (
+                <div className="executive-block">
+                    <h3>Key Takeaways</h3>
+                    {excerpts.map((section) => (
+                        <p className="executive-excerpt" key={section.id}>
+                            <strong>{section.title}</strong> {excerpt(section.text)}
+                        </p>
+                    ))}
+                </div>
+            )}
+        </section>
+    )
+}
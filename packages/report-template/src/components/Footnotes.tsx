@@ -0,0 +1,28 @@
+import React from 'react'
+import { footnotesIn } from '../richText'
+import { RichText } from './RichText'
+
+export const FOOTNOTE_STYLES = `
+    .footnotes{ margin:6px 0 0; padding:3px 0 0 14px; border-top:0.5pt solid #94a3b8; font-size:8px; color:#475569; line-height:1.3; break-inside:avoid; }
+    .footnotes li{ margin:0; }
+`
+
+type Props = {
+    texts: string[]
+    start?: number
+}
+
+// Chromium has no page floats, so footnotes are kept inside the unbreakable block that cites them to stay on the same page.
+export function Footnotes({ texts, start = 1 }: Props) {
+    const notes = texts.flatMap(footnotesIn)
+    if (!notes.length) return null
+    return (
+        <ol className="footnotes" start={start}>
+            {notes.map((note, idx) => (
+                <li key={idx}>
+                    <RichText text={note} />
+                </li>
+            ))}
+        </ol>
+    )
+}
@@ -0,0 +1,39 @@
+import React from 'react'
+import type { FormField } from '../types'
+
+export const FORM_FIELD_STYLES = `
+    .form-fields{ margin:var(--rf-space-xl) 0; padding:8px 10px; border-radius:8px; background:#f8fafc; page-break-inside:avoid; }
+    .form-fields h3{ margin:0 0 8px; }
+    .form-field{ margin:0 0 8px; font-size:10px; color:#111; }
+    .form-field label{ display:block; margin:0 0 3px; font-weight:600; }
+    .form-field-box{ border:1px solid #cbd5e1; border-radius:4px; background:#fff; height:18px; }
+    .form-field-multiline .form-field-box{ height:72px; }
+    .form-field-checkbox{ display:flex; align-items:center; gap:6px; }
+    .form-field-checkbox label{ order:1; margin:0; font-weight:400; }
+    .form-field-checkbox .form-field-box{ width:11px; height:11px; border-radius:2px; }
+`
+
+type Props = {
+    fields: FormField[]
+}
+
+// The boxes are only placeholders: the PDF service measures them after pagination and lays real AcroForm widgets on top.
+export function FormFields({ fields }: Props) {
+    return (
+        <section className="form-fields" id="follow-up-notes">
+            <h3>Follow-up Notes</h3>
+            {fields.map((field) => (
+                <div className={`form-field form-field-${field.type ?? 'text'}`} key={field.id}>
+                    <label htmlFor={`form-${field.id}`}>{field.label}</label>
+                    <div
+                        className="form-field-box"
+                        id={`form-${field.id}`}
+                        data-form-field={field.id}
+                        data-form-type={field.type ?? 'text'}
+                        data-form-value={field.type === 'checkbox' ? (field.checked ? 'on' : undefined) : field.value}
+                    />
+                </div>
+            ))}
+        </section>
+    )
+}
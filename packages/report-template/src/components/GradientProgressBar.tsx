@@ -4,6 +4,8 @@ import gradientFill from '../assets/gradient-fill.png'
 const TRACK_BORDER = 'rgba(15,23,42,0.08)'
 const TRACK_BACKGROUND = '#f6f8fb'
 const TRACK_SHADOW = 'inset 0 1px 1px rgba(15,23,42,0.08)'
+const RANGE_BACKGROUND = 'rgba(100,116,139,0.22)'
+const RANGE_OVERHANG = 3
 const FULL_THRESHOLD = 99.95
 const CLIP_PAD_START = -0.15
 const CLIP_PAD_END = 0.1
@@ -16,6 +18,9 @@ export type GradientProgressBarProps = {
     height?: number
     showHeader?: boolean
     gradientString?: string
+    valueStyle?: React.CSSProperties
+    // Low/high observed values, drawn as a light band that shows around the bar.
+    range?: [number, number]
 }
 
 export type GradientBarProps = GradientProgressBarProps & {
@@ -49,7 +54,8 @@ export function GradientBar({
     showValue = true,
     valueColor = '#111',
     'aria-label': ariaLabel,
-    gradientString
+    gradientString,
+    valueStyle
 }: GradientBarProps) {
     const percent = getPercent(value, min, max)
     const fillPercent = Math.min(100, Math.max(0, Number((percent * 100).toFixed(4))))
@@ -88,7 +94,7 @@ export function GradientBar({
                 overflow: 'hidden'
             }}
         >
-            {hasFill && <div style={gradientStyle} />}
+            {hasFill && <div className="gradient-fill" style={gradientStyle} />}
             {showValue && (
                 <span
                     style={{
@@ -100,7 +106,8 @@ export function GradientBar({
                         fontWeight: 600,
                         color: valueColor,
                         whiteSpace: 'nowrap',
-                        pointerEvents: 'none'
+                        pointerEvents: 'none',
+                        ...valueStyle
                     }}
                 >
                     {gradientString ? gradientString : `${percentValue}%`}
@@ -117,16 +124,73 @@ export function GradientProgressBar({
     max = 100,
     height = 16,
     showHeader,
-    gradientString
+    gradientString,
+    valueStyle,
+    range
 }: GradientProgressBarProps) {
     const headerVisible = showHeader ?? Boolean(label)
+    const [low, high] = range ? [Math.min(...range), Math.max(...range)] : [0, 0]
+    const rangeLeft = getPercent(low, min, max) * 100
+    const rangeWidth = getPercent(high, min, max) * 100 - rangeLeft
 
     return (
         <div style={{ display: 'flex', flexDirection: 'column', gap: 6 }}>
             {headerVisible && (
                 <div style={{ display: 'flex', justifyContent: 'space-between', fontSize: 12, color: '#555' }}>{label}</div>
             )}
-            <GradientBar value={value} min={min} max={max} height={height} aria-label={label} gradientString={gradientString} />
+            <div style={{ position: 'relative' }}>
+                {range && (
+                    <div
+                        className="kpi-range"
+                        style={{
+                            position: 'absolute',
+                            top: -RANGE_OVERHANG,
+                            bottom: -RANGE_OVERHANG,
+                            left: `${rangeLeft}%`,
+                            width: `${rangeWidth}%`,
+                            borderRadius: 2,
+                            background: RANGE_BACKGROUND
+                        }}
+                    />
+                )}
+                <div style={{ position: 'relative' }}>
+                    <GradientBar value={value} min={min} max={max} height={height} aria-label={label} gradientString={gradientString} valueStyle={valueStyle} />
+                </div>
+            </div>
         </div>
     )
 }
+
+const WHISKER_COLOR = 'rgba(15,23,42,0.55)'
+const WHISKER_CAP = 8
+
+// Marks the uncertainty around a score. Sits beside the bar in a positioned parent rather than inside it, since the bar
+// clips its contents to the rounded track.
+export function ScoreWhisker({ low, high, min = 0, max = 100 }: { low?: number; high?: number; min?: number; max?: number }) {
+    if (low == null || high == null) return null
+    const percent = (bound: number) => Math.round(getPercent(bound, min, max) * 10000) / 100
+    const [from, to] = [percent(low), percent(high)].sort((a, b) => a - b) as [number, number]
+    const cap = (side: 'left' | 'right'): React.CSSProperties => ({
+        position: 'absolute',
+        [side]: 0,
+        top: -WHISKER_CAP / 2,
+        height: WHISKER_CAP,
+        borderLeft: `1px solid ${WHISKER_COLOR}`
+    })
+    return (
+        <span
+            className="score-whisker"
+            aria-hidden="true"
+            style={{
+                position: 'absolute',
+                top: '50%',
+                left: `${from}%`,
+                width: `${Math.round((to - from) * 100) / 100}%`,
+                borderTop: `1px solid ${WHISKER_COLOR}`
+            }}
+        >
+            <span style={cap('left')} />
+            <span style={cap('right')} />
+        </span>
+    )
+}
@@ -0,0 +1,34 @@
+import React from 'react'
+import type { IconName } from '../types'
+
+const ICON_PATHS: Record<IconName, string> = {
+    check: 'M9 16.2 4.8 12l-1.4 1.4L9 19 21 7l-1.4-1.4z',
+    warning: 'M1 21h22L12 2 1 21zm12-3h-2v-2h2v2zm0-4h-2v-4h2v4z',
+    'arrow-up': 'M4 12l1.41 1.41L11 7.83V20h2V7.83l5.58 5.59L20 12l-8-8-8 8z',
+    'arrow-down': 'M20 12l-1.41-1.41L13 16.17V4h-2v12.17l-5.58-5.59L4 12l8 8 8-8z',
+    'arrow-right': 'M12 4l-1.41 1.41L16.17 11H4v2h12.17l-5.58 5.59L12 20l8-8z',
+    star: 'M12 17.27 18.18 21l-1.64-7.03L22 9.24l-7.19-.61L12 2 9.19 8.63 2 9.24l5.46 4.73L5.82 21z',
+}
+
+export type IconProps = {
+    name: IconName
+    size?: number
+    color?: string
+    style?: React.CSSProperties
+}
+
+export function Icon({ name, size = 12, color = 'currentColor', style }: IconProps) {
+    const path = ICON_PATHS[name]
+    if (!path) return null
+    return (
+        <svg
+            viewBox="0 0 24 24"
+            width={size}
+            height={size}
+            aria-hidden="true"
+            style={{ display: 'inline-block', verticalAlign: 'middle', flexShrink: 0, ...style }}
+        >
+            <path d={path} fill={color} />
+        </svg>
+    )
+}
@@ -0,0 +1,77 @@
+import React from 'react'
+import { BulletGraph } from './BulletGraph'
+import { GradientProgressBar } from './GradientProgressBar'
+import { BandBar, DEFAULT_SCORE_BANDS } from './ScoreLegend'
+import { formatKpiChange, formatKpiValue, kpiScale, percentileCaption } from '../kpi'
+import { ruleStyle } from '../rules'
+import type { FormatRule, KPI, KpiDisplay, ScoreBand } from '../types'
+
+type Props = {
+    kpis: KPI[]
+    display?: KpiDisplay
+    rules: FormatRule[]
+    scoreBands?: ScoreBand[]
+    // Band labels only make sense against the bands' percentage thresholds, so other value types go without.
+    bandLabels?: boolean
+    // Named in the "vs practice" label of KPIs that carry a comparison value.
+    clientName: string
+}
+
+const kpiTrend = (kpi: KPI) => {
+    if (kpi.previousValue == null) return null
+    const change = Math.round((kpi.value - kpi.previousValue) * 10) / 10
+    if (change === 0) return { className: 'flat', arrow: '▶', text: '0' }
+    const text = formatKpiChange(kpi, change)
+    return change > 0 ? { className: 'up', arrow: '▲', text } : { className: 'down', arrow: '▼', text }
+}
+
+export function KpiGrid({ kpis, display, rules, scoreBands, bandLabels, clientName }: Props) {
+    // Percentages share the score bands' thresholds; other scales are split into thirds.
+    const bulletBands = (k: KPI) => {
+        if ((k.valueType ?? 'percent') === 'percent') return (scoreBands ?? DEFAULT_SCORE_BANDS).map((band) => band.min)
+        const { min, max } = kpiScale(k)
+        return [min + (max - min) / 3, min + ((max - min) * 2) / 3]
+    }
+    const label = (k: KPI) =>
+        k.practice ? `${formatKpiValue(k, k.value)} vs ${formatKpiValue(k, k.practice)} (${clientName})` : formatKpiValue(k, k.value)
+
+    return (
+        <div className="kpi">
+            {kpis.map((k) => {
+                const trend = kpiTrend(k)
+                return (
+                    <div className="kpi-card" key={k.name}>
+                        <strong>{k.name}</strong>
+                        {trend && (
+                            <span className={`kpi-delta ${trend.className}`}>
+                                {trend.arrow} {trend.text}
+                            </span>
+                        )}
+                        <BandBar value={k.value} bands={bandLabels && (k.valueType ?? 'percent') === 'percent' && (scoreBands ?? DEFAULT_SCORE_BANDS)}>
+                            {display === 'bullet' ? (
+                                <BulletGraph
+                                    value={k.value}
+                                    {...kpiScale(k)}
+                                    bands={bulletBands(k)}
+                                    target={k.target ?? k.practice}
+                                    label={formatKpiValue(k, k.value)}
+                                    valueStyle={ruleStyle(rules, 'kpi', 'value', k)}
+                                />
+                            ) : (
+                                <GradientProgressBar
+                                    value={k.value}
+                                    {...kpiScale(k)}
+                                    range={k.min != null && k.max != null ? [k.min, k.max] : undefined}
+                                    height={12}
+                                    gradientString={label(k)}
+                                    valueStyle={ruleStyle(rules, 'kpi', 'value', k)}
+                                />
+                            )}
+                        </BandBar>
+                        {k.percentile != null && <p className="kpi-percentile">{percentileCaption(k.percentile)}</p>}
+                    </div>
+                )
+            })}
+        </div>
+    )
+}
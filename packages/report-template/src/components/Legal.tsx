@@ -0,0 +1,29 @@
+import React from 'react'
+
+// Last in the document and unbreakable where it fits, so the whole disclaimer lands on the final page; longer text
+// may only break between clauses. The PDF service appends the tenant profile's own legal text to the same section.
+export const LEGAL_STYLES = `
+    .legal{ margin:var(--rf-space-xl) 0 0; padding-top:var(--rf-space-s); border-top:0.5pt solid #cbd5e1; font-size:7.5px; line-height:1.35; color:#64748b; break-before:avoid; break-inside:avoid; }
+    .legal h4{ margin:0 0 var(--rf-space-xs); font-size:8px; color:#475569; text-transform:uppercase; letter-spacing:0.04em; }
+    .legal p{ margin:0 0 var(--rf-space-xs); text-align:left; break-inside:avoid; orphans:99; widows:99; }
+`
+
+// Blank lines separate clauses.
+export const legalClauses = (text: string) =>
+    text
+        .split(/\n\s*\n/)
+        .map((clause) => clause.replace(/\s+/g, ' ').trim())
+        .filter(Boolean)
+
+export function Legal({ texts, title }: { texts: string[]; title?: string }) {
+    const clauses = texts.flatMap(legalClauses)
+    if (!clauses.length) return null
+    return (
+        <section className="legal" aria-label={title ?? 'Legal'}>
+            {title && <h4>{title}</h4>}
+            {clauses.map((clause, idx) => (
+                <p key={idx}>{clause}</p>
+            ))}
+        </section>
+    )
+}
@@ -0,0 +1,68 @@
+import React from 'react'
+import { DEFAULT_SCORE_BANDS } from './ScoreLegend'
+import { SectionHeading } from './SectionHeading'
+import type { ScoreBand } from '../types'
+
+export const METHODOLOGY_STYLES = `
+    .methodology{ break-before:page; }
+    .methodology h4{ margin:var(--rf-space-l) 0 var(--rf-space-xs); font-size:11px; color:#0f172a; }
+    .methodology p{ margin:0 0 var(--rf-space-s); }
+    .methodology-bands{ margin:0 0 var(--rf-space-s); padding:0 0 0 14px; }
+    .methodology-bands li{ margin:1px 0; }
+    .methodology dl{ display:grid; grid-template-columns:max-content 1fr; gap:3px 12px; margin:0; }
+    .methodology dt{ font-weight:600; }
+    .methodology dd{ margin:0; color:#334155; }
+`
+
+// Maintained here rather than in every payload; the band list follows the report's own score bands.
+const GLOSSARY: [string, string][] = [
+    ['KPI', 'Key performance indicator: a measured result of the practice, such as case acceptance or hygiene reappointment.'],
+    ['Benchmark', 'The value a top-performing practice reaches on the same KPI, drawn as the comparison mark on each bar.'],
+    ['Growth category', 'A group of related KPIs (e.g. Clinical, Operations, Marketing) scored together.'],
+    ['Change', 'The difference from the previous reporting period, in points for percentages and in units otherwise.'],
+]
+
+type Props = {
+    scoreBands?: ScoreBand[]
+    number?: string
+    running?: string
+}
+
+export function Methodology({ scoreBands = DEFAULT_SCORE_BANDS, number, running }: Props) {
+    const bands = [...scoreBands].sort((a, b) => a.min - b.min)
+    return (
+        <section className="methodology">
+            <SectionHeading as="h2" id="methodology" number={number} running={running}>
+                Methodology
+            </SectionHeading>
+            <h4>Scores</h4>
+            <p>
+                Each growth category is scored from 0 to 100. Every KPI in the category is compared with its benchmark, the
+                comparisons are placed on a common scale, and the category score is their average. Higher is better on every
+                scale, including KPIs where a lower raw value is the goal.
+            </p>
+            <ul className="methodology-bands">
+                {bands.map((band, idx) => (
+                    <li key={band.min}>
+                        <strong>{band.label}</strong>: {bands[idx + 1] ? `${band.min} to below ${bands[idx + 1]!.min}` : `${band.min} and above`}
+                    </li>
+                ))}
+            </ul>
+            <h4>Confidence</h4>
+            <p>
+                Confidence shows how much of a score rests on measured data. It rises with the share of the category's KPIs that
+                could be scored (shown as "KPIs scored") and with the completeness of the months behind them. Treat low-confidence
+                scores as directional until more data is available.
+            </p>
+            <h4>Glossary</h4>
+            <dl>
+                {GLOSSARY.map(([term, definition]) => (
+                    <React.Fragment key={term}>
+                        <dt>{term}</dt>
+                        <dd>{definition}</dd>
+                    </React.Fragment>
+                ))}
+            </dl>
+        </section>
+    )
+}
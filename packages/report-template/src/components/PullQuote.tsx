@@ -0,0 +1,40 @@
+import React from 'react'
+import { RichText } from './RichText'
+
+export const PULL_QUOTE_STYLES = `
+    .quote-block{ position:relative; margin:var(--rf-space-l) 0 var(--rf-space-xs); padding:0 var(--rf-space-xl) 0 28px; break-inside:avoid; }
+    .quote-block::before{ content:'\\201C'; position:absolute; left:0; top:-6px; font-family:Georgia, 'Times New Roman', serif; font-size:36px; line-height:1; color:var(--rf-brand-accent); opacity:0.6; }
+    .quote-text{ margin:0; font-style:italic; font-size:11px; color:#111; line-height:1.6; }
+    .quote-text::after{ content:'\\201D'; }
+    .quote-attribution{ margin:var(--rf-space-s) 0 0; font-size:9px; font-weight:600; color:#475569; }
+    .quote-role{ font-weight:400; }
+    .quote-signature{ font-family:"Zapfino"; font-size:9px; margin:var(--rf-space-s) 0 20px 2rem; color:#111; font-weight:300; font-style:normal; white-space:nowrap; display:inline-block; letter-spacing:0.25px; }
+`
+
+type Props = {
+    text: string
+    attribution?: string
+    role?: string
+    // Set the attribution as a handwritten signature instead of a "— name, role" line.
+    signature?: boolean
+    // Quotes in a blocks document take rich-text markup, footnotes numbered from here included.
+    footnoteStart?: number
+}
+
+// Quotation marks are drawn by the stylesheet, so callers pass the quote without quotation marks.
+export function PullQuote({ text, attribution, role, signature, footnoteStart }: Props) {
+    return (
+        <blockquote className="quote-block">
+            <p className="quote-text">{footnoteStart == null ? text : <RichText text={text} footnoteStart={footnoteStart} />}</p>
+            {attribution &&
+                (signature ? (
+                    <span className="quote-signature">{attribution}</span>
+                ) : (
+                    <p className="quote-attribution">
+                        — {attribution}
+                        {role && <span className="quote-role">, {role}</span>}
+                    </p>
+                ))}
+        </blockquote>
+    )
+}
@@ -0,0 +1,53 @@
+import React from 'react'
+import { sanitizeSvg } from '../svg'
+import type { ImageAlign, ReportImage as ReportImageSource } from '../types'
+
+// Raster images are capped so they never print below this resolution; the PDF service applies it once naturalWidth is known.
+export const DEFAULT_IMAGE_DPI = 150
+
+const POSITION_X: Record<ImageAlign, string> = { start: 'left', center: 'center', end: 'right' }
+const POSITION_Y: Record<ImageAlign, string> = { start: 'top', center: 'center', end: 'bottom' }
+
+type Props = {
+    image: ReportImageSource
+    className?: string
+}
+
+export function ReportImage({ image, className: extraClass }: Props) {
+    // The page rules size full-bleed images, since their width depends on the page margins and bleed.
+    const className = image.fullBleed ? ['full-bleed', extraClass].filter(Boolean).join(' ') : extraClass
+    const style: React.CSSProperties = {
+        width: image.fullBleed ? undefined : (image.width ?? (image.height != null ? 'auto' : '100%')),
+        height: image.height ?? 'auto',
+    }
+
+    if (image.svg) {
+        const markup = sanitizeSvg(image.svg)
+        if (!markup) return null
+        return (
+            <span
+                className={['report-image', 'report-image-svg', className].filter(Boolean).join(' ')}
+                role="img"
+                aria-label={image.alt}
+                style={style}
+                dangerouslySetInnerHTML={{ __html: markup }}
+            />
+        )
+    }
+
+    if (!image.src) return null
+    const fit = image.fit ?? 'contain'
+    return (
+        <img
+            className={['report-image', className].filter(Boolean).join(' ')}
+            src={image.src}
+            alt={image.alt ?? ''}
+            data-dpi={image.dpi ?? DEFAULT_IMAGE_DPI}
+            style={{
+                ...style,
+                objectFit: fit === 'stretch' ? 'fill' : fit,
+                objectPosition: `${POSITION_X[image.alignX ?? 'center']} ${POSITION_Y[image.alignY ?? 'center']}`,
+            }}
+        />
+    )
+}
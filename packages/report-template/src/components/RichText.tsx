@@ -0,0 +1,69 @@
+import React from 'react'
+import { type RichMark, parseRichText } from '../richText'
+
+const MARK_CLASS: Record<RichMark, string> = {
+    bold: 'rt-bold',
+    underline: 'rt-underline',
+    strike: 'rt-strike',
+    sup: 'rt-sup',
+    sub: 'rt-sub',
+}
+
+export const RICH_TEXT_STYLES = `
+    .rt-bold{ font-weight:700; }
+    .rt-underline{ text-decoration-line:underline; text-decoration-thickness:0.08em; text-underline-offset:0.15em; }
+    .rt-strike{ text-decoration-line:line-through; text-decoration-thickness:0.08em; }
+    .rt-underline.rt-strike{ text-decoration-line:underline line-through; }
+    .rt-sup,.rt-sub,.rt-footnote-ref{ font-size:0.7em; line-height:0; position:relative; }
+    .rt-sup,.rt-footnote-ref{ vertical-align:baseline; top:-0.45em; }
+    .rt-sub{ vertical-align:baseline; top:0.25em; }
+    .rt-footnote-ref{ margin-left:0.05em; font-weight:600; }
+    .xref{ color:inherit; text-decoration:underline dotted; }
+    .rt-link{ color:#0369a1; text-decoration:underline; text-underline-offset:0.15em; }
+    .xref-labelled::after{ content:" (page " target-counter(attr(href url), page) ")"; }
+    .xref-page::after{ content:" " target-counter(attr(href url), page); }
+`
+
+type Props = {
+    text: string
+    footnoteStart?: number
+}
+
+export function RichText({ text, footnoteStart = 1 }: Props) {
+    let footnoteNumber = footnoteStart
+    return (
+        <>
+            {parseRichText(text).map((span, idx) => {
+                if (span.xref != null) {
+                    return (
+                        <a key={idx} className={span.text ? 'xref xref-labelled' : 'xref xref-page'} href={`#${span.xref}`}>
+                            {span.text || 'see page'}
+                        </a>
+                    )
+                }
+                if (span.footnote != null) {
+                    return (
+                        <span key={idx} className="rt-footnote-ref">
+                            {footnoteNumber++}
+                        </span>
+                    )
+                }
+                const className = span.marks.map((mark) => MARK_CLASS[mark]).join(' ')
+                if (span.href != null) {
+                    return (
+                        <a key={idx} className={['rt-link', className].filter(Boolean).join(' ')} href={span.href}>
+                            {span.text}
+                        </a>
+                    )
+                }
+                return className ? (
+                    <span key={idx} className={className}>
+                        {span.text}
+                    </span>
+                ) : (
+                    <React.Fragment key={idx}>{span.text}</React.Fragment>
+                )
+            })}
+        </>
+    )
+}
@@ -0,0 +1,65 @@
+import React from 'react'
+import type { ScoreBand } from '../types'
+
+export const DEFAULT_SCORE_BANDS: ScoreBand[] = [
+    { min: 0, label: 'Needs attention', color: '#dc2626' },
+    { min: 40, label: 'Developing', color: '#f59e0b' },
+    { min: 70, label: 'Strong', color: '#16a34a' },
+]
+
+export const SCORE_LEGEND_STYLES = `
+    .score-legend{ display:flex; flex-wrap:wrap; gap:4px 12px; justify-content:center; margin:var(--rf-space-s) 0 var(--rf-space-l); padding:0; list-style:none; font-size:8.5px; color:#475569; break-inside:avoid; }
+    .score-legend li{ display:flex; align-items:center; gap:4px; }
+    .score-legend-swatch{ width:14px; height:6px; border-radius:3px; }
+    .score-legend-range{ font-variant-numeric:tabular-nums; color:#111; }
+`
+
+export const BAND_LABEL_STYLES = `
+    .band-bar{ display:flex; align-items:center; gap:6px; }
+    .band-bar > :first-child{ flex:1; min-width:0; }
+    .band-label{ flex-shrink:0; font-size:8.5px; font-weight:600; white-space:nowrap; }
+`
+
+// The highest band whose `min` the value reaches; values below every band fall in the lowest.
+export function bandFor(value: number, bands: ScoreBand[] = DEFAULT_SCORE_BANDS) {
+    const sorted = [...bands].sort((a, b) => a.min - b.min)
+    return sorted.filter((band) => value >= band.min).pop() ?? sorted[0]
+}
+
+// Prints the band's label after a bar, for readers who think in "on track" rather than percentages; without bands
+// the bar renders on its own.
+export function BandBar({ value, bands, children }: { value: number; bands?: ScoreBand[] | false; children: React.ReactNode }) {
+    const band = bands ? bandFor(value, bands) : undefined
+    if (!band) return <>{children}</>
+    return (
+        <div className="band-bar">
+            {children}
+            <span className="band-label" style={{ color: band.color }}>
+                {band.label}
+            </span>
+        </div>
+    )
+}
+
+type Props = {
+    bands?: ScoreBand[]
+    max?: number
+}
+
+const rangeLabel = (band: ScoreBand, next: ScoreBand | undefined, max: number) =>
+    next ? `${band.min}–${next.min}` : band.min >= max ? `${band.min}` : `${band.min}+`
+
+export function ScoreLegend({ bands = DEFAULT_SCORE_BANDS, max = 100 }: Props) {
+    const sorted = [...bands].sort((a, b) => a.min - b.min)
+    return (
+        <ul className="score-legend" aria-label="Score legend">
+            {sorted.map((band, idx) => (
+                <li key={band.min}>
+                    <span className="score-legend-swatch" style={{ background: band.color }} />
+                    <span className="score-legend-range">{rangeLabel(band, sorted[idx + 1], max)}</span>
+                    <span>{band.label}</span>
+                </li>
+            ))}
+        </ul>
+    )
+}
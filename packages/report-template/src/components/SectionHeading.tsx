@@ -0,0 +1,19 @@
+import React from 'react'
+
+type Props = {
+    as: 'h2' | 'h3' | 'h4'
+    id?: string
+    number?: string
+    className?: string
+    running?: string
+    children: React.ReactNode
+}
+
+export function SectionHeading({ as: Tag, id, number, className, running, children }: Props) {
+    return (
+        <Tag id={id} className={className} data-running={running}>
+            {number && <span className="heading-number">{number}</span>}
+            {children}
+        </Tag>
+    )
+}
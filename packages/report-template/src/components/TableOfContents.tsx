@@ -0,0 +1,41 @@
+import React from 'react'
+import type { OutlineEntry } from '../outline'
+
+export const TABLE_OF_CONTENTS_STYLES = `
+    .toc{ page-break-after: always; }
+    .toc h2{ margin:var(--rf-space-s) 0 var(--rf-space-l); font-size:16px; }
+    .toc ol{ list-style:none; margin:0; padding:0; }
+    .toc li{ margin:0 0 5px; }
+    .toc-row{ display:flex; align-items:baseline; color:#111; text-decoration:none; font-size:11px; }
+    .toc-depth-1 .toc-row{ padding-left:14px; font-size:10px; color:#334155; }
+    .toc-number{ min-width:24px; color:#475569; font-variant-numeric:tabular-nums; }
+    .toc-leader{ flex:1; margin:0 4px; border-bottom:1px dotted #94a3b8; transform:translateY(-3px); }
+    .toc-page{ font-variant-numeric:tabular-nums; }
+    .toc-page::after{ content: target-counter(attr(data-target url), page); }
+`
+
+type Props = {
+    entries: OutlineEntry[]
+    showNumbers?: boolean
+}
+
+// Page numbers come from target-counter(), which the PDF service resolves in its paged layout pass.
+export function TableOfContents({ entries, showNumbers }: Props) {
+    return (
+        <nav className="toc" id="table-of-contents">
+            <h2>Contents</h2>
+            <ol>
+                {entries.map((entry) => (
+                    <li key={entry.id} className={`toc-depth-${Math.min(entry.depth, 1)}`}>
+                        <a className="toc-row" href={`#${entry.id}`}>
+                            {showNumbers && <span className="toc-number">{entry.number}</span>}
+                            <span className="toc-title">{entry.title}</span>
+                            <span className="toc-leader" />
+                            <span className="toc-page" data-target={`#${entry.id}`} />
+                        </a>
+                    </li>
+                ))}
+            </ol>
+        </nav>
+    )
+}
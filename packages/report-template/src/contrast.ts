@@ -0,0 +1,36 @@
+const HEX = /^#([0-9a-f]{3}|[0-9a-f]{6})$/i
+const RGB = /^rgba?\(\s*(\d+)\s*,\s*(\d+)\s*,\s*(\d+)\s*(?:,\s*[\d.]+\s*)?\)$/i
+
+const DARK_TEXT = '#111'
+const LIGHT_TEXT = '#fff'
+
+export function parseColor(value: string): [number, number, number] | undefined {
+    const hex = HEX.exec(value.trim())
+    if (hex) {
+        const full = hex[1]!.length === 3 ? hex[1]!.replace(/./g, (c) => c + c) : hex[1]!
+        return [0, 2, 4].map((i) => parseInt(full.slice(i, i + 2), 16)) as [number, number, number]
+    }
+    const rgb = RGB.exec(value.trim())
+    return rgb ? [Number(rgb[1]), Number(rgb[2]), Number(rgb[3])] : undefined
+}
+
+// WCAG 2 relative luminance.
+const relativeLuminance = ([r, g, b]: [number, number, number]) => {
+    const linear = (channel: number) => {
+        const c = Math.min(255, channel) / 255
+        return c <= 0.03928 ? c / 12.92 : ((c + 0.055) / 1.055) ** 2.4
+    }
+    return 0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+const contrastRatio = (a: number, b: number) => (Math.max(a, b) + 0.05) / (Math.min(a, b) + 0.05)
+
+// Dark or white text, whichever contrasts more with the background; undefined for colors we can't parse (e.g. names),
+// which keep the table's own text color.
+export function readableTextColor(background: string) {
+    const rgb = parseColor(background)
+    if (!rgb) return undefined
+    const luminance = relativeLuminance(rgb)
+    const dark = relativeLuminance(parseColor(DARK_TEXT)!)
+    return contrastRatio(luminance, dark) >= contrastRatio(luminance, 1) ? DARK_TEXT : LIGHT_TEXT
+}
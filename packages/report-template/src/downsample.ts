@@ -0,0 +1,41 @@
+import type { ChartPoint } from './types'
+
+export const DEFAULT_MAX_CHART_POINTS = 240
+
+// Largest-Triangle-Three-Buckets: keeps the first and last points and, per bucket, the point forming the largest
+// triangle with the previous pick and the next bucket's average, so peaks and dips survive. Points are assumed to be
+// evenly spaced, so their index stands in for x.
+export function downsample(points: ChartPoint[], target: number): ChartPoint[] {
+    if (target >= points.length || target < 3) return points
+    const sampled: ChartPoint[] = [points[0]!]
+    const bucketSize = (points.length - 2) / (target - 2)
+    let previous = 0
+    for (let bucket = 0; bucket < target - 2; bucket++) {
+        const start = Math.floor(bucket * bucketSize) + 1
+        const end = Math.floor((bucket + 1) * bucketSize) + 1
+        const nextEnd = Math.min(points.length, Math.floor((bucket + 2) * bucketSize) + 1)
+        let avgX = 0
+        let avgY = 0
+        for (let idx = end; idx < nextEnd; idx++) {
+            avgX += idx
+            avgY += points[idx]!.y
+        }
+        const count = Math.max(1, nextEnd - end)
+        avgX /= count
+        avgY /= count
+
+        let best = start
+        let bestArea = -1
+        for (let idx = start; idx < end; idx++) {
+            const area = Math.abs((previous - avgX) * (points[idx]!.y - points[previous]!.y) - (previous - idx) * (avgY - points[previous]!.y))
+            if (area > bestArea) {
+                bestArea = area
+                best = idx
+            }
+        }
+        sampled.push(points[best]!)
+        previous = best
+    }
+    sampled.push(points[points.length - 1]!)
+    return sampled
+}
@@ -0,0 +1,39 @@
+const DOCUMENT = /^(?:<!doctype html>)?<html([^>]*)>(<head>[\s\S]*<\/head>)<body>([\s\S]*)<\/body><\/html>$/i
+const REFERENCES = /(\s(?:id|for|data-form-field)="|\s(?:href|data-target)="#)([^"]*)"/g
+
+const EDITION_RULES = `<style>.edition + .edition{ break-before: page; }</style>`
+
+export type Edition = {
+    lang: string
+    html: string
+}
+
+// Every id (and everything pointing at one) is prefixed per edition so anchors, cross-references and form fields
+// in the second language don't resolve into the first.
+const prefixReferences = (body: string, lang: string) =>
+    body.replace(REFERENCES, (_, attribute: string, value: string) => `${attribute}${lang}-${value}"`)
+
+// A malformed tag such as "en_US" makes Intl throw; it is labelled as given rather than failing the render.
+export const languageName = (lang: string) => {
+    try {
+        const name = new Intl.DisplayNames([lang], { type: 'language' }).of(lang) ?? lang
+        return name.charAt(0).toLocaleUpperCase(lang) + name.slice(1)
+    } catch {
+        return lang
+    }
+}
+
+// Editions share the first document's head; the <html> flags (e.g. data-paged) are the union of all editions.
+export function combineEditions(editions: Edition[]) {
+    const parsed = editions.map(({ lang, html }) => {
+        const match = DOCUMENT.exec(html)
+        if (!match) throw new Error(`Cannot combine edition "${lang}": unexpected document shape`)
+        const [, attributes = '', head = '', body = ''] = match
+        return { lang, attributes, head, body }
+    })
+    const attributes = new Set(
+        parsed.flatMap((edition) => (edition.attributes.match(/\s[\w-]+="[^"]*"/g) ?? []).filter((attribute) => !attribute.startsWith(' lang=')))
+    )
+    const bodies = parsed.map((edition) => `<div class="edition" lang="${edition.lang}">${prefixReferences(edition.body, edition.lang)}</div>`)
+    return `<!doctype html><html lang="${parsed[0]!.lang}"${[...attributes].join('')}>${parsed[0]!.head.replace('</head>', `${EDITION_RULES}</head>`)}<body>${bodies.join('')}</body></html>`
+}
@@ -0,0 +1,112 @@
+import type { EmojiPolicy } from './types'
+
+// Emoji-presentation characters (or text-default ones forced with U+FE0F), skin tones, ZWJ sequences, flags and
+// keycaps. Text-style symbols such as ©, ™ and ▶ are left alone: body fonts draw those fine.
+const PICTOGRAPH = String.raw`(?:\p{Emoji_Presentation}|\p{Extended_Pictographic}\uFE0F)\p{Emoji_Modifier}?`
+export const EMOJI = new RegExp(
+    String.raw`\p{Regional_Indicator}{2}|[#*0-9]\uFE0F?\u20E3|${PICTOGRAPH}(?:\u200D\p{Extended_Pictographic}\uFE0F?\p{Emoji_Modifier}?)*`,
+    'gu'
+)
+
+// The ones LLM drafts actually use; anything else is dropped under the shortcode policy too.
+const SHORTCODES: Record<string, string> = {
+    '😀': 'grinning',
+    '😁': 'grin',
+    '😃': 'smiley',
+    '😊': 'blush',
+    '🙂': 'slightly_smiling_face',
+    '😉': 'wink',
+    '👍': '+1',
+    '👎': '-1',
+    '👏': 'clap',
+    '🙌': 'raised_hands',
+    '🙏': 'pray',
+    '💪': 'muscle',
+    '🤝': 'handshake',
+    '👉': 'point_right',
+    '🎉': 'tada',
+    '✨': 'sparkles',
+    '🚀': 'rocket',
+    '🔥': 'fire',
+    '💡': 'bulb',
+    '🎯': 'dart',
+    '🏆': 'trophy',
+    '⭐': 'star',
+    '🌟': 'star2',
+    '✅': 'white_check_mark',
+    '✔': 'heavy_check_mark',
+    '❌': 'x',
+    '⚠': 'warning',
+    '❗': 'exclamation',
+    '❤': 'heart',
+    '📈': 'chart_with_upwards_trend',
+    '📉': 'chart_with_downwards_trend',
+    '📊': 'bar_chart',
+    '💰': 'moneybag',
+    '💵': 'dollar',
+    '📅': 'date',
+    '📌': 'pushpin',
+    '📝': 'memo',
+    '📞': 'telephone_receiver',
+    '✉': 'email',
+    '🔔': 'bell',
+    '⏰': 'alarm_clock',
+    '🦷': 'tooth',
+}
+
+// Lookups ignore presentation selectors and skin tones, so 👍🏽 still finds +1.
+const shortcode = (emoji: string) => {
+    const name = SHORTCODES[emoji.replace(/[\uFE0F\p{Emoji_Modifier}]/gu, '')]
+    return name ? `:${name}:` : ''
+}
+
+// `--rf-emoji-font` is set by the PDF service when a color emoji font is installed in its assets.
+const FALLBACK_FONTS = `var(--rf-emoji-font, 'Noto Color Emoji'), 'Apple Color Emoji', 'Segoe UI Emoji'`
+
+const WORD = /[\p{L}\p{N}]/u
+
+// A run of emoji between two words leaves one space behind; one next to punctuation takes its space with it.
+const STRIPPED = new RegExp(String.raw`( ?)(?:${EMOJI.source})+( ?)`, 'gu')
+
+// Tags (and so attributes), comments and the bodies of <style> and <script> pass through untouched.
+const MARKUP = /<(style|script)\b[^>]*>[\s\S]*?<\/\1\s*>|<!--[\s\S]*?-->|<[^>]*>/gi
+
+const rewriteText = (html: string, rewrite: (text: string) => string) => {
+    let output = ''
+    let last = 0
+    for (const match of html.matchAll(MARKUP)) {
+        output += rewrite(html.slice(last, match.index)) + match[0]
+        last = match.index + match[0].length
+    }
+    return output + rewrite(html.slice(last))
+}
+
+// Runs on the finished markup so every text source (sections, picks, blocks, table cells) is covered at once. The
+// policy and counts are recorded on <html> so the PDF service can report what happened.
+export function emojiMarkup(html: string, policy: EmojiPolicy = 'font') {
+    let count = 0
+    // Emoji without a shortcode, which the shortcode policy drops.
+    let dropped = 0
+    const replaced = rewriteText(html, (text) => {
+        const found = text.match(EMOJI)?.length ?? 0
+        if (!found) return text
+        count += found
+        if (policy === 'strip') {
+            return text.replace(STRIPPED, (run: string, before: string, after: string, offset: number) => {
+                const neighbour = before ? text[offset + run.length] : text[offset - 1]
+                return (before && after) || ((before || after) && WORD.test(neighbour ?? '')) ? ' ' : ''
+            })
+        }
+        if (policy === 'shortcode') {
+            return text.replace(EMOJI, (emoji) => {
+                const code = shortcode(emoji)
+                if (!code) dropped++
+                return code
+            })
+        }
+        return text
+    })
+    if (!count) return html
+    const tagged = replaced.replace('<html', `<html data-emoji="${policy}" data-emoji-count="${count}"${dropped ? ` data-emoji-dropped="${dropped}"` : ''}`)
+    return policy === 'font' ? tagged.replace('</head>', `<style>body{ font-family: var(--rf-brand-font), ${FALLBACK_FONTS}; }</style></head>`) : tagged
+}
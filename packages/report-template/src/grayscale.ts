@@ -0,0 +1,41 @@
+// References and strings are matched first so that url(#abc), content:"#100" and the like are passed through as-is.
+const COLOR =
+    /url\([^)]*\)|"[^"]*"|'[^']*'|&quot;[\s\S]*?&quot;|#([0-9a-f]{8}|[0-9a-f]{6}|[0-9a-f]{4}|[0-9a-f]{3})(?![\w-])|rgba?\(\s*([\d.]+%?)(?:\s*,\s*|\s+)([\d.]+%?)(?:\s*,\s*|\s+)([\d.]+%?)\s*(?:[,/]\s*([\d.]+%?)\s*)?\)/gi
+
+// Colors only ever appear in declaration values and SVG paint attributes; selectors (#add), hrefs and text are left alone.
+const STYLED_REGIONS = /(<style[^>]*>)([\s\S]*?)(<\/style>)|(\s(?:style|fill|stroke|stop-color|flood-color|lighting-color|color)=")([^"]*)(")/gi
+// Innermost braces hold declarations, never selectors, at any @media or @page nesting depth.
+const DECLARATION_BLOCK = /\{([^{}]*)\}/g
+const DECLARATION = /([\w-]+\s*:)([^;]*)/g
+
+const luminance = (r: number, g: number, b: number) => Math.round(0.2126 * r + 0.7152 * g + 0.0722 * b)
+
+const channel = (value: string) => (value.endsWith('%') ? (parseFloat(value) / 100) * 255 : Number(value))
+
+const hexByte = (value: number) => Math.min(255, Math.max(0, value)).toString(16).padStart(2, '0')
+
+const grayHex = (hex: string) => {
+    const full = hex.length <= 4 ? hex.replace(/./g, (c) => c + c) : hex
+    const [r, g, b] = [0, 2, 4].map((i) => parseInt(full.slice(i, i + 2), 16)) as [number, number, number]
+    const gray = hexByte(luminance(r, g, b))
+    return `#${gray}${gray}${gray}${full.slice(6)}`
+}
+
+const convertColors = (value: string) =>
+    value.replace(COLOR, (match, hex?: string, r?: string, g?: string, b?: string, alpha?: string) => {
+        if (hex) return grayHex(hex)
+        if (!r || !g || !b) return match
+        const gray = luminance(channel(r), channel(g), channel(b))
+        return alpha ? `rgba(${gray},${gray},${gray},${alpha})` : `rgb(${gray},${gray},${gray})`
+    })
+
+const convertDeclarations = (declarations: string) =>
+    declarations.replace(DECLARATION, (_, property: string, value: string) => property + convertColors(value))
+
+export function grayscaleMarkup(html: string) {
+    return html.replace(STYLED_REGIONS, (_, open?: string, css?: string, close?: string, attribute?: string, value?: string, quote?: string) => {
+        if (open) return open + css!.replace(DECLARATION_BLOCK, (_block, body: string) => `{${convertDeclarations(body)}}`) + close
+        const paint = attribute!.trim().toLowerCase() !== 'style='
+        return attribute + (paint ? convertColors(value!) : convertDeclarations(value!)) + quote
+    })
+}
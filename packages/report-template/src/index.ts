@@ -1,5 +1,14 @@
-export { renderReport } from './render'
+export { renderReport, renderReportParallel } from './render'
+export { renderComparisonReport } from './renderComparison'
 export { renderDrilldownReport } from './renderDrilldown'
 export { Report } from './Report'
+export { reservedAnchors } from './anchors'
+export { kpiCsv, sourceAttachment, type SourceAttachment } from './attachments'
+export { isCurrencyCode } from './kpi'
+export { redactionPattern } from './redact'
 export { GradientProgressBar, GradientBar } from './components/GradientProgressBar'
+export { BulletGraph } from './components/BulletGraph'
+export { DotScale } from './components/DotScale'
+export { Icon } from './components/Icon'
+export { ReportImage } from './components/ReportImage'
 export * from './types'
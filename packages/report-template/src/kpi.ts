@@ -0,0 +1,58 @@
+import type { KPI } from './types'
+
+const SCALE_STEPS = [1, 2, 2.5, 5, 10]
+
+// Rounds up to 1/2/2.5/5 × 10ⁿ so a bar's full width is a readable number.
+const niceMax = (value: number) => {
+    if (value <= 0) return 1
+    const magnitude = 10 ** Math.floor(Math.log10(value))
+    return (SCALE_STEPS.find((step) => step * magnitude >= value) ?? 10) * magnitude
+}
+
+// Percentages keep the historical 0–100 bar; other types scale to an explicit `scaleMax` or the larger of value/practice.
+export function kpiScale(kpi: KPI) {
+    const type = kpi.valueType ?? 'percent'
+    if (kpi.scaleMax != null) return { min: kpi.scaleMin ?? 0, max: kpi.scaleMax }
+    if (type === 'percent') return { min: 0, max: 100 }
+    return { min: 0, max: niceMax(Math.max(kpi.value, kpi.practice ?? 0)) }
+}
+
+export function kpiFraction(kpi: KPI) {
+    const { min, max } = kpiScale(kpi)
+    return max === min ? 0 : (kpi.value - min) / (max - min)
+}
+
+const CURRENCIES = new Set(Intl.supportedValuesOf('currency'))
+
+// Intl throws on anything that isn't an ISO 4217 code; the PDF service rejects those up front, the CLI and reviewer don't.
+export const isCurrencyCode = (unit: string) => CURRENCIES.has(unit.toUpperCase())
+
+export function formatKpiValue(kpi: KPI, value: number) {
+    switch (kpi.valueType ?? 'percent') {
+        case 'currency': {
+            const digits = Math.abs(value) < 100 ? 2 : 0
+            const currency = kpi.unit ?? 'USD'
+            if (!isCurrencyCode(currency)) return `${new Intl.NumberFormat('en-US', { maximumFractionDigits: digits }).format(value)} ${currency}`
+            return new Intl.NumberFormat('en-US', { style: 'currency', currency, maximumFractionDigits: digits }).format(value)
+        }
+        case 'count':
+            return `${new Intl.NumberFormat('en-US').format(value)}${kpi.unit ? ` ${kpi.unit}` : ''}`
+        case 'ratio':
+            return `${new Intl.NumberFormat('en-US', { maximumFractionDigits: 2 }).format(value)}${kpi.unit ?? '×'}`
+        default:
+            return `${value}${kpi.unit ?? '%'}`
+    }
+}
+
+export function formatKpiChange(kpi: KPI, change: number) {
+    const type = kpi.valueType ?? 'percent'
+    // Percentage changes are points, so they stay unitless as before.
+    const magnitude = type === 'percent' ? String(Math.abs(change)) : formatKpiValue(kpi, Math.abs(change))
+    return `${change > 0 ? '+' : change < 0 ? '-' : ''}${magnitude}`
+}
+
+// Upper half reads as "Top 15% of practices"; the lower half as the share of practices this one is ahead of.
+export function percentileCaption(percentile: number) {
+    const rank = Math.min(100, Math.max(0, Math.round(percentile)))
+    return rank >= 50 ? `Top ${Math.max(1, 100 - rank)}% of practices` : `Ahead of ${rank}% of practices`
+}
@@ -0,0 +1,29 @@
+export type OutlineNode = {
+    id: string
+    title: string
+    children?: OutlineNode[]
+}
+
+export type OutlineEntry = {
+    id: string
+    title: string
+    number: string
+    depth: number
+}
+
+// Flattens the document outline in reading order with hierarchical numbers (1, 1.1, 1.2, 2…).
+export function numberOutline(nodes: Array<OutlineNode | false | 0 | undefined>) {
+    const entries: OutlineEntry[] = []
+    const visit = (list: OutlineNode[], prefix: string, depth: number) =>
+        list.forEach((node, idx) => {
+            const number = prefix ? `${prefix}.${idx + 1}` : `${idx + 1}`
+            entries.push({ id: node.id, title: node.title, number, depth })
+            if (node.children?.length) visit(node.children, number, depth + 1)
+        })
+    visit(
+        nodes.filter((node): node is OutlineNode => Boolean(node)),
+        '',
+        0
+    )
+    return entries
+}
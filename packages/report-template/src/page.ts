@@ -0,0 +1,67 @@
+import type { PageMargins, PrintMarks } from './types'
+
+export const REPORT_MARGIN_MM = 8.89
+
+const LETTER_MM = { width: 215.9, height: 279.4 }
+const DEFAULT_BLEED_MM = 3
+const DEFAULT_MARK_LENGTH_MM = 5
+const MARK_GAP_MM = 2
+const MARK_WEIGHT = '0.25pt'
+
+export type PageSetup = {
+    margins?: PageMargins
+    duplex?: boolean
+    printMarks?: PrintMarks
+}
+
+// Distance from the media edge to the trim line: crop mark, a small gap, then the bleed.
+const slugSize = (marks: PrintMarks) => (marks.markLength ?? DEFAULT_MARK_LENGTH_MM) + MARK_GAP_MM + (marks.bleed ?? DEFAULT_BLEED_MM)
+
+const mark = (x: string, y: string, width: string, height: string) =>
+    `linear-gradient(#000, #000) ${x} ${y} / ${width} ${height} no-repeat`
+
+const cropMarkRules = (marks: PrintMarks) => {
+    const pad = `${slugSize(marks)}mm`
+    const length = `${marks.markLength ?? DEFAULT_MARK_LENGTH_MM}mm`
+    const corners = [
+        ['top-left-corner', 'left', 'top'],
+        ['top-right-corner', 'right', 'top'],
+        ['bottom-left-corner', 'left', 'bottom'],
+        ['bottom-right-corner', 'right', 'bottom'],
+    ] as const
+    return corners.map(([box, h, v]) => {
+        const horizontal = mark(`${h} 0`, `${v} ${pad}`, length, MARK_WEIGHT)
+        const vertical = mark(`${h} ${pad}`, `${v} 0`, MARK_WEIGHT, length)
+        return `@${box} { content: ''; background: ${horizontal}, ${vertical}; }`
+    })
+}
+
+// In duplex mode `left` is the inner (binding) margin and `right` the outer one, swapped on verso pages.
+export function pageRules({ margins, duplex, printMarks }: PageSetup, fallbackMm: number) {
+    const pad = printMarks ? slugSize(printMarks) : 0
+    const side = (value?: number) => `${(value ?? fallbackMm) + pad}mm`
+    const size = printMarks ? `${LETTER_MM.width + pad * 2}mm ${LETTER_MM.height + pad * 2}mm` : 'Letter'
+    const landscape = printMarks ? `${LETTER_MM.height + pad * 2}mm ${LETTER_MM.width + pad * 2}mm` : 'Letter landscape'
+    const inner = side(margins?.left)
+    const outer = side(margins?.right)
+
+    const body = [`size: ${size};`, `margin: ${side(margins?.top)} ${outer} ${side(margins?.bottom)} ${inner};`]
+    if (printMarks) body.push(...cropMarkRules(printMarks))
+
+    // Elements with `page: landscape` start a new page of the rotated size; Chrome breaks before and after them.
+    const rules = [`@page { ${body.join(' ')} }`, `@page landscape { size: ${landscape}; }`, `.landscape { page: landscape; }`]
+    if (duplex) {
+        rules.push(`@page :right { margin-left: ${inner}; margin-right: ${outer}; }`)
+        rules.push(`@page :left { margin-left: ${outer}; margin-right: ${inner}; }`)
+    }
+    if (printMarks) {
+        // From the text column's edge out past the trim to the edge of the bleed.
+        const bleed = `${printMarks.bleed ?? DEFAULT_BLEED_MM}mm`
+        const left = `(${inner} - ${pad}mm + ${bleed})`
+        const right = `(${outer} - ${pad}mm + ${bleed})`
+        rules.push(`.report-image.full-bleed { display: block; width: calc(100% + ${left} + ${right}); margin-left: calc(-1 * ${left}); margin-right: calc(-1 * ${right}); }`)
+    } else {
+        rules.push(`.report-image.full-bleed { display: block; width: 100%; }`)
+    }
+    return rules.join('\n')
+}
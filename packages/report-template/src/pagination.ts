@@ -0,0 +1,23 @@
+export const DEFAULT_TABLE_MIN_ROWS = 2
+export const DEFAULT_KEEP_LINES = 2
+
+export type PaginationOptions = {
+    tableMinRows?: number
+    keepLines?: number
+}
+
+// Keep-together constraints shared by every document type, so a heading, a paragraph's first lines and a table's
+// first rows all follow the same rule: nothing is left alone at the bottom of a page.
+// Chained break-after:avoid keeps the header and the first N rows together, so if they don't fit the table moves
+// to the next page; the lead-in heading travels with it.
+export function paginationRules({ tableMinRows = DEFAULT_TABLE_MIN_ROWS, keepLines = DEFAULT_KEEP_LINES }: PaginationOptions = {}) {
+    const rows = Math.max(1, tableMinRows)
+    const lines = Math.max(1, keepLines)
+    return `
+    h1,h2,h3,h4,caption,.block-card > h4{ break-after:avoid; }
+    p,li,blockquote,.summary-copy{ orphans:${lines}; widows:${lines}; }
+    table tr{ break-inside:avoid; }
+    table thead{ break-after:avoid; }
+    ${rows > 1 ? `table tbody:first-of-type tr:nth-child(-n+${rows - 1}){ break-after:avoid; }` : ''}
+`
+}
@@ -0,0 +1,51 @@
+import { renderToStaticMarkup } from 'react-dom/server'
+import { Chart } from './components/Chart'
+import type { ChartSpec } from './types'
+
+export type ChartJob = { id: string; chart: ChartSpec }
+
+// Prerendered charts go into the tree as empty slots and are swapped in after the render, so the output is
+// byte-identical to rendering them inline.
+export const ChartSlot = ({ id }: { id: string }) => <template data-chart-slot={id} />
+
+const unescapeAttribute = (value: string) =>
+    value.replace(/&quot;/g, '"').replace(/&#x27;/g, "'").replace(/&lt;/g, '<').replace(/&gt;/g, '>').replace(/&amp;/g, '&')
+
+export const fillChartSlots = (html: string, markup: Record<string, string>) =>
+    html.replace(/<template data-chart-slot="([^"]*)"><\/template>/g, (slot, id: string) => markup[unescapeAttribute(id)] ?? slot)
+
+const workerCount = (jobs: number) => Math.min(jobs, Math.max(1, (globalThis.navigator?.hardwareConcurrency ?? 2) - 1))
+
+// Charts are the only sections whose markup is expensive (downsampling, label placement), and each one depends on
+// nothing but its own spec, so they render on a worker pool and the results are stitched back in by section id.
+export async function prerenderCharts(jobs: ChartJob[]): Promise<Record<string, string>> {
+    if (jobs.length < 2 || typeof Worker === 'undefined') {
+        return Object.fromEntries(jobs.map(({ id, chart }) => [id, renderToStaticMarkup(<Chart chart={chart} />)]))
+    }
+    const markup: Record<string, string> = {}
+    const queue = [...jobs]
+    const workers = Array.from({ length: workerCount(jobs.length) }, () => new Worker(new URL('./chartWorker.tsx', import.meta.url), { type: 'module' }))
+    try {
+        await Promise.all(
+            workers.map(
+                (worker) =>
+                    new Promise<void>((resolve, reject) => {
+                        const next = () => {
+                            const job = queue.shift()
+                            if (!job) return resolve()
+                            worker.postMessage(job)
+                        }
+                        worker.onmessage = (event: MessageEvent<{ id: string; markup: string }>) => {
+                            markup[event.data.id] = event.data.markup
+                            next()
+                        }
+                        worker.onerror = (event) => reject(new Error(`Chart worker failed: ${event.message}`))
+                        next()
+                    })
+            )
+        )
+    } finally {
+        workers.forEach((worker) => worker.terminate())
+    }
+    return markup
+}
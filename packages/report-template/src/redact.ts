@@ -0,0 +1,167 @@
+import type { Block, DraftBundle, RedactionRules, ReportImage, SectionSelection, TableCellValue, TableRow } from './types'
+
+// Built-in patterns; `redaction.patterns` adds to them and `redaction.terms` covers names no pattern can find.
+const DEFAULT_PATTERNS = [
+    /[\w.+-]+@[\w-]+(?:\.[\w-]+)+/g,
+    /(?:\+?1[\s.-]?)?\(?\b\d{3}\)?[\s.-]?\d{3}[\s.-]\d{4}\b/g,
+    /\+\d{1,3}(?:[\s.-]?\d{2,4}){2,4}\b/g,
+]
+
+const escapeRegExp = (text: string) => text.replace(/[.*+?^${}()|[\]\\]/g, '\\$&')
+
+// Same length as the original so a redacted sample paginates like the real report.
+const mask = (match: string) => match.replace(/\S/g, '█')
+
+const MAX_PATTERN_LENGTH = 200
+
+// Nested quantifiers such as (a+)+ are the usual source of catastrophic backtracking, and a user pattern runs over
+// every narrative field, so they are refused rather than timed.
+const nestedQuantifier = (pattern: string) => {
+    const outer: boolean[] = []
+    let quantified = false
+    for (let idx = 0; idx < pattern.length; idx++) {
+        const char = pattern[idx]
+        if (char === '\\') idx++
+        else if (char === '[') {
+            while (++idx < pattern.length && pattern[idx] !== ']') if (pattern[idx] === '\\') idx++
+        } else if (char === '(') {
+            outer.push(quantified)
+            quantified = false
+        } else if (char === ')') {
+            const repeated = /[*+{]/.test(pattern[idx + 1] ?? '')
+            if (repeated && quantified) return true
+            quantified = (outer.pop() ?? false) || quantified || repeated
+        } else if (/[*+{]/.test(char!)) quantified = true
+    }
+    return false
+}
+
+// Throws with a message fit for an invalid_payload response; the PDF service calls it while reading the payload.
+export function redactionPattern(pattern: unknown) {
+    if (typeof pattern !== 'string' || !pattern) throw new Error('Redaction patterns must be non-empty strings')
+    if (pattern.length > MAX_PATTERN_LENGTH) throw new Error(`Redaction pattern is longer than ${MAX_PATTERN_LENGTH} characters`)
+    if (nestedQuantifier(pattern) || /\\[1-9k]/.test(pattern)) throw new Error(`Redaction pattern "${pattern}" could backtrack without bound`)
+    try {
+        return new RegExp(pattern, 'gi')
+    } catch (error) {
+        throw new Error(`Redaction pattern "${pattern}" is not a valid regular expression`, { cause: error })
+    }
+}
+
+// Letters and digits on either side rather than \b, which only knows ASCII and would never match around "José".
+const termPattern = (term: string) => new RegExp(`(?<![\\p{L}\\p{N}])${escapeRegExp(term)}(?![\\p{L}\\p{N}])`, 'giu')
+
+const compile = (rules: RedactionRules = {}) => [
+    ...DEFAULT_PATTERNS,
+    ...(rules.patterns ?? []).map(redactionPattern),
+    ...(rules.terms ?? []).filter((term) => term.trim()).map((term) => termPattern(term.trim())),
+]
+
+const redactor = (bundle: DraftBundle) => {
+    const patterns = compile(bundle.redaction)
+    return <T extends string | undefined>(text: T): T =>
+        (text == null ? text : patterns.reduce((current, pattern) => current.replace(pattern, mask), text as string)) as T
+}
+
+const redactImage = <T extends ReportImage | undefined>(image: T, redact: (text: string) => string): T =>
+    image && { ...image, alt: image.alt && redact(image.alt) }
+
+const redactBlocks = (blocks: Block[], redact: (text: string) => string): Block[] =>
+    blocks.map((block) => {
+        switch (block.type) {
+            case 'heading':
+            case 'paragraph':
+            case 'legal':
+                return { ...block, text: redact(block.text) }
+            case 'table': {
+                const redactValue = (value: TableCellValue) => (typeof value === 'string' ? redact(value) : value)
+                const redactRow = (row: TableRow) =>
+                    row.map((cell) => (cell != null && typeof cell === 'object' ? { ...cell, value: redactValue(cell.value) } : redactValue(cell)))
+                const subRows = block.subRows && Object.fromEntries(Object.entries(block.subRows).map(([idx, rows]) => [idx, rows.map(redactRow)]))
+                return {
+                    ...block,
+                    caption: block.caption && redact(block.caption),
+                    columns: (block.columns ?? []).map(redact),
+                    rows: (block.rows ?? []).map(redactRow),
+                    subRows,
+                }
+            }
+            case 'contactCard':
+                return {
+                    ...block,
+                    name: redact(block.name),
+                    title: block.title && redact(block.title),
+                    phone: block.phone && redact(block.phone),
+                    email: block.email && redact(block.email),
+                    website: block.website && redact(block.website),
+                    photo: redactImage(block.photo, redact),
+                }
+            case 'quote':
+                return {
+                    ...block,
+                    text: redact(block.text),
+                    attribution: block.attribution && redact(block.attribution),
+                    role: block.role && redact(block.role),
+                }
+            case 'image':
+                return { ...block, image: redactImage(block.image, redact) }
+            case 'checklist':
+                return { ...block, title: block.title && redact(block.title), items: (block.items ?? []).map((item) => ({ ...item, text: redact(item.text) })) }
+            case 'timeline':
+                return {
+                    ...block,
+                    title: block.title && redact(block.title),
+                    milestones: (block.milestones ?? []).map((milestone) => ({
+                        ...milestone,
+                        title: redact(milestone.title),
+                        description: milestone.description && redact(milestone.description),
+                    })),
+                }
+            case 'card':
+                return { ...block, title: block.title && redact(block.title), blocks: redactBlocks(block.blocks ?? [], redact) }
+            default:
+                return block
+        }
+    })
+
+// Masks every narrative field, image alt texts and form fields (written into the PDF as AcroForm values); ids, numbers
+// and KPI names are left alone so anchors and layout are unchanged. The rules themselves are dropped, since their terms are the very names being hidden and the bundle can be attached as JSON.
+export function redactBundle(bundle: DraftBundle): DraftBundle {
+    if (!bundle.redact) return bundle
+    const redact = redactor(bundle)
+    const { redaction: _rules, ...rest } = bundle
+    return {
+        ...rest,
+        clientName: redact(bundle.clientName),
+        sections: (bundle.sections || []).map((section) => ({
+            ...section,
+            title: redact(section.title),
+            options: section.options?.map((option) => ({ ...option, text: redact(option.text) })),
+            image: redactImage(section.image, redact),
+            translations:
+                section.translations &&
+                Object.fromEntries(
+                    Object.entries(section.translations).map(([lang, translation]) => [
+                        lang,
+                        { title: redact(translation.title), text: redact(translation.text) },
+                    ])
+                ),
+        })),
+        growthCategories: bundle.growthCategories?.map((category) => ({
+            ...category,
+            explanation: redact(category.explanation),
+            narrative: redact(category.narrative),
+            chart: redactImage(category.chart, redact),
+        })),
+        summaryDetails: bundle.summaryDetails?.map((detail) => ({ ...detail, title: redact(detail.title), text: redact(detail.text) })),
+        blocks: bundle.blocks && redactBlocks(bundle.blocks, redact),
+        formFields: bundle.formFields?.map((field) => ({ ...field, label: redact(field.label), value: redact(field.value) })),
+    }
+}
+
+// The reviewer's picks can be free text too.
+export function redactSelection(bundle: DraftBundle, chosen: SectionSelection): SectionSelection {
+    if (!bundle.redact) return chosen
+    const redact = redactor(bundle)
+    return Object.fromEntries(Object.entries(chosen).map(([id, text]) => [id, redact(text)]))
+}
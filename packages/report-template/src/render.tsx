@@ -1,27 +1,118 @@
 import { renderToStaticMarkup } from 'react-dom/server'
+import { reservedAnchors } from './anchors'
+import { blockCharts } from './components/Blocks'
+import { combineEditions, languageName } from './editions'
+import { emojiMarkup } from './emoji'
+import { grayscaleMarkup } from './grayscale'
+import { fillChartSlots, prerenderCharts } from './parallel'
+import { redactBundle, redactSelection } from './redact'
 import { Report } from './Report'
 import type { DraftBundle, ReportSection, SectionSelection } from './types'
 
+// Translations fall back to the primary-language title and the reviewer's chosen text.
+const reportSections = (bundle: DraftBundle, chosen: SectionSelection, lang?: string): ReportSection[] =>
+    (bundle.sections || []).map((section) => {
+        const translation = lang ? section.translations?.[lang] : undefined
+        return {
+            id: section.id,
+            title: translation?.title ?? section.title,
+            chartUrl: section.chartUrl,
+            chart: section.chart,
+            group: section.group,
+            icon: section.icon,
+            image: section.image,
+            align: section.align,
+            orientation: section.orientation,
+            text: translation?.text ?? chosen[section.id] ?? section.options?.[0]?.text ?? '',
+        }
+    })
+
 export function renderReport(bundle: DraftBundle, chosen: SectionSelection = {}) {
-    const sections: ReportSection[] = (bundle.sections || []).map((section) => ({
-        id: section.id,
-        title: section.title,
-        chartUrl: section.chartUrl,
-        group: section.group,
-        text: chosen[section.id] ?? section.options?.[0]?.text ?? ''
-    }))
+    return stitchReport(bundle, chosen)
+}
+
+// Same output as renderReport, with chart sections rendered on worker threads first.
+export async function renderReportParallel(bundle: DraftBundle, chosen: SectionSelection = {}) {
+    const charts = [
+        ...(bundle.sections || []).flatMap((section) => (section.chart ? [{ id: section.id, chart: section.chart }] : [])),
+        ...blockCharts(bundle.blocks ?? []),
+    ]
+    return stitchReport(bundle, chosen, await prerenderCharts(charts))
+}
+
+function stitchReport(source: DraftBundle, picks: SectionSelection, chartMarkup?: Record<string, string>) {
+    const [clash] = reservedAnchors(source)
+    if (clash != null) throw new Error(`Id "${clash}" is reserved for the report's own anchors; give the section or heading another id`)
+    const bundle = redactBundle(source)
+    const chosen = redactSelection(source, picks)
+    const languages = bundle.languages ?? []
+    const html =
+        languages.length > 1
+            ? combineEditions(
+                  languages.map((lang, idx) => ({
+                      lang,
+                      html: renderEdition(
+                          bundle,
+                          reportSections(bundle, chosen, idx ? lang : undefined),
+                          lang,
+                          languageName(lang),
+                          chartMarkup
+                      ),
+                  }))
+              )
+            : renderEdition(bundle, reportSections(bundle, chosen), languages[0], undefined, chartMarkup)
+
+    return emojiMarkup(bundle.grayscale ? grayscaleMarkup(html) : html, bundle.emoji)
+}
 
-    return (
+function renderEdition(
+    bundle: DraftBundle,
+    sections: ReportSection[],
+    lang?: string,
+    editionLabel?: string,
+    chartMarkup?: Record<string, string>
+) {
+    const html =
         '<!doctype html>' +
         renderToStaticMarkup(
             <Report
                 clientName={bundle.clientName}
                 date={bundle.date}
+                logo={bundle.logo}
                 kpis={bundle.kpis || []}
+                kpiDisplay={bundle.kpiDisplay}
                 sections={sections}
                 growthCategories={bundle.growthCategories ?? []}
+                categoryTable={bundle.categoryTable}
+                summaryBadges={bundle.summaryBadges}
                 summaryDetails={bundle.summaryDetails ?? []}
+                rules={bundle.rules ?? []}
+                executiveSummary={bundle.executiveSummary}
+                margins={bundle.margins}
+                duplex={bundle.duplex}
+                sectionsStartRight={bundle.sectionsStartRight}
+                printMarks={bundle.printMarks}
+                grayscale={bundle.grayscale}
+                theme={bundle.theme}
+                numberHeadings={bundle.numberHeadings}
+                tableOfContents={bundle.tableOfContents}
+                runningHeaders={bundle.runningHeaders}
+                debug={bundle.debug}
+                brandProfile={bundle.brandProfile}
+                formFields={bundle.formFields ?? []}
+                scoreLegend={bundle.scoreLegend}
+                scoreBands={bundle.scoreBands}
+                bandLabels={bundle.bandLabels}
+                tableMinRows={bundle.tableMinRows}
+                keepLines={bundle.keepLines}
+                fitToPages={bundle.fitToPages}
+                continuationMarkers={bundle.continuationMarkers}
+                includeMethodology={bundle.includeMethodology}
+                blocks={bundle.blocks}
+                lang={lang}
+                editionLabel={editionLabel}
+                chartMarkup={chartMarkup}
             />
         )
-    )
+    return chartMarkup ? fillChartSlots(html, chartMarkup) : html
 }
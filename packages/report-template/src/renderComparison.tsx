@@ -0,0 +1,7 @@
+import { renderToStaticMarkup } from 'react-dom/server'
+import { ComparisonReport } from './ComparisonReport'
+import type { DraftBundle } from './types'
+
+export function renderComparisonReport(current: DraftBundle, previous: DraftBundle) {
+    return '<!doctype html>' + renderToStaticMarkup(<ComparisonReport current={current} previous={previous} />)
+}
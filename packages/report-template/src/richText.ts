@@ -0,0 +1,80 @@
+export type RichMark = 'bold' | 'underline' | 'strike' | 'sup' | 'sub'
+
+export type RichSpan = {
+    text: string
+    marks: RichMark[]
+    footnote?: string
+    xref?: string
+    href?: string
+}
+
+const MARKERS: Record<string, RichMark> = {
+    '**': 'bold',
+    __: 'underline',
+    '~~': 'strike',
+    '^': 'sup',
+    _: 'sub',
+}
+
+// Paired markers (**, __, ~~), baseline shifts (^{..}, _{..}), footnotes ([^..]), cross-references ([[anchor|label]]),
+// markdown links ([label](url)), then bare URLs.
+const INLINE =
+    /(\*\*|__|~~)(.+?)\1|([\^_])\{(.+?)\}|\[\^(.+?)\]|\[\[([^\]|]+)(?:\|([^\]]+))?\]\]|\[([^\]]+)\]\(((?:https?:\/\/|mailto:|tel:)[^)\s]+)\)|(https?:\/\/[^\s<]*[^\s<.,;:!?)])/g
+
+const withMark = (marks: RichMark[], mark?: RichMark) => (mark && !marks.includes(mark) ? [...marks, mark] : marks)
+
+const parse = (text: string, marks: RichMark[]): RichSpan[] => {
+    const spans: RichSpan[] = []
+    let cursor = 0
+    for (const match of text.matchAll(INLINE)) {
+        const [whole, pair, paired = '', shift, shifted = '', footnote, anchor, label, linkText = '', linkUrl, bareUrl] = match
+        const start = match.index ?? 0
+        if (start > cursor) spans.push({ text: text.slice(cursor, start), marks })
+        if (bareUrl != null) {
+            spans.push({ text: bareUrl, marks, href: bareUrl })
+        } else if (linkUrl != null) {
+            spans.push(...parse(linkText, marks).map((span) => ({ ...span, href: linkUrl })))
+        } else if (anchor != null) {
+            spans.push({ text: label?.trim() ?? '', marks, xref: anchor.trim() })
+        } else if (footnote != null) {
+            spans.push({ text: '', marks, footnote: footnote.trim() })
+        } else if (shift) {
+            spans.push(...parse(shifted, withMark(marks, MARKERS[shift])))
+        } else if (pair) {
+            spans.push(...parse(paired, withMark(marks, MARKERS[pair])))
+        }
+        cursor = start + whole.length
+    }
+    if (cursor < text.length) spans.push({ text: text.slice(cursor), marks })
+    return spans
+}
+
+// Inline markup for narrative sections: **bold**, __underline__, ~~strike~~, ^{sup}, _{sub}, [^footnote], [[anchor|label]],
+// [label](https://…) and bare https:// URLs.
+export function parseRichText(text: string) {
+    return parse(text, [])
+}
+
+export function plainText(text: string) {
+    return parseRichText(text)
+        .map((span) => span.text)
+        .join('')
+}
+
+export function hasCrossReferences(text: string) {
+    return parseRichText(text).some((span) => span.xref != null)
+}
+
+export function footnotesIn(text: string) {
+    return parseRichText(text).flatMap((span) => (span.footnote != null ? [span.footnote] : []))
+}
+
+// Footnote markers are numbered in document order, so each text block needs to know where its numbering starts.
+export function footnoteOffsets(texts: string[]) {
+    let next = 1
+    return texts.map((text) => {
+        const start = next
+        next += footnotesIn(text).length
+        return start
+    })
+}
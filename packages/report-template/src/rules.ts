@@ -0,0 +1,62 @@
+import type React from 'react'
+import type { FormatRule, RuleOperator, RuleStyle, RuleTarget } from './types'
+
+const DEFAULT_FIELD: Record<RuleTarget, string> = {
+    kpi: 'value',
+    category: 'score',
+    summary: 'avgProfit',
+    table: '',
+}
+
+const compare = (actual: number, operator: RuleOperator, expected: number) => {
+    switch (operator) {
+        case '<':
+            return actual < expected
+        case '<=':
+            return actual <= expected
+        case '>':
+            return actual > expected
+        case '>=':
+            return actual >= expected
+        case '==':
+            return actual === expected
+        case '!=':
+            return actual !== expected
+        default:
+            return false
+    }
+}
+
+const toCss = (style: RuleStyle) => {
+    const css: React.CSSProperties = {}
+    if (style.color) css.color = style.color
+    if (style.background) css.backgroundColor = style.background
+    if (style.bold) css.fontWeight = 700
+    if (style.italic) css.fontStyle = 'italic'
+    return css
+}
+
+const matches = (rule: FormatRule, field: string, item: object) => {
+    const actual = (item as Record<string, unknown>)[field]
+    return typeof actual === 'number' && compare(actual, rule.operator, rule.value)
+}
+
+// Later rules win, so payloads can layer a general rule with more specific overrides.
+export function ruleStyle(rules: FormatRule[], target: RuleTarget, field: string, item: object) {
+    let result: React.CSSProperties | undefined
+    for (const rule of rules) {
+        if (rule.target !== target || rule.scope === 'row' || (rule.field ?? DEFAULT_FIELD[target]) !== field) continue
+        if (matches(rule, field, item)) result = { ...result, ...toCss(rule.style) }
+    }
+    return result
+}
+
+// Row-scoped rules, e.g. highlighting every category scoring below 40; any field of the item can be tested.
+export function rowStyle(rules: FormatRule[], target: RuleTarget, item: object) {
+    let result: React.CSSProperties | undefined
+    for (const rule of rules) {
+        if (rule.target !== target || rule.scope !== 'row') continue
+        if (matches(rule, rule.field ?? DEFAULT_FIELD[target], item)) result = { ...result, ...toCss(rule.style) }
+    }
+    return result
+}
@@ -0,0 +1,114 @@
+// Client SVGs are inlined so Chromium keeps them as vector paths in the PDF. They are rebuilt from an allowlist of
+// elements and attributes rather than scrubbed with a blocklist, so anything unknown (scripts, event handlers,
+// <animate>/<set>, foreignObject, external references) is simply never written back out.
+
+const ELEMENTS = [
+    'svg', 'g', 'defs', 'symbol', 'use', 'title', 'desc', 'path', 'rect', 'circle', 'ellipse', 'line', 'polyline',
+    'polygon', 'text', 'tspan', 'textPath', 'linearGradient', 'radialGradient', 'stop', 'clipPath', 'mask', 'pattern',
+    'marker', 'image', 'filter', 'feBlend', 'feColorMatrix', 'feComposite', 'feDropShadow', 'feFlood', 'feGaussianBlur',
+    'feMerge', 'feMergeNode', 'feOffset', 'style',
+]
+
+// Links only lose their tag; their text still belongs to the drawing.
+const UNWRAPPED = ['a']
+
+const ATTRIBUTES = [
+    'xmlns', 'xmlns:xlink', 'xml:space', 'version', 'id', 'class', 'style', 'href', 'xlink:href', 'role', 'aria-label',
+    'aria-hidden', 'focusable', 'x', 'y', 'x1', 'y1', 'x2', 'y2', 'cx', 'cy', 'r', 'rx', 'ry', 'fx', 'fy', 'dx', 'dy',
+    'width', 'height', 'd', 'points', 'pathLength', 'transform', 'transform-origin', 'viewBox', 'preserveAspectRatio',
+    'offset', 'gradientUnits', 'gradientTransform', 'spreadMethod', 'patternUnits', 'patternContentUnits',
+    'patternTransform', 'clipPathUnits', 'maskUnits', 'maskContentUnits', 'markerWidth', 'markerHeight', 'markerUnits',
+    'refX', 'refY', 'orient', 'rotate', 'textLength', 'lengthAdjust', 'startOffset', 'filterUnits', 'primitiveUnits',
+    'stdDeviation', 'in', 'in2', 'result', 'mode', 'operator', 'k1', 'k2', 'k3', 'k4', 'type', 'values', 'fill',
+    'fill-opacity', 'fill-rule', 'stroke', 'stroke-width', 'stroke-opacity', 'stroke-linecap', 'stroke-linejoin',
+    'stroke-miterlimit', 'stroke-dasharray', 'stroke-dashoffset', 'opacity', 'color', 'clip-path', 'clip-rule', 'mask',
+    'filter', 'marker-start', 'marker-mid', 'marker-end', 'stop-color', 'stop-opacity', 'flood-color', 'flood-opacity',
+    'font-family', 'font-size', 'font-weight', 'font-style', 'text-anchor', 'dominant-baseline', 'alignment-baseline',
+    'baseline-shift', 'letter-spacing', 'word-spacing', 'text-decoration', 'display', 'visibility', 'overflow',
+    'vector-effect', 'shape-rendering', 'text-rendering', 'image-rendering', 'color-interpolation-filters', 'paint-order',
+]
+
+// Matched case-insensitively (entity and case tricks are decoded before any check) and written back in canonical case.
+const canonical = (names: string[]) => new Map(names.map((name) => [name.toLowerCase(), name]))
+const ALLOWED_ELEMENTS = canonical(ELEMENTS)
+const ALLOWED_ATTRIBUTES = canonical(ATTRIBUTES)
+
+const TOKEN =
+    /<!--[\s\S]*?(?:-->|$)|<!\[CDATA\[[\s\S]*?(?:\]\]>|$)|<[!?][^>]*>?|<\/([A-Za-z][\w:.-]*)\s*>|<([A-Za-z][\w:.-]*)((?:\s+[^\s"'>/=]+(?:\s*=\s*(?:"[^"]*"|'[^']*'|[^\s"'=<>`]+))?)*)\s*(\/?)>|[^<]+|</g
+const ATTRIBUTE = /([^\s"'>/=]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'=<>`]+)))?/g
+
+const NAMED_ENTITIES: Record<string, string> = { amp: '&', lt: '<', gt: '>', quot: '"', apos: "'", nbsp: ' ' }
+
+const decodeEntities = (text: string) =>
+    text.replace(/&(#x[\da-f]+|#\d+|[a-z]+);?/gi, (entity, body: string) => {
+        if (body[0] !== '#') return NAMED_ENTITIES[body.toLowerCase()] ?? entity
+        const code = body[1] === 'x' || body[1] === 'X' ? parseInt(body.slice(2), 16) : parseInt(body.slice(1), 10)
+        return code > 0 && code <= 0x10ffff ? String.fromCodePoint(code) : ''
+    })
+
+const escapeText = (text: string) => text.replace(/&/g, '&amp;').replace(/</g, '&lt;').replace(/>/g, '&gt;')
+const escapeAttribute = (text: string) => escapeText(text).replace(/"/g, '&quot;')
+
+// References stay inside the document: fragment ids, or inline raster data for <image>.
+const SAFE_HREF = /^(?:#[\w:.-]*|data:image\/(?:png|jpe?g|gif|webp);base64,[a-z\d+/=]*)$/i
+// Paint servers, clip paths and the like may only point at elements of this SVG.
+const EXTERNAL_URL = /url\(\s*(?!['"]?#)/i
+
+const safeCss = (css: string) => !EXTERNAL_URL.test(css) && !/expression\s*\(|@import|javascript:/i.test(css)
+
+const attributeValue = (name: string, value: string) => {
+    if (name === 'href' || name === 'xlink:href') {
+        const href = value.replace(/[\u0000-\u0020\u007f]+/g, '')
+        return SAFE_HREF.test(href) ? href : undefined
+    }
+    return safeCss(value) ? value : undefined
+}
+
+const sanitizeAttributes = (source: string) => {
+    const kept: string[] = []
+    for (const [, rawName = '', double, single, bare] of source.matchAll(ATTRIBUTE)) {
+        const name = ALLOWED_ATTRIBUTES.get(rawName.toLowerCase())
+        if (!name || kept.some((attribute) => attribute.startsWith(`${name}=`))) continue
+        const value = attributeValue(name, decodeEntities(double ?? single ?? bare ?? ''))
+        if (value != null) kept.push(`${name}="${escapeAttribute(value)}"`)
+    }
+    return kept.map((attribute) => ` ${attribute}`).join('')
+}
+
+export function sanitizeSvg(markup: string) {
+    const start = markup.search(/<svg\b/i)
+    if (start === -1) return ''
+    const output: string[] = []
+    const open: string[] = []
+    // Depth inside a dropped element; everything up to its matching end tag goes with it.
+    let dropping = 0
+    for (const [token, endName, startName, attributes = '', selfClosing] of markup.slice(start).matchAll(TOKEN)) {
+        if (startName) {
+            const lower = startName.toLowerCase()
+            const name = ALLOWED_ELEMENTS.get(lower)
+            if (dropping || (!name && !UNWRAPPED.includes(lower))) {
+                if (!selfClosing) dropping++
+                continue
+            }
+            if (!name || (!open.length && name !== 'svg')) continue
+            output.push(`<${name}${sanitizeAttributes(attributes)}${selfClosing ? '/>' : '>'}`)
+            if (!selfClosing) open.push(name)
+        } else if (endName) {
+            if (dropping) {
+                dropping--
+                continue
+            }
+            const name = ALLOWED_ELEMENTS.get(endName.toLowerCase())
+            const depth = name ? open.lastIndexOf(name) : -1
+            if (depth === -1) continue
+            while (open.length > depth) output.push(`</${open.pop()}>`)
+            if (!open.length) break
+        } else if (!dropping && open.length && !token.startsWith('<!') && !token.startsWith('<?')) {
+            // Exported logos often style their paths with classes, so <style> stays, minus anything that fetches.
+            const text = decodeEntities(token)
+            if (open.at(-1) !== 'style' || safeCss(text)) output.push(escapeText(text))
+        }
+    }
+    while (open.length) output.push(`</${open.pop()}>`)
+    return output.join('')
+}
@@ -0,0 +1,68 @@
+import type { SpacingStep, Theme } from './types'
+
+export const DEFAULT_TYPOGRAPHY = {
+    letterSpacing: 0,
+    lineHeight: 1.25,
+    paragraphSpacing: 2,
+    justify: false,
+}
+
+// Every margin and gap between blocks is one of these steps, so density can change without touching component styles.
+export const DEFAULT_SPACING: Record<SpacingStep, number> = { xs: 2, s: 4, m: 6, l: 10, xl: 12 }
+
+const DENSITY = { compact: 0.75, default: 1, comfortable: 1.35 }
+
+// On a baseline grid every step becomes a whole number of half-lines, so gaps never push text off the grid.
+const snap = (value: number, grid?: number) => (grid ? Math.max(1, Math.round(value / (grid / 2))) * (grid / 2) : value)
+
+const spacingVariables = (theme?: Theme) => {
+    const scale = { ...DEFAULT_SPACING, ...theme?.spacing?.scale }
+    const factor = DENSITY[theme?.spacing?.density ?? 'default'] ?? 1
+    const grid = theme?.typography?.baselineGrid
+    return (Object.keys(DEFAULT_SPACING) as SpacingStep[])
+        .map((step) => `--rf-space-${step}: ${Math.round(snap(scale[step] * factor, grid) * 100) / 100}px;`)
+        .join('\n        ')
+}
+
+// Exposed as custom properties so component styles can scale relative to the theme instead of hard-coding values.
+export function themeVariables(theme?: Theme) {
+    const typography = { ...DEFAULT_TYPOGRAPHY, ...theme?.typography }
+    return `:root {
+        --rf-letter-spacing: ${typography.letterSpacing}em;
+        --rf-line-height: ${typography.lineHeight};
+        --rf-paragraph-spacing: ${typography.paragraphSpacing}px;
+        --rf-text-align: ${typography.justify ? 'justify' : 'left'};
+        --rf-brand-font: system-ui, -apple-system, Segoe UI, Roboto, sans-serif;
+        --rf-brand-primary: #111;
+        --rf-brand-accent: #047857;
+        ${spacingVariables(theme)}
+    }`
+}
+
+// Chrome shapes text with HarfBuzz, but "auto" kerning is skipped for some fonts and sizes and letter-spacing turns
+// ligatures off, which is what makes embedded display fonts look typewriter-spaced. Headings therefore drop the body
+// tracking while ligatures are on.
+export function shapingRules(theme?: Theme) {
+    const { kerning = true, ligatures = true } = theme?.typography ?? {}
+    return `
+    body{ font-kerning:${kerning ? 'normal' : 'none'}; font-variant-ligatures:${ligatures ? 'common-ligatures contextual' : 'none'}; text-rendering:${kerning || ligatures ? 'optimizeLegibility' : 'optimizeSpeed'}; }
+    ${ligatures ? 'h1, h2, h3, h4{ letter-spacing:normal; }' : ''}
+`
+}
+
+// Chrome has no line-height-step, so the grid is approximated: single lines are exactly one grid step, headings a
+// whole number of steps, and block padding is taken in half (cards) or quarter (table cells) steps, net of borders.
+export function baselineRules(theme?: Theme, debug?: boolean) {
+    const grid = theme?.typography?.baselineGrid
+    if (!grid) return ''
+    const lines = (fontSize: number) => Math.ceil((fontSize * 1.15) / grid) * grid
+    return `
+    body, p, li, td, th, blockquote, .summary-copy, .question-item p, .executive-excerpt, .footnotes{ line-height:${grid}px !important; }
+    h1{ line-height:${lines(16)}px; } h2{ line-height:${lines(14)}px; } h3{ line-height:${lines(12)}px; } h4{ line-height:${lines(11)}px; }
+    h1, h2, h3, h4{ margin-top:0 !important; margin-bottom:${grid / 2}px !important; }
+    p{ margin-top:0 !important; margin-bottom:${grid / 2}px !important; }
+    .kpi-card, .question-item, .executive-block, .block-card, .commentary, .form-fields{ padding-top:${grid / 2}px !important; padding-bottom:${grid / 2}px !important; }
+    td, th{ padding-top:${grid / 4}px !important; padding-bottom:calc(${grid / 4}px - 1px) !important; }
+    ${debug ? `.debug-margins{ background-image: repeating-linear-gradient(to bottom, transparent 0 ${grid - 0.5}px, rgba(14,165,233,0.35) ${grid - 0.5}px ${grid}px); }` : ''}
+`
+}
@@ -1,20 +1,200 @@
+export type KpiValueType = 'percent' | 'currency' | 'count' | 'ratio'
+
 export type KPI = {
     name: string
     value: number
     practice?: number
     delta?: number
+    previousValue?: number
+    valueType?: KpiValueType
+    // Currency code for `currency`, otherwise a suffix (e.g. "patients", "x").
+    unit?: string
+    // Bar scale; defaults to 0–100 for percentages and a rounded-up maximum otherwise.
+    scaleMin?: number
+    scaleMax?: number
+    // Month-to-month variability, shown as a band around the bar.
+    min?: number
+    max?: number
+    // Goal drawn as the bullet-graph tick; falls back to `practice`.
+    target?: number
+    // Percentile rank among benchmarked practices (0–100), captioned under the bar.
+    percentile?: number
+}
+
+export type KpiDisplay = 'gradient' | 'bullet'
+
+export type IconName = 'check' | 'warning' | 'arrow-up' | 'arrow-down' | 'arrow-right' | 'star'
+
+export type ImageFit = 'contain' | 'cover' | 'stretch'
+
+export type ImageAlign = 'start' | 'center' | 'end'
+
+export type ReportImage = {
+    svg?: string
+    src?: string
+    alt?: string
+    width?: number
+    height?: number
+    fit?: ImageFit
+    alignX?: ImageAlign
+    alignY?: ImageAlign
+    dpi?: number
+    // Spans the full text column, and with `printMarks` runs past the trim into the bleed on both sides.
+    fullBleed?: boolean
+}
+
+export type ChartPoint = { x: string; y: number }
+
+export type ChartSeries = {
+    name: string
+    points: ChartPoint[]
+    color?: string
+}
+
+// Horizontal reference line across the plot, e.g. the practice's production goal.
+export type ChartAnnotation = {
+    label: string
+    value: number
+    style?: 'solid' | 'dashed' | 'dotted'
+    color?: string
+}
+
+export type ChartSpec = {
+    type: 'line' | 'bar'
+    title?: string
+    series: ChartSeries[]
+    annotations?: ChartAnnotation[]
+    // Value labels on bars/points; positions are chosen automatically and colliding labels are dropped.
+    dataLabels?: boolean
+    // Longer series are downsampled (LTTB) to this many points; defaults to 240.
+    maxPoints?: number
+    yMin?: number
+    yMax?: number
+}
+
+export type TextAlign = 'left' | 'justify'
+
+// Bounds for auto-fitted table columns, as fractions of the table width.
+export type ColumnFit = { min?: number; max?: number }
+
+// Building blocks of a `blocks` document, rendered in order by a single dispatcher.
+export type HeadingBlock = { type: 'heading'; text: string; level?: 2 | 3 | 4; id?: string; icon?: IconName }
+
+// Same rich-text markup as section text: emphasis, links, cross-references and footnotes.
+export type ParagraphBlock = { type: 'paragraph'; text: string; align?: TextAlign }
+
+export type KpiGridBlock = { type: 'kpiGrid'; kpis: KPI[]; display?: KpiDisplay }
+
+export type TableCellValue = string | number | null
+
+// Caller-set styling for a single cell, e.g. red for a missed target; without a `color` the text switches to dark
+// or white for contrast with the background. Overrides format rules.
+export type StyledCell = { value: TableCellValue; background?: string; color?: string; bold?: boolean }
+
+export type TableCell = TableCellValue | StyledCell
+
+export type TableRow = TableCell[]
+
+export type TableBlock = {
+    type: 'table'
+    columns: string[]
+    rows: TableRow[]
+    caption?: string
+    // Column indexes set in tabular figures and right-aligned.
+    numericColumns?: number[]
+    // Size columns by their content instead of letting the browser share the width out evenly.
+    autoFit?: boolean | ColumnFit
+    // Tint every other row.
+    striped?: boolean
+    // Indented detail rows shown beneath a row, keyed by that row's index.
+    subRows?: Record<number, TableRow[]>
+}
+
+export type ChartBlock = { type: 'chart'; chart: ChartSpec; id?: string }
+
+// A shaded box grouping other blocks, kept on one page where possible.
+export type CardBlock = { type: 'card'; title?: string; tone?: 'neutral' | 'positive' | 'warning'; blocks: Block[] }
+
+// Vertical gap in millimetres; defaults to the theme's xl spacing step.
+export type SpacerBlock = { type: 'spacer'; height?: number }
+
+// Horizontal rule; `spacing` is the gap above and below it in millimetres, the theme's l step by default.
+export type DividerBlock = {
+    type: 'divider'
+    style?: 'solid' | 'dashed' | 'dotted'
+    color?: string
+    // Line weight in points.
+    thickness?: number
+    spacing?: number
+}
+
+export type ImageBlock = { type: 'image'; image: ReportImage }
+
+export type ChecklistItem = { text: string; checked?: boolean }
+
+// Action items with box glyphs; `strikeCompleted` also strikes through the checked ones.
+export type ChecklistBlock = { type: 'checklist'; title?: string; items: ChecklistItem[]; strikeCompleted?: boolean }
+
+// Pull quote or client testimonial; `attribution` and `role` make up the "— name, role" line below it.
+export type QuoteBlock = { type: 'quote'; text: string; attribution?: string; role?: string }
+
+// The consultant's details with tel:, mailto: and web links; `photo` is an optional headshot, cropped round.
+export type ContactCardBlock = {
+    type: 'contactCard'
+    name: string
+    title?: string
+    phone?: string
+    email?: string
+    website?: string
+    photo?: ReportImage
 }
 
+// Disclaimer in small type; wherever it appears in `blocks`, it is set at the very end of the report.
+export type LegalBlock = { type: 'legal'; text: string; title?: string }
+
+export type Milestone = { date: string; title: string; description?: string; status?: 'done' | 'current' | 'upcoming' }
+
+// Vertical roadmap: milestones in the given order, connected by a line through their nodes.
+export type TimelineBlock = { type: 'timeline'; title?: string; milestones: Milestone[] }
+
+export type Block =
+    | HeadingBlock
+    | ParagraphBlock
+    | KpiGridBlock
+    | TableBlock
+    | ChartBlock
+    | CardBlock
+    | SpacerBlock
+    | DividerBlock
+    | ImageBlock
+    | ChecklistBlock
+    | TimelineBlock
+    | QuoteBlock
+    | ContactCardBlock
+    | LegalBlock
+
+export type Orientation = 'portrait' | 'landscape'
+
 export type SectionGroup = 'question' | 'summary' | 'general'
 
 export type SectionOption = { id: string; text: string }
 
+export type SectionTranslation = { title?: string; text?: string }
+
 export type DraftSection = {
     id: string
     title: string
     options: SectionOption[]
     chartUrl?: string
+    chart?: ChartSpec
     group?: SectionGroup
+    icon?: IconName
+    image?: ReportImage
+    align?: TextAlign
+    // Landscape sections (general group only) get their own pages, e.g. for wide trend tables.
+    orientation?: Orientation
+    // Keyed by language code, used for the additional editions listed in DraftBundle.languages.
+    translations?: Record<string, SectionTranslation>
 }
 
 export type ReportSection = {
@@ -22,35 +202,213 @@ export type ReportSection = {
     title: string
     text: string
     chartUrl?: string
+    chart?: ChartSpec
     group?: SectionGroup
+    icon?: IconName
+    image?: ReportImage
+    align?: TextAlign
+    orientation?: Orientation
 }
 
 export type GrowthCategory = {
     id: string
     name: string
     score: number
+    // Uncertainty bounds on the score, drawn as whiskers on its bar; useful when few KPIs were scored.
+    scoreLow?: number
+    scoreHigh?: number
     explanation?: string
     confidence: number
     scored: number
     total: number
+    icon?: IconName
+    // Prior-period score; any category having one adds a Change column to the table.
+    previousScore?: number
+    // Parent group (e.g. Clinical, Operations, Marketing) shown as a band with a subtotal row.
+    group?: string
+    // Content for the category's drill-down page (see CategoryTableOptions.detailPages).
+    kpis?: KPI[]
+    narrative?: string
+    chart?: ReportImage
+}
+
+export type CategorySortKey = 'score' | 'confidence' | 'name'
+
+export type SortDirection = 'asc' | 'desc'
+
+export type ConfidenceDisplay = 'percent' | 'dots'
+
+export type CategoryTableOptions = {
+    sortBy?: CategorySortKey
+    direction?: SortDirection
+    // Group order; groups not listed follow in order of first appearance.
+    groups?: string[]
+    subtotals?: boolean
+    // Adds a page per category after the report body, linked from its table row.
+    detailPages?: boolean
+    confidence?: ConfidenceDisplay
+    // List each category's KPIs as indented rows beneath it.
+    kpiRows?: boolean
+    // Tint every other row.
+    striped?: boolean
 }
 
 export type SummaryDetail = {
     id: string
-    label: string
+    // Badge text; items without one follow DraftBundle.summaryBadges.
+    label?: string
     title: string
     sectionId?: string
     text?: string
     avgProfit?: number
+    icon?: IconName
+    badge?: BadgeStyle
+}
+
+// Overrides the default green badge, e.g. to encode severity; text turns dark or white for contrast with `fill`
+// unless `color` is given. `size` is the diameter in px (default 25).
+export type BadgeStyle = { fill?: string; color?: string; size?: number }
+
+// `table` rules match generic table blocks, with `field` naming a column header.
+export type RuleTarget = 'kpi' | 'category' | 'summary' | 'table'
+
+export type RuleOperator = '<' | '<=' | '>' | '>=' | '==' | '!='
+
+export type RuleStyle = {
+    color?: string
+    background?: string
+    bold?: boolean
+    italic?: boolean
+}
+
+export type FormatRule = {
+    target: RuleTarget
+    field?: string
+    operator: RuleOperator
+    value: number
+    style: RuleStyle
+    // `row` restyles the whole table row the item is shown in rather than just the matched value.
+    scope?: 'value' | 'row'
+}
+
+export type PageMargins = {
+    top?: number
+    right?: number
+    bottom?: number
+    left?: number
+}
+
+export type PrintMarks = {
+    bleed?: number
+    markLength?: number
+}
+
+export type Typography = {
+    letterSpacing?: number
+    lineHeight?: number
+    paragraphSpacing?: number
+    justify?: boolean
+    // Baseline grid in px (e.g. 12); line boxes and vertical gaps snap to it so text lines up across columns and cards.
+    baselineGrid?: number
+    // OpenType kerning and standard ligatures (fi, fl, ff), both on by default.
+    kerning?: boolean
+    ligatures?: boolean
+}
+
+export type SpacingStep = 'xs' | 's' | 'm' | 'l' | 'xl'
+
+export type Spacing = {
+    // Multiplies the whole scale: compact 0.75×, comfortable 1.35×.
+    density?: 'compact' | 'default' | 'comfortable'
+    // Individual steps in px, before the density is applied.
+    scale?: Partial<Record<SpacingStep, number>>
+}
+
+export type Theme = {
+    typography?: Typography
+    spacing?: Spacing
+}
+
+export type FormFieldType = 'text' | 'multiline' | 'checkbox'
+
+export type FormField = {
+    id: string
+    label: string
+    type?: FormFieldType
+    value?: string
+    checked?: boolean
+}
+
+// A band starts at `min` and runs up to the next band's `min`.
+export type ScoreBand = {
+    min: number
+    label: string
+    color: string
 }
 
 export type DraftBundle = {
     clientName: string
     date: string
+    logo?: ReportImage
     kpis: KPI[]
     sections: DraftSection[]
+    // Generic document body; when present it replaces the KPI grid and section layout, while the header,
+    // page setup, theme and branding still come from the fields above and below.
+    blocks?: Block[]
     growthCategories?: GrowthCategory[]
+    categoryTable?: CategoryTableOptions
     summaryDetails?: SummaryDetail[]
+    // Badges for summary items without a label or icon: a bullet (default), the item's position, or a check icon.
+    summaryBadges?: 'bullet' | 'number' | 'icon'
+    rules?: FormatRule[]
+    executiveSummary?: boolean
+    margins?: PageMargins
+    duplex?: boolean
+    sectionsStartRight?: boolean
+    printMarks?: PrintMarks
+    grayscale?: boolean
+    theme?: Theme
+    numberHeadings?: boolean
+    tableOfContents?: boolean
+    runningHeaders?: boolean
+    debug?: boolean
+    // Tenant id resolved by the PDF service; the stored profile's colors, fonts, logo and footer override the payload.
+    brandProfile?: string
+    // Embed the bundle itself (json) or its KPIs (csv) in the PDF as a file attachment.
+    attachSource?: 'json' | 'csv'
+    formFields?: FormField[]
+    scoreLegend?: boolean
+    scoreBands?: ScoreBand[]
+    // Prints the score band's label (e.g. "Developing") after each percentage KPI and category score bar.
+    bandLabels?: boolean
+    // Data rows that must fit under a table header before the page may break (default 2).
+    tableMinRows?: number
+    // Lines of a paragraph that must stay together on either side of a page break (orphans and widows, default 2).
+    keepLines?: number
+    // BCP 47 codes; with more than one, the report is rendered once per language in a single PDF, primary first.
+    languages?: string[]
+    kpiDisplay?: KpiDisplay
+    // Page budget; the PDF service shrinks type and spacing (down to 80%) until the report fits.
+    fitToPages?: number
+    // Label cards that break across pages with "continued →" / "← continued"; the PDF service paginates such reports itself.
+    continuationMarkers?: boolean
+    // Mask emails, phone numbers and `redaction` matches in all narrative text, e.g. for sample reports.
+    redact?: boolean
+    redaction?: RedactionRules
+    // Appends the standard methodology appendix (how scores and confidence work, plus a glossary) as its own page.
+    includeMethodology?: boolean
+    // What to do with emoji in the text; see EmojiPolicy. The PDF service reports the outcome as a render warning.
+    emoji?: EmojiPolicy
+}
+
+// `strip` removes them, `shortcode` writes e.g. :rocket:, `font` (default) draws them with a color emoji font.
+export type EmojiPolicy = 'strip' | 'shortcode' | 'font'
+
+export type RedactionRules = {
+    // Regular expressions (case-insensitive) masked in addition to emails and phone numbers.
+    patterns?: string[]
+    // Literal words or names, e.g. patient names, matched on word boundaries.
+    terms?: string[]
 }
 
 export type SectionSelection = Record<string, string>
@@ -59,4 +417,6 @@ export type DrilldownTable = {
     title?: string
     columns: string[]
     rows: string[][]
+    // Size columns by their content instead of the fixed per-column widths.
+    autoFit?: boolean | ColumnFit
 }
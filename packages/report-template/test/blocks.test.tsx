@@ -0,0 +1,335 @@
+import { describe, expect, test } from 'bun:test'
+import { renderReport } from '../src/render'
+import type { DraftBundle } from '../src/types'
+
+const bundle: DraftBundle = {
+    clientName: 'Smile Co',
+    date: 'Q3 2026',
+    kpis: [{ name: 'Legacy KPI', value: 10 }],
+    sections: [],
+    tableOfContents: true,
+    blocks: [
+        { type: 'heading', text: 'Overview', id: 'overview' },
+        { type: 'paragraph', text: 'Production grew this quarter.' },
+        { type: 'kpiGrid', kpis: [{ name: 'Case Acceptance', value: 62 }] },
+        { type: 'heading', text: 'Detail', id: 'detail', level: 3 },
+        { type: 'table', columns: ['Provider', 'Visits'], rows: [['Dr. Lee', 120], ['Dr. Kim', null]], numericColumns: [1] },
+        { type: 'spacer', height: 6 },
+        { type: 'divider', style: 'dashed', thickness: 1, spacing: 5 },
+        { type: 'card', title: 'Next steps', tone: 'positive', blocks: [{ type: 'paragraph', text: 'Call back reminder@smile.example.' }] },
+    ],
+}
+
+describe('blocks documents', () => {
+    test('render every block in order instead of the legacy layout', () => {
+        const html = renderReport(bundle)
+        const order = ['id="overview"', 'Production grew', 'Case Acceptance', 'id="detail"', 'block-table', 'block-spacer', 'block-divider', 'block-card positive']
+        const positions = order.map((marker) => html.indexOf(marker))
+        expect(positions.every((position) => position > 0)).toBe(true)
+        expect([...positions].sort((a, b) => a - b)).toEqual(positions)
+        expect(html).not.toContain('Legacy KPI')
+        expect(html).toContain('data-ignored-fields="kpis"')
+        expect(html).not.toContain('Zero Risk')
+        expect(html).toContain('<td class="numeric">120</td>')
+        expect(html).toContain('height:6mm')
+        expect(html).toContain('border-top:1pt dashed #e2e8f0;margin:5mm 0')
+    })
+
+    test('refuse ids that collide with the report's own anchors', () => {
+        expect(() => renderReport({ ...bundle, blocks: [{ type: 'heading', text: 'Method', id: 'methodology' }] })).toThrow('reserved')
+        const section = { id: 'category-recall', title: 'Recall', options: [{ id: 'a', text: 'Recall is steady.' }] }
+        expect(() => renderReport({ ...bundle, blocks: undefined, sections: [section] })).toThrow('"category-recall" is reserved')
+    })
+
+    test('headings with ids feed the table of contents', () => {
+        const html = renderReport(bundle)
+        expect(html).toContain('href="#overview"')
+        expect(html).toContain('href="#detail"')
+    })
+
+    test('redaction reaches nested blocks', () => {
+        const html = renderReport({ ...bundle, redact: true })
+        expect(html).not.toContain('reminder@smile.example')
+    })
+
+    test('legacy bundles keep their layout', () => {
+        const html = renderReport({ ...bundle, blocks: undefined })
+        expect(html).toContain('Legacy KPI')
+        expect(html).not.toContain('block-table')
+    })
+})
+
+describe('continuation markers', () => {
+    test('switch the report to paged layout and style split cards', () => {
+        const plain = { ...bundle, tableOfContents: false }
+        const html = renderReport({ ...plain, continuationMarkers: true })
+        expect(html).toContain('data-paged="true"')
+        expect(html).toContain("content:'continued →'")
+        expect(renderReport(plain)).not.toContain('data-paged')
+    })
+})
+
+describe('row rules and striping', () => {
+    test('highlight whole rows of block tables by column value', () => {
+        const html = renderReport({
+            ...bundle,
+            blocks: [{ type: 'table', columns: ['Provider', 'Visits'], rows: [['Dr. Lee', 120], ['Dr. Kim', 30]], striped: true }],
+            rules: [
+                { target: 'table', field: 'Visits', operator: '<', value: 40, scope: 'row', style: { background: '#fef2f2' } },
+                { target: 'table', field: 'Visits', operator: '>', value: 100, style: { bold: true } },
+            ],
+        })
+        expect(html).toContain('block-table striped')
+        expect(html).toContain('<tr class="even" style="background-color:#fef2f2"><td>Dr. Kim</td>')
+        expect(html).toContain('<td style="font-weight:700">120</td>')
+        expect(html).toContain('<tr><td>Dr. Lee</td>')
+    })
+
+    test('highlight categories below a score in the category table', () => {
+        const html = renderReport({
+            ...bundle,
+            blocks: undefined,
+            growthCategories: [
+                { id: 'a', name: 'Hygiene', score: 72, confidence: 80, scored: 4, total: 5 },
+                { id: 'b', name: 'Recall', score: 35, confidence: 60, scored: 3, total: 5 },
+            ],
+            categoryTable: { striped: true },
+            rules: [{ target: 'category', operator: '<', value: 40, scope: 'row', style: { background: '#fef2f2' } }],
+        })
+        expect(html).toContain('category-table striped')
+        const highlighted = html.match(/<tr class="even" style="background-color:#fef2f2">.*?<\/tr>/g) ?? []
+        expect(highlighted).toHaveLength(1)
+        expect(highlighted[0]).toContain('ecall')
+    })
+
+    test('stripe parent rows regardless of the sub-rows between them', () => {
+        const html = renderReport({
+            ...bundle,
+            blocks: [
+                {
+                    type: 'table',
+                    columns: ['Provider', 'Visits'],
+                    rows: [['Dr. Lee', 120], ['Dr. Kim', 80], ['Dr. Roe', 60]],
+                    subRows: { 0: [['Hygiene', 70]] },
+                    striped: true,
+                },
+            ],
+        })
+        expect(html).toContain('<tr><td>Dr. Lee</td>')
+        expect(html).toContain('<tr class="sub-row"><td>Hygiene</td>')
+        expect(html).toContain('<tr class="even"><td>Dr. Kim</td>')
+        expect(html).toContain('<tr><td>Dr. Roe</td>')
+    })
+})
+
+describe('nested rows', () => {
+    test('render sub-rows beneath their parent row', () => {
+        const html = renderReport({
+            ...bundle,
+            blocks: [
+                {
+                    type: 'table',
+                    columns: ['Provider', 'Visits'],
+                    rows: [['Dr. Lee', 120], ['Dr. Kim', 80]],
+                    subRows: { 0: [['Hygiene', 70], ['Restorative', 50]] },
+                },
+            ],
+        })
+        const order = ['Dr. Lee', 'class="sub-row"><td>Hygiene', 'class="sub-row"><td>Restorative', 'Dr. Kim']
+        const positions = order.map((marker) => html.indexOf(marker))
+        expect(positions.every((position) => position > 0)).toBe(true)
+        expect([...positions].sort((a, b) => a - b)).toEqual(positions)
+        expect(html.match(/class="sub-row"/g)).toHaveLength(2)
+    })
+
+    test('list category KPIs under their category', () => {
+        const categories = [{ id: 'a', name: 'Recall', score: 35, confidence: 60, scored: 3, total: 5, kpis: [{ name: 'Reappointment Rate', value: 48 }] }]
+        const html = renderReport({ ...bundle, blocks: undefined, growthCategories: categories, categoryTable: { kpiRows: true } })
+        expect(html).toContain('<tr class="category-kpi"><td>Reappointment Rate</td><td>48%</td>')
+        expect(renderReport({ ...bundle, blocks: undefined, growthCategories: categories })).not.toContain('category-kpi"')
+    })
+})
+
+describe('styled cells', () => {
+    test('apply caller colors and pick a readable text color', () => {
+        const html = renderReport({
+            ...bundle,
+            blocks: [
+                {
+                    type: 'table',
+                    columns: ['Provider', 'Visits'],
+                    rows: [
+                        ['Dr. Lee', { value: 30, background: '#b91c1c' }],
+                        ['Dr. Kim', { value: 'On target', background: '#dcfce7', color: '#065f46', bold: true }],
+                    ],
+                },
+            ],
+        })
+        expect(html).toContain('<td style="background-color:#b91c1c;color:#fff">30</td>')
+        expect(html).toContain('<td style="background-color:#dcfce7;color:#065f46;font-weight:700">On target</td>')
+    })
+})
+
+describe('checklist blocks', () => {
+    const checklist: DraftBundle = {
+        ...bundle,
+        blocks: [
+            {
+                type: 'checklist',
+                title: 'Action items from last quarter',
+                items: [{ text: 'Launch recall campaign', checked: true }, { text: 'Email dr.kim@smile.example about hygiene hours' }],
+                strikeCompleted: true,
+            },
+        ],
+    }
+
+    test('draw a box per item and mark completed ones', () => {
+        const html = renderReport(checklist)
+        expect(html).toContain('class="block-checklist strike"')
+        expect(html).toContain('<h4>Action items from last quarter</h4>')
+        expect(html.match(/<li class="done">/g)).toHaveLength(1)
+        expect(html.match(/<rect/g)).toHaveLength(2)
+        expect(html.match(/<path d="M3 6.2/g)).toHaveLength(1)
+    })
+
+    test('redact item text', () => {
+        expect(renderReport({ ...checklist, redact: true })).not.toContain('dr.kim@smile.example')
+    })
+})
+
+describe('timeline blocks', () => {
+    const timeline: DraftBundle = {
+        ...bundle,
+        blocks: [
+            {
+                type: 'timeline',
+                title: '12-month growth plan',
+                milestones: [
+                    { date: 'Jan 2027', title: 'Recall campaign', status: 'done' },
+                    { date: 'Apr 2027', title: 'Second hygienist', description: 'Hire and onboard.', status: 'current' },
+                    { date: 'Oct 2027', title: 'Review with Jane Roe' },
+                ],
+            },
+        ],
+    }
+
+    test('list milestones in order with their status', () => {
+        const html = renderReport(timeline)
+        const order = ['Jan 2027', 'Recall campaign', 'Apr 2027', 'Hire and onboard.', 'Oct 2027']
+        const positions = order.map((marker) => html.indexOf(marker))
+        expect([...positions].sort((a, b) => a - b)).toEqual(positions)
+        expect(html).toContain('<li class="done"><div class="milestone-date">Jan 2027</div>')
+        expect(html).toContain('<li class="current">')
+        expect(html).toContain('<li><div class="milestone-date">Oct 2027</div>')
+    })
+
+    test('redact milestone text', () => {
+        expect(renderReport({ ...timeline, redact: true, redaction: { terms: ['Jane Roe'] } })).not.toContain('Jane Roe')
+    })
+})
+
+describe('quote blocks', () => {
+    test('typeset testimonials with an attribution line', () => {
+        const html = renderReport({
+            ...bundle,
+            blocks: [{ type: 'quote', text: 'Our schedule has never been fuller.', attribution: 'Dr. Lee', role: 'Smile Co' }],
+        })
+        expect(html).toContain('<blockquote class="quote-block"><p class="quote-text">Our schedule has never been fuller.</p>')
+        expect(html).toContain('<p class="quote-attribution">— Dr. Lee<span class="quote-role">, Smile Co</span></p>')
+    })
+
+    test('the legacy closing quote is signed', () => {
+        const html = renderReport({ ...bundle, blocks: undefined })
+        expect(html).toContain('<span class="quote-signature">Shawn Rowbotham</span>')
+        expect(html).not.toContain('&quot;We love helping')
+    })
+})
+
+describe('contact card blocks', () => {
+    const contact: DraftBundle = {
+        ...bundle,
+        blocks: [
+            {
+                type: 'contactCard',
+                name: 'Alex Rivera',
+                title: 'Growth Consultant',
+                phone: '+1 (555) 010-2030',
+                email: 'alex@growth.example',
+                website: 'growth.example/alex/',
+                photo: { src: 'https://cdn.example/alex.jpg', alt: 'Alex Rivera' },
+            },
+        ],
+    }
+
+    test('link phone, email and website', () => {
+        const html = renderReport(contact)
+        expect(html).toContain('<p class="contact-name">Alex Rivera</p>')
+        expect(html).toContain('href="tel:+15550102030"')
+        expect(html).toContain('href="mailto:alex@growth.example"')
+        expect(html).toContain('<a href="https://growth.example/alex/">growth.example/alex</a>')
+        expect(html).toContain('contact-photo')
+    })
+
+    test('dial extensions as an ext parameter', () => {
+        const html = renderReport({ ...contact, blocks: [{ type: 'contactCard', name: 'Alex Rivera', phone: '(555) 010-2030 ext. 4' }] })
+        expect(html).toContain('href="tel:5550102030;ext=4"')
+    })
+
+    test('redact contact details in sample reports', () => {
+        const html = renderReport({ ...contact, redact: true })
+        expect(html).not.toContain('alex@growth.example')
+        expect(html).not.toContain('010-2030')
+        const named = renderReport({ ...contact, redact: true, redaction: { terms: ['Alex', 'Rivera'] } })
+        expect(named).not.toContain('Rivera')
+        expect(named).not.toContain('alex/')
+    })
+})
+
+describe('footnotes', () => {
+    test('number on through cards, checklists and quotes', () => {
+        const html = renderReport({
+            ...bundle,
+            blocks: [
+                { type: 'paragraph', text: 'Production grew[^Net of refunds.].' },
+                { type: 'card', blocks: [{ type: 'checklist', items: [{ text: 'Call lapsed patients[^Over 18 months.]' }] }] },
+                { type: 'quote', text: 'Best quarter yet[^Internal survey.]' },
+            ],
+        })
+        expect(html.match(/<ol class="footnotes" start="\d+">/g)).toEqual([
+            '<ol class="footnotes" start="1">',
+            '<ol class="footnotes" start="2">',
+            '<ol class="footnotes" start="3">',
+        ])
+        expect(html).toContain('Best quarter yet<span class="rt-footnote-ref">3</span>')
+    })
+})
+
+describe('full-bleed images', () => {
+    const image: DraftBundle = { ...bundle, blocks: [{ type: 'image', image: { src: 'https://cdn.example/office.jpg', fullBleed: true } }] }
+
+    test('run into the bleed when print marks are on', () => {
+        const html = renderReport({ ...image, printMarks: { bleed: 3 } })
+        expect(html).toContain('class="report-image full-bleed section-image"')
+        expect(html).toMatch(/\.report-image\.full-bleed \{ display: block; width: calc\(100% \+ \([^)]*3mm\)/)
+    })
+
+    test('span the text column without them', () => {
+        expect(renderReport(image)).toContain('.report-image.full-bleed { display: block; width: 100%; }')
+    })
+})
+
+describe('legal blocks', () => {
+    test('are set after everything else, one paragraph per clause', () => {
+        const html = renderReport({
+            ...bundle,
+            includeMethodology: true,
+            blocks: [
+                { type: 'legal', title: 'Disclaimer', text: 'Projections are estimates.\n\nPast results do not\nguarantee future results.' },
+                { type: 'paragraph', text: 'Production grew this quarter.' },
+            ],
+        })
+        expect(html).toContain('<section class="legal" aria-label="Disclaimer"><h4>Disclaimer</h4><p>Projections are estimates.</p><p>Past results do not guarantee future results.</p></section>')
+        expect(html.indexOf('class="legal"')).toBeGreaterThan(html.indexOf('Production grew'))
+        expect(html.indexOf('class="legal"')).toBeGreaterThan(html.indexOf('id="methodology"'))
+    })
+})
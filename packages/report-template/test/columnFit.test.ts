@@ -0,0 +1,33 @@
+import { describe, expect, test } from 'bun:test'
+import fc from 'fast-check'
+import { autoFitColumns } from '../src/columnFit'
+
+const sum = (values: number[]) => values.reduce((total, value) => total + value, 0)
+
+describe('autoFitColumns', () => {
+    test('gives wider content a wider column', () => {
+        const [name, notes, score] = autoFitColumns(['Name', 'Notes', 'Score'], [['Dr. Lee', 'Follow up on the hygiene recall backlog', 92]])
+        expect(notes!).toBeGreaterThan(name!)
+        expect(name!).toBeGreaterThan(score!)
+    })
+
+    test('widths fill the table and respect the bounds', () => {
+        fc.assert(
+            fc.property(
+                fc.array(fc.string({ maxLength: 12 }), { minLength: 1, maxLength: 8 }),
+                fc.array(fc.array(fc.string({ maxLength: 80 }), { maxLength: 8 }), { maxLength: 20 }),
+                (columns, rows) => {
+                    const widths = autoFitColumns(columns, rows, { min: 0.05, max: 0.5 })
+                    expect(widths).toHaveLength(columns.length)
+                    expect(sum(widths)).toBeCloseTo(1, 6)
+                    const floor = Math.min(0.05, 1 / columns.length)
+                    const ceiling = Math.max(0.5, 1 / columns.length)
+                    widths.forEach((width) => {
+                        expect(width).toBeGreaterThanOrEqual(floor - 1e-9)
+                        expect(width).toBeLessThanOrEqual(ceiling + 1e-9)
+                    })
+                }
+            )
+        )
+    })
+})
@@ -0,0 +1,16 @@
+import { describe, expect, test } from 'bun:test'
+import { parseColor, readableTextColor } from '../src/contrast'
+
+describe('readableTextColor', () => {
+    test('picks white on dark and dark on light backgrounds', () => {
+        expect(readableTextColor('#b91c1c')).toBe('#fff')
+        expect(readableTextColor('#0f172a')).toBe('#fff')
+        expect(readableTextColor('#fef2f2')).toBe('#111')
+        expect(readableTextColor('rgb(250, 204, 21)')).toBe('#111')
+    })
+
+    test('leaves colors it cannot parse alone', () => {
+        expect(readableTextColor('tomato')).toBeUndefined()
+        expect(parseColor('#abc')).toEqual([170, 187, 204])
+    })
+})
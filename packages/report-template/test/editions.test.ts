@@ -0,0 +1,9 @@
+import { describe, expect, test } from 'bun:test'
+import { languageName } from '../src/editions'
+
+describe('edition labels', () => {
+    test('name the language in itself, or fall back to the raw tag when it is malformed', () => {
+        expect(languageName('fr')).toBe('Français')
+        expect(languageName('en_US')).toBe('en_US')
+    })
+})
@@ -0,0 +1,55 @@
+import { describe, expect, test } from 'bun:test'
+import { emojiMarkup } from '../src/emoji'
+import { renderReport } from '../src/render'
+import type { DraftBundle } from '../src/types'
+
+const page = (body: string) => `<!doctype html><html lang="en"><head></head><body>${body}</body></html>`
+
+describe('emoji policy', () => {
+    test('strip removes emoji without leaving stray spaces', () => {
+        const html = emojiMarkup(page('<p>Great quarter 🎉! Keep going 🚀🚀 team</p><p>👍🏽 Nice</p>'), 'strip')
+        expect(html).toContain('<p>Great quarter! Keep going team</p><p>Nice</p>')
+        expect(html).toContain('data-emoji="strip" data-emoji-count="4"')
+    })
+
+    test('shortcode writes known emoji as text', () => {
+        const html = emojiMarkup(page('<p>Recall 📈 ⚠️ and 👍🏽</p>'), 'shortcode')
+        expect(html).toContain('<p>Recall :chart_with_upwards_trend: :warning: and :+1:</p>')
+    })
+
+    test('shortcode counts the emoji it had to drop', () => {
+        const html = emojiMarkup(page('<p>New patients 🦄 up 📈</p>'), 'shortcode')
+        expect(html).toContain('<p>New patients  up :chart_with_upwards_trend:</p>')
+        expect(html).toContain('data-emoji-count="2" data-emoji-dropped="1"')
+    })
+
+    test('only rewrites text, not attributes, styles or scripts', () => {
+        const markup = '<p title="🚀 launch"><style>.x::before{content:"🚀"}</style><script>const s = "🚀"</script>Go 🚀</p>'
+        const html = emojiMarkup(page(markup), 'strip')
+        expect(html).toContain('<p title="🚀 launch"><style>.x::before{content:"🚀"}</style><script>const s = "🚀"</script>Go</p>')
+        expect(html).toContain('data-emoji-count="1"')
+    })
+
+    test('font keeps emoji and adds the color emoji fallback', () => {
+        const html = emojiMarkup(page('<p>Launch 🚀</p>'))
+        expect(html).toContain('Launch 🚀')
+        expect(html).toContain("'Noto Color Emoji'")
+        expect(html).toContain('data-emoji="font"')
+    })
+
+    test('text symbols and emoji-free reports are untouched', () => {
+        const markup = page('<p>© 2026 ▶ +12 → ✓</p>')
+        expect(emojiMarkup(markup, 'strip')).toBe(markup)
+    })
+
+    test('applies to the rendered report', () => {
+        const bundle: DraftBundle = {
+            clientName: 'Smile Co',
+            date: 'Q3 2026',
+            kpis: [],
+            sections: [{ id: 's1', title: 'Summary', options: [{ id: 'o1', text: 'Production is up 🔥 this quarter.' }] }],
+            emoji: 'shortcode',
+        }
+        expect(renderReport(bundle)).toContain('Production is up :fire: this quarter.')
+    })
+})
@@ -0,0 +1,22 @@
+import { describe, expect, test } from 'bun:test'
+import { grayscaleMarkup } from '../src/grayscale'
+
+describe('grayscale', () => {
+    test('converts colors in declarations and paint attributes, keeping alpha', () => {
+        const html = grayscaleMarkup(
+            '<style>.a{ color:#ff0000; border:1px solid #00ff0080; background:rgb(0 0 255 / 50%); }</style>' +
+                '<p style="color:rgba(255, 0, 0, 0.5)">x</p><rect fill="#0f0" stroke="#f008"/>'
+        )
+        expect(html).toBe(
+            '<style>.a{ color:#363636; border:1px solid #b6b6b680; background:rgba(18,18,18,50%); }</style>' +
+                '<p style="color:rgba(54,54,54,0.5)">x</p><rect fill="#b6b6b6" stroke="#36363688"/>'
+        )
+    })
+
+    test('leaves fragment references, selectors, strings and text alone', () => {
+        const html =
+            '<style>#add, a[href="#abc123"]{ fill:url(#abc); content:"#100"; }</style>' +
+            '<rect fill="url(#beef)"/><a href="#abc123">#add</a><p>Ticket #100</p>'
+        expect(grayscaleMarkup(html)).toBe(html)
+    })
+})
@@ -0,0 +1,33 @@
+import { describe, expect, test } from 'bun:test'
+import { formatKpiValue, percentileCaption } from '../src/kpi'
+import { renderReport } from '../src/render'
+
+describe('percentile captions', () => {
+    test('name the top share for the upper half and the share behind for the lower half', () => {
+        expect(percentileCaption(85)).toBe('Top 15% of practices')
+        expect(percentileCaption(99.6)).toBe('Top 1% of practices')
+        expect(percentileCaption(50)).toBe('Top 50% of practices')
+        expect(percentileCaption(32)).toBe('Ahead of 32% of practices')
+    })
+
+    test('render under the KPI bar when the KPI has a percentile', () => {
+        const html = renderReport({
+            clientName: 'Smile Co',
+            date: 'Q3 2026',
+            kpis: [
+                { name: 'Case Acceptance', value: 62, percentile: 85 },
+                { name: 'Hygiene Reappointment', value: 71 },
+            ],
+            sections: [],
+        })
+        expect(html).toContain('<p class="kpi-percentile">Top 15% of practices</p>')
+        expect(html.match(/class="kpi-percentile"/g)).toHaveLength(1)
+    })
+})
+
+describe('currency values', () => {
+    test('fall back to a suffix for units that are not currency codes', () => {
+        expect(formatKpiValue({ name: 'Production', value: 1250, valueType: 'currency', unit: 'EUR' }, 1250)).toBe('€1,250')
+        expect(formatKpiValue({ name: 'Production', value: 12.5, valueType: 'currency', unit: 'credits' }, 12.5)).toBe('12.5 credits')
+    })
+})
@@ -0,0 +1,128 @@
+import { describe, expect, test } from 'bun:test'
+import fc from 'fast-check'
+import { renderToStaticMarkup } from 'react-dom/server'
+import { labelBox, placeLabels } from '../src/chartLabels'
+import { Chart } from '../src/components/Chart'
+import { downsample } from '../src/downsample'
+import { kpiFraction } from '../src/kpi'
+import type { ChartSpec, KPI } from '../src/types'
+
+const finite = fc.double({ min: -1e6, max: 1e6, noNaN: true, noDefaultInfinity: true })
+
+const chartSpec: fc.Arbitrary<ChartSpec> = fc.record({
+    type: fc.constantFrom('line' as const, 'bar' as const),
+    title: fc.string(),
+    series: fc.array(
+        fc.record({
+            name: fc.string({ minLength: 1 }),
+            points: fc.uniqueArray(fc.record({ x: fc.string({ minLength: 1, maxLength: 12 }), y: finite }), {
+                selector: (point) => point.x,
+                maxLength: 400,
+            }),
+        }),
+        { minLength: 1, maxLength: 4, selector: (series) => series.name }
+    ),
+    annotations: fc.option(fc.array(fc.record({ label: fc.string({ maxLength: 20 }), value: finite }), { maxLength: 3 }), {
+        nil: undefined,
+    }),
+    dataLabels: fc.boolean(),
+    maxPoints: fc.option(fc.integer({ min: 0, max: 300 }), { nil: undefined }),
+    // Zero on both sides makes empty ranges common alongside inverted ones.
+    yMin: fc.option(fc.oneof(finite, fc.constant(0)), { nil: undefined }),
+    yMax: fc.option(fc.oneof(finite, fc.constant(0)), { nil: undefined }),
+}) as fc.Arbitrary<ChartSpec>
+
+// Every number that ends up in the SVG: rect geometry, polyline points and text anchors.
+const svgNumbers = (markup: string) =>
+    [...markup.matchAll(/\s(?:x|y|x1|x2|y1|y2|width|height|points)="([^"]*)"/g)].flatMap((match) =>
+        match[1]!.split(/[\s,]+/).filter(Boolean).map(Number)
+    )
+
+// Vertical positions only: y attributes, bar heights and the second number of each polyline point.
+const svgHeights = (markup: string) => [
+    ...[...markup.matchAll(/\s(?:y|y1|y2|height)="([^"]*)"/g)].map((match) => Number(match[1])),
+    ...[...markup.matchAll(/\spoints="([^"]*)"/g)].flatMap((match) =>
+        match[1]!.split(' ').filter(Boolean).map((point) => Number(point.split(',')[1]))
+    ),
+]
+
+// Chart.tsx's viewBox height.
+const CHART_HEIGHT = 180
+
+describe('layout invariants', () => {
+    test('chart labels stay inside the plot and never overlap', () => {
+        const bounds = { left: 0, right: 400, top: 0, bottom: 200 }
+        const candidate = fc.record({
+            text: fc.string({ maxLength: 16 }),
+            x: fc.double({ min: -50, max: 450, noNaN: true }),
+            y: fc.double({ min: -50, max: 250, noNaN: true }),
+            bar: fc.option(fc.record({ width: fc.double({ min: 0, max: 80, noNaN: true }), height: fc.double({ min: 0, max: 200, noNaN: true }) }), {
+                nil: undefined,
+            }),
+        })
+        fc.assert(
+            fc.property(fc.array(candidate, { maxLength: 60 }), (candidates) => {
+                const boxes = placeLabels(candidates, bounds).map(labelBox)
+                for (const [idx, box] of boxes.entries()) {
+                    expect(box.left).toBeGreaterThanOrEqual(bounds.left)
+                    expect(box.right).toBeLessThanOrEqual(bounds.right)
+                    expect(box.top).toBeGreaterThanOrEqual(bounds.top)
+                    expect(box.bottom).toBeLessThanOrEqual(bounds.bottom)
+                    for (const other of boxes.slice(idx + 1)) {
+                        const overlap = box.left < other.right && other.left < box.right && box.top < other.bottom && other.top < box.bottom
+                        expect(overlap).toBe(false)
+                    }
+                }
+            })
+        )
+    })
+
+    test('charts draw only finite coordinates', () => {
+        fc.assert(
+            fc.property(chartSpec, (chart) => {
+                for (const value of svgNumbers(renderToStaticMarkup(<Chart chart={chart} />))) expect(Number.isFinite(value)).toBe(true)
+            }),
+            { numRuns: 200 }
+        )
+    })
+
+    test('charts stay inside their viewBox whatever y-range is asked for', () => {
+        fc.assert(
+            fc.property(chartSpec, (chart) => {
+                for (const value of svgHeights(renderToStaticMarkup(<Chart chart={chart} />))) {
+                    expect(value).toBeGreaterThanOrEqual(0)
+                    expect(value).toBeLessThanOrEqual(CHART_HEIGHT)
+                }
+            }),
+            { numRuns: 200 }
+        )
+    })
+
+    test('downsampling keeps the endpoints and order and respects the target', () => {
+        const points = fc.array(fc.record({ x: fc.string(), y: finite }), { maxLength: 2000 })
+        fc.assert(
+            fc.property(points, fc.integer({ min: 0, max: 500 }), (series, target) => {
+                const sampled = downsample(series, target)
+                if (target >= 3 && series.length > target) expect(sampled.length).toBe(target)
+                if (series.length) {
+                    expect(sampled[0]).toBe(series[0])
+                    expect(sampled.at(-1)).toBe(series.at(-1))
+                }
+                const indexes = sampled.map((point) => series.indexOf(point))
+                expect(indexes).toEqual([...indexes].sort((a, b) => a - b))
+            })
+        )
+    })
+
+    test('KPI bar fractions are always finite', () => {
+        const kpi: fc.Arbitrary<KPI> = fc.record({
+            name: fc.string(),
+            value: finite,
+            practice: fc.option(finite, { nil: undefined }),
+            valueType: fc.option(fc.constantFrom('percent' as const, 'currency' as const, 'count' as const, 'ratio' as const), { nil: undefined }),
+            scaleMin: fc.option(finite, { nil: undefined }),
+            scaleMax: fc.option(finite, { nil: undefined }),
+        })
+        fc.assert(fc.property(kpi, (value) => expect(Number.isFinite(kpiFraction(value))).toBe(true)))
+    })
+})
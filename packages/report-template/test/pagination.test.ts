@@ -0,0 +1,24 @@
+import { describe, expect, test } from 'bun:test'
+import { paginationRules } from '../src/pagination'
+
+describe('pagination rules', () => {
+    test('keeps headings with what follows and two lines of a paragraph together by default', () => {
+        const css = paginationRules({})
+        expect(css).toContain('h1,h2,h3,h4,caption,.block-card > h4{ break-after:avoid; }')
+        expect(css).toContain('orphans:2; widows:2;')
+        expect(css).toContain('table thead{ break-after:avoid; }')
+        expect(css).toContain('tr:nth-child(-n+1){ break-after:avoid; }')
+    })
+
+    test('follows the configured line and row minimums', () => {
+        const css = paginationRules({ keepLines: 3, tableMinRows: 3 })
+        expect(css).toContain('orphans:3; widows:3;')
+        expect(css).toContain('table tbody:first-of-type tr:nth-child(-n+2){ break-after:avoid; }')
+    })
+
+    test('drops the row rule for a single-row minimum and never asks for fewer than one line', () => {
+        const css = paginationRules({ keepLines: 0, tableMinRows: 1 })
+        expect(css).toContain('orphans:1; widows:1;')
+        expect(css).not.toContain('nth-child')
+    })
+})
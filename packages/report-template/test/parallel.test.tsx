@@ -0,0 +1,32 @@
+import { describe, expect, test } from 'bun:test'
+import { renderReport, renderReportParallel } from '../src/render'
+import type { ChartSpec, DraftBundle } from '../src/types'
+
+const chart = (name: string): ChartSpec => ({
+    type: 'line',
+    dataLabels: true,
+    series: [{ name, points: Array.from({ length: 400 }, (_, idx) => ({ x: `W${idx}`, y: Math.sin(idx / 9) * 50 + idx })) }],
+})
+
+const base: DraftBundle = { clientName: 'Smile Dental', date: 'Q3 2026', kpis: [], sections: [] }
+
+describe('parallel rendering', () => {
+    test('renders section charts byte-for-byte like the inline path', async () => {
+        const bundle: DraftBundle = {
+            ...base,
+            sections: ['production', 'hygiene', 'recall'].map((id) => ({ id, title: id, chart: chart(id), options: [{ id: 'a', text: `About ${id}.` }] })),
+        }
+        expect(await renderReportParallel(bundle)).toBe(renderReport(bundle))
+    })
+
+    test('renders block charts byte-for-byte like the inline path', async () => {
+        const bundle: DraftBundle = {
+            ...base,
+            blocks: [
+                { type: 'chart', id: 'production', chart: chart('production') },
+                { type: 'card', blocks: [{ type: 'chart', chart: chart('hygiene') }] },
+            ],
+        }
+        expect(await renderReportParallel(bundle)).toBe(renderReport(bundle))
+    })
+})
@@ -0,0 +1,61 @@
+import { describe, expect, test } from 'bun:test'
+import { sourceAttachment } from '../src/attachments'
+import { redactBundle, redactionPattern } from '../src/redact'
+import { renderReport } from '../src/render'
+import type { DraftBundle } from '../src/types'
+
+const bundle: DraftBundle = {
+    clientName: 'José Smith Dental',
+    date: 'Q3 2026',
+    kpis: [{ name: 'Case Acceptance', value: 62 }],
+    sections: [{ id: 'intro', title: 'Welcome', options: [{ id: 'a', text: 'Dr. José Smith and Zoë reviewed Smithson charts.' }] }],
+    redact: true,
+    redaction: { terms: ['José', 'Smith', 'Zoë'] },
+}
+
+describe('redaction', () => {
+    test('masks terms that start or end with non-ASCII letters, on whole words only', () => {
+        const [option] = redactBundle(bundle).sections[0]!.options!
+        expect(option!.text).toBe('Dr. ████ █████ and ███ reviewed Smithson charts.')
+    })
+
+    test('masks the client name and drops the rules from the redacted bundle', () => {
+        const redacted = redactBundle(bundle)
+        expect(redacted.clientName).toBe('████ █████ Dental')
+        expect('redaction' in redacted).toBe(false)
+    })
+
+    test('keeps the hidden names out of source attachments', () => {
+        const json = sourceAttachment({ ...bundle, attachSource: 'json' })!
+        expect(json.content).not.toContain('José')
+        expect(json.description).not.toContain('José')
+        expect(sourceAttachment({ ...bundle, attachSource: 'csv' })!.description).toBe('KPIs for ████ █████ Dental (Q3 2026)')
+    })
+
+    test('masks captions, column headers, roles, titles, alt texts and form fields', () => {
+        const email = 'leak@example.com'
+        const image = { src: 'data:image/png;base64,iVBORw0KGgo=', alt: `Photo from ${email}` }
+        const html = renderReport({
+            ...bundle,
+            sections: [{ id: 'intro', title: 'Welcome', options: [{ id: 'a', text: 'Hello.' }], image }],
+            growthCategories: [{ id: 'get-new', name: 'Get New', score: 50, confidence: 60, scored: 4, total: 6, chart: image }],
+            blocks: [
+                { type: 'table', caption: `Sent to ${email}`, columns: [`Owner ${email}`], rows: [['x']] },
+                { type: 'quote', text: 'Great.', attribution: 'Ann', role: `Manager, ${email}` },
+                { type: 'contactCard', name: 'Ann', title: `Reach me at ${email}` },
+                { type: 'image', image },
+            ],
+            formFields: [{ id: 'notes', label: `Notes for ${email}`, value: `Reply to ${email}` }],
+        })
+        expect(html).not.toContain(email)
+        expect(html).toContain('████████████████')
+    })
+
+    test('refuses patterns that do not compile or nest quantifiers', () => {
+        expect(redactionPattern('MRN-\\d{6}').test('MRN-123456')).toBe(true)
+        expect(() => redactionPattern('(unclosed')).toThrow('not a valid regular expression')
+        expect(() => redactionPattern('(a+)+$')).toThrow('backtrack')
+        expect(() => redactionPattern('([a-z]+)*x')).toThrow('backtrack')
+        expect(() => redactionPattern('x'.repeat(201))).toThrow('longer than')
+    })
+})
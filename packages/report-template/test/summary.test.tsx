@@ -0,0 +1,161 @@
+import { describe, expect, test } from 'bun:test'
+import { renderReport } from '../src/render'
+import type { DraftBundle } from '../src/types'
+
+const bundle: DraftBundle = {
+    clientName: 'Smile Co',
+    date: 'Q3 2026',
+    kpis: [],
+    sections: [],
+    summaryDetails: [
+        { id: 'a', label: 'A', title: 'Recall', text: 'Recall is behind.', badge: { fill: '#b91c1c', size: 30 } },
+        { id: 'b', label: 'B', title: 'Hygiene', text: 'Hygiene is on track.', badge: { fill: '#fef3c7', color: '#92400e' } },
+        { id: 'c', label: 'C', title: 'Marketing', text: 'Marketing is steady.' },
+    ],
+}
+
+describe('summary badges', () => {
+    test('use per-item fill, text color and size', () => {
+        const html = renderReport(bundle)
+        expect(html).toContain('style="background-color:#b91c1c;border-color:#b91c1c;color:#fff;width:30px;height:30px;font-size:14px">A</span>')
+        expect(html).toContain('style="background-color:#fef3c7;border-color:#fef3c7;color:#92400e">B</span>')
+        expect(html).toContain('<span class="summary-badge">C</span>')
+    })
+
+    test('win over format rules for the same item', () => {
+        const html = renderReport({
+            ...bundle,
+            summaryDetails: [{ id: 'a', label: 'A', title: 'Recall', avgProfit: 10, badge: { color: '#111' } }],
+            rules: [{ target: 'summary', operator: '>', value: 5, style: { color: '#b91c1c', bold: true } }],
+        })
+        expect(html).toContain('style="color:#111;font-weight:700">A</span>')
+    })
+})
+
+describe('summary badge fallbacks', () => {
+    const unlabeled: DraftBundle = {
+        ...bundle,
+        summaryDetails: [
+            { id: 'a', title: 'Recall', text: 'Recall is behind.' },
+            { id: 'b', label: 'B', title: 'Hygiene', text: 'Hygiene is on track.' },
+            { id: 'c', title: 'Marketing', text: 'Marketing is steady.' },
+        ],
+    }
+
+    test('bullet unlabeled items by default', () => {
+        expect(renderReport(unlabeled).match(/<span class="summary-badge">•<\/span>/g)).toHaveLength(2)
+    })
+
+    test('number unlabeled items by position', () => {
+        const html = renderReport({ ...unlabeled, summaryBadges: 'number' })
+        expect(html).toContain('<span class="summary-badge">1</span>')
+        expect(html).toContain('<span class="summary-badge">B</span>')
+        expect(html).toContain('<span class="summary-badge">3</span>')
+    })
+
+    test('draw an icon for unlabeled items', () => {
+        const html = renderReport({ ...unlabeled, summaryBadges: 'icon' })
+        expect(html).not.toContain('•</span>')
+        expect(html).toContain('<span class="summary-badge">B</span>')
+        expect(html.match(/<span class="summary-badge"><svg/g)).toHaveLength(2)
+    })
+})
+
+describe('methodology appendix', () => {
+    test('is appended with the report score bands and listed in the contents', () => {
+        const html = renderReport({
+            ...bundle,
+            tableOfContents: true,
+            includeMethodology: true,
+            scoreBands: [
+                { min: 0, label: 'Low', color: '#dc2626' },
+                { min: 50, label: 'High', color: '#16a34a' },
+            ],
+        })
+        expect(html).toContain('<h2 id="methodology">Methodology</h2>')
+        expect(html).toContain('href="#methodology"')
+        expect(html).toContain('<strong>Low</strong>: 0 to below 50')
+        expect(html).toContain('<strong>High</strong>: 50 and above')
+        expect(html.indexOf('id="methodology"')).toBeGreaterThan(html.indexOf('quote-signature'))
+    })
+
+    test('is left out unless requested', () => {
+        expect(renderReport(bundle)).not.toContain('id="methodology"')
+    })
+})
+
+describe('band labels', () => {
+    const scored: DraftBundle = {
+        ...bundle,
+        summaryDetails: [],
+        kpis: [
+            { name: 'Case Acceptance', value: 62 },
+            { name: 'New Patients', value: 45, valueType: 'count' },
+        ],
+        growthCategories: [{ id: 'a', name: 'Recall', score: 82, confidence: 60, scored: 3, total: 5 }],
+        bandLabels: true,
+    }
+
+    test('follow the score bands on percentage KPI and category bars', () => {
+        const html = renderReport(scored)
+        expect(html).toContain('<span class="band-label" style="color:#f59e0b">Developing</span>')
+        expect(html).toContain('<span class="band-label" style="color:#16a34a">Strong</span>')
+        expect(html.match(/class="band-label"/g)).toHaveLength(2)
+    })
+
+    test('use custom band thresholds and labels', () => {
+        const html = renderReport({
+            ...scored,
+            scoreBands: [
+                { min: 0, label: 'At risk', color: '#dc2626' },
+                { min: 50, label: 'On track', color: '#f59e0b' },
+                { min: 80, label: 'Excelling', color: '#16a34a' },
+            ],
+        })
+        expect(html).toContain('>On track</span>')
+        expect(html).toContain('>Excelling</span>')
+    })
+
+    test('are off by default', () => {
+        expect(renderReport({ ...scored, bandLabels: undefined })).not.toContain('class="band-label"')
+    })
+})
+
+describe('score whiskers', () => {
+    const categories = [
+        { id: 'a', name: 'Recall', score: 82, scoreLow: 70, scoreHigh: 91, confidence: 40, scored: 2, total: 5 },
+        { id: 'b', name: 'Hygiene', score: 64, confidence: 90, scored: 5, total: 5 },
+    ]
+
+    test('span the bounds on the bars of categories that have them', () => {
+        const html = renderReport({ clientName: 'Smile Co', date: 'Q3 2026', kpis: [], sections: [], growthCategories: categories })
+        expect(html.match(/class="score-whisker"/g)).toHaveLength(1)
+        expect(html).toMatch(/class="score-whisker"[^>]*style="[^"]*left:70%;width:21%/)
+    })
+
+    test('clamp bounds to the score scale', () => {
+        const html = renderReport({
+            clientName: 'Smile Co',
+            date: 'Q3 2026',
+            kpis: [],
+            sections: [],
+            growthCategories: [{ ...categories[0]!, scoreLow: -10, scoreHigh: 120 }],
+        })
+        expect(html).toMatch(/class="score-whisker"[^>]*style="[^"]*left:0%;width:100%/)
+    })
+})
+
+describe('category table breaks', () => {
+    const categories = [
+        { id: 'a', name: 'Recall', score: 82, confidence: 40, scored: 2, total: 5 },
+        { id: 'b', name: 'Hygiene', score: 64, confidence: 90, scored: 5, total: 5 },
+    ]
+    const render = (growthCategories: DraftBundle['growthCategories']) =>
+        renderReport({ clientName: 'Smile Co', date: 'Q3 2026', kpis: [], sections: [], growthCategories })
+
+    test('only keep grouped bodies together, so an ungrouped table can split across pages', () => {
+        expect(render(categories)).not.toContain('category-group-body')
+        const grouped = render(categories.map((category) => ({ ...category, group: 'Retention' })))
+        expect(grouped).toContain('<tbody class="category-group-body">')
+    })
+})
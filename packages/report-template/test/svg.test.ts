@@ -0,0 +1,31 @@
+import { describe, expect, test } from 'bun:test'
+import { sanitizeSvg } from '../src/svg'
+
+describe('svg sanitizing', () => {
+    test('keeps drawing markup and internal references', () => {
+        const svg =
+            '<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10"><linearGradient id="g"><stop offset="0" stop-color="red"/></linearGradient><rect width="10" height="10" fill="url(#g)"/><text>A &amp; B</text></svg>'
+        expect(sanitizeSvg(`<?xml version="1.0"?>${svg}`)).toBe(svg)
+    })
+
+    test('drops handlers, script URLs, animation and foreign content however they are spelled', () => {
+        const markup = sanitizeSvg(
+            '<SVG><Rect ONCLICK="alert(1)" onload=alert(1) width="1"/><a xlink:href="javascript:alert(1)"><text>Hi</text></a>' +
+                '<use href="&#106;ava&#x09;script:alert(1)"/><animate attributeName="href" to="javascript:alert(1)"/>' +
+                '<set attributeName="onclick" to="alert(1)"></set><foreignObject><div onclick="x">hi</div></foreignObject>' +
+                '<script>alert(1)</script><image href="https://tracker.example/pixel.png"/><rect fill="url(https://x.example/p)"/></SVG>'
+        )
+        expect(markup).toBe('<svg><rect width="1"/><text>Hi</text><use/><image/><rect/></svg>')
+    })
+
+    test('keeps class styles but not stylesheets that fetch', () => {
+        expect(sanitizeSvg('<svg><style>.a{fill:#0af}</style><path class="a" d="M0 0h1"/></svg>')).toBe(
+            '<svg><style>.a{fill:#0af}</style><path class="a" d="M0 0h1"/></svg>'
+        )
+        expect(sanitizeSvg('<svg><style>@import url(https://x.example/a.css);</style></svg>')).toBe('<svg><style></style></svg>')
+    })
+
+    test('returns nothing without an svg root', () => {
+        expect(sanitizeSvg('<img src=x onerror=alert(1)>')).toBe('')
+    })
+})
@@ -0,0 +1,54 @@
+import { describe, expect, test } from 'bun:test'
+import { baselineRules, shapingRules, themeVariables } from '../src/theme'
+
+describe('spacing scale', () => {
+    test('defaults match the historical spacings', () => {
+        const css = themeVariables()
+        expect(css).toContain('--rf-space-xs: 2px;')
+        expect(css).toContain('--rf-space-l: 10px;')
+    })
+
+    test('density scales every step', () => {
+        expect(themeVariables({ spacing: { density: 'compact' } })).toContain('--rf-space-l: 7.5px;')
+        expect(themeVariables({ spacing: { density: 'comfortable' } })).toContain('--rf-space-s: 5.4px;')
+    })
+
+    test('individual steps can be overridden before density applies', () => {
+        const css = themeVariables({ spacing: { density: 'compact', scale: { xl: 20 } } })
+        expect(css).toContain('--rf-space-xl: 15px;')
+        expect(css).toContain('--rf-space-m: 4.5px;')
+    })
+})
+
+describe('baseline grid', () => {
+    const theme = { typography: { baselineGrid: 12 } }
+
+    test('snaps spacing steps to half-lines', () => {
+        const css = themeVariables(theme)
+        expect(css).toContain('--rf-space-xs: 6px;')
+        expect(css).toContain('--rf-space-l: 12px;')
+    })
+
+    test('sets line boxes to whole grid steps', () => {
+        const css = baselineRules(theme)
+        expect(css).toContain('line-height:12px !important')
+        expect(css).toContain('h1{ line-height:24px; }')
+        expect(baselineRules()).toBe('')
+    })
+})
+
+describe('text shaping', () => {
+    test('kerning and ligatures are on by default and headings drop tracking', () => {
+        const css = shapingRules()
+        expect(css).toContain('font-kerning:normal')
+        expect(css).toContain('font-variant-ligatures:common-ligatures contextual')
+        expect(css).toContain('h1, h2, h3, h4{ letter-spacing:normal; }')
+    })
+
+    test('either can be turned off', () => {
+        const css = shapingRules({ typography: { kerning: false, ligatures: false } })
+        expect(css).toContain('font-kerning:none')
+        expect(css).toContain('font-variant-ligatures:none')
+        expect(css).not.toContain('letter-spacing')
+    })
+})